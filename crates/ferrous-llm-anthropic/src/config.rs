@@ -7,6 +7,7 @@ use url::Url;
 
 /// Configuration for the Anthropic provider.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AnthropicConfig {
     /// Anthropic API key
     pub api_key: SecretString,
@@ -108,6 +109,16 @@ impl AnthropicConfig {
         }
     }
 
+    /// Get the models endpoint URL.
+    pub fn models_url(&self) -> String {
+        let base = self.base_url();
+        if base.ends_with('/') {
+            format!("{base}v1/models")
+        } else {
+            format!("{base}/v1/models")
+        }
+    }
+
     /// Load configuration from environment variables.
     pub fn from_env() -> Result<Self, ConfigError> {
         use ferrous_llm_core::env;
@@ -237,6 +248,7 @@ mod tests {
             config.messages_url(),
             "https://api.anthropic.com/v1/messages"
         );
+        assert_eq!(config.models_url(), "https://api.anthropic.com/v1/models");
     }
 
     #[test]
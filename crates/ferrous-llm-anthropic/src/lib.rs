@@ -6,17 +6,21 @@
 pub mod config;
 pub mod error;
 pub mod provider;
+mod stream;
 pub mod types;
 
 // Re-export main types for convenience
 pub use config::AnthropicConfig;
 pub use error::AnthropicError;
-pub use provider::AnthropicProvider;
+pub use provider::{AnthropicProvider, AnthropicStream};
 pub use types::{
     AnthropicContent, AnthropicContentBlock, AnthropicMessage, AnthropicMessagesRequest,
-    AnthropicMessagesResponse, AnthropicStreamChunk, AnthropicTool, AnthropicToolChoice,
-    AnthropicUsage,
+    AnthropicMessagesResponse, AnthropicModel, AnthropicModelList, AnthropicStreamChunk,
+    AnthropicTool, AnthropicToolChoice, AnthropicUsage,
 };
 
 // Re-export core traits
-pub use ferrous_llm_core::{ChatProvider, StreamingProvider, ToolProvider};
+pub use ferrous_llm_core::{
+    ChatProvider, ModelCatalog, ResponseSchema, StreamEvent, StreamingProvider,
+    StreamingToolProvider, StructuredProvider, ToolProvider,
+};
@@ -1,20 +1,26 @@
 //! Anthropic provider implementation.
 
-use crate::{config::AnthropicConfig, error::AnthropicError, types::*};
+use crate::{config::AnthropicConfig, error::AnthropicError, stream::StreamParser, types::*};
 use async_trait::async_trait;
 use ferrous_llm_core::{
-    ChatProvider, ChatRequest, ProviderResult, StreamingProvider, Tool, ToolProvider,
+    ChatProvider, ChatRequest, ModelCatalog, ModelInfo, Parameters, ProviderResult, ResponseSchema,
+    RetryConfig, StreamEvent, StreamingProvider, StreamingToolProvider, StructuredProvider, Tool,
+    ToolChoice, ToolProvider, parse_retry_after, retry,
 };
 use futures::Stream;
 use reqwest::{Client, RequestBuilder};
 use std::pin::Pin;
 use tokio_stream::{StreamExt, wrappers::ReceiverStream};
 
+/// Stream of events returned by [`AnthropicProvider`] streaming requests.
+pub type AnthropicStream = Pin<Box<dyn Stream<Item = Result<StreamEvent, AnthropicError>> + Send>>;
+
 /// Anthropic provider implementation.
 #[derive(Debug, Clone)]
 pub struct AnthropicProvider {
     config: AnthropicConfig,
     client: Client,
+    retry: RetryConfig,
 }
 
 impl AnthropicProvider {
@@ -102,7 +108,11 @@ impl AnthropicProvider {
             .build()
             .map_err(|e| AnthropicError::Network { source: e })?;
 
-        Ok(Self { config, client })
+        Ok(Self {
+            retry: RetryConfig::from(&config.http),
+            config,
+            client,
+        })
     }
 
     /// Create a request builder with common settings.
@@ -123,24 +133,95 @@ impl AnthropicProvider {
                 .await
                 .map_err(|e| AnthropicError::Network { source: e })
         } else {
-            let body = response.text().await.unwrap_or_default();
-            Err(AnthropicError::from_response(status.as_u16(), &body))
+            Err(Self::error_from_response(response).await)
         }
     }
 
+    /// Convert an unsuccessful HTTP response into an error.
+    async fn error_from_response(response: reqwest::Response) -> AnthropicError {
+        let status = response.status().as_u16();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_retry_after);
+        let body = response.text().await.unwrap_or_default();
+        AnthropicError::from_response(status, &body).with_retry_after(retry_after)
+    }
+
+    /// POST a JSON body and decode the response, retrying transient failures.
+    async fn post_json<B, T>(&self, url: &str, body: &B) -> Result<T, AnthropicError>
+    where
+        B: serde::Serialize + ?Sized + Sync,
+        T: serde::de::DeserializeOwned,
+    {
+        retry(&self.retry, || async move {
+            let response = self
+                .request_builder(reqwest::Method::POST, url)
+                .json(body)
+                .send()
+                .await
+                .map_err(|e| AnthropicError::Network { source: e })?;
+
+            self.handle_response(response).await
+        })
+        .await
+    }
+
+    /// GET a JSON response, retrying transient failures.
+    async fn get_json<T>(&self, url: &str) -> Result<T, AnthropicError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        retry(&self.retry, || async move {
+            let response = self
+                .request_builder(reqwest::Method::GET, url)
+                .send()
+                .await
+                .map_err(|e| AnthropicError::Network { source: e })?;
+
+            self.handle_response(response).await
+        })
+        .await
+    }
+
+    /// POST a JSON body for a streaming response, retrying transient failures
+    /// until the connection is established.
+    async fn post_stream<B>(&self, url: &str, body: &B) -> Result<reqwest::Response, AnthropicError>
+    where
+        B: serde::Serialize + ?Sized + Sync,
+    {
+        retry(&self.retry, || async move {
+            let response = self
+                .request_builder(reqwest::Method::POST, url)
+                .json(body)
+                .send()
+                .await
+                .map_err(|e| AnthropicError::Network { source: e })?;
+
+            if response.status().is_success() {
+                Ok(response)
+            } else {
+                Err(Self::error_from_response(response).await)
+            }
+        })
+        .await
+    }
+
     /// Convert core ChatRequest to Anthropic format.
     fn convert_chat_request(&self, request: &ChatRequest) -> AnthropicMessagesRequest {
-        let mut system_message = None;
+        let mut system_texts = Vec::new();
         let mut messages = Vec::new();
 
-        // Separate system messages from other messages
+        // Separate system messages from other messages; Anthropic takes a
+        // single system prompt, so several system messages are joined
         for message in &request.messages {
             if message.role == ferrous_llm_core::Role::System {
                 if let ferrous_llm_core::MessageContent::Text(text) = &message.content {
-                    system_message = Some(text.clone());
+                    system_texts.push(text.as_str());
                 }
             } else {
-                messages.push(message.into());
+                Self::push_message(&mut messages, message.into());
             }
         }
 
@@ -148,7 +229,7 @@ impl AnthropicProvider {
             model: self.config.model.clone(),
             max_tokens: request.parameters.max_tokens.unwrap_or(4096), // Anthropic requires max_tokens
             messages,
-            system: system_message,
+            system: (!system_texts.is_empty()).then(|| system_texts.join("\n\n")),
             temperature: request.parameters.temperature,
             top_p: request.parameters.top_p,
             top_k: None, // Anthropic-specific parameter, not in core
@@ -158,58 +239,66 @@ impl AnthropicProvider {
             tool_choice: None,
         }
     }
-}
-
-#[async_trait]
-impl ChatProvider for AnthropicProvider {
-    type Config = AnthropicConfig;
-    type Response = AnthropicMessagesResponse;
-    type Error = AnthropicError;
-
-    async fn chat(&self, request: ChatRequest) -> ProviderResult<Self::Response, Self::Error> {
-        let anthropic_request = self.convert_chat_request(&request);
-
-        let response = self
-            .request_builder(reqwest::Method::POST, &self.config.messages_url())
-            .json(&anthropic_request)
-            .send()
-            .await
-            .map_err(|e| AnthropicError::Network { source: e })?;
 
-        self.handle_response(response).await
+    /// Append a message, merging consecutive tool results into a single user
+    /// turn as the Messages API requires.
+    fn push_message(messages: &mut Vec<AnthropicMessage>, message: AnthropicMessage) {
+        if let Some(previous) = messages.last_mut()
+            && previous.is_tool_result()
+            && message.is_tool_result()
+            && let (AnthropicContent::Blocks(previous), AnthropicContent::Blocks(blocks)) =
+                (&mut previous.content, &message.content)
+        {
+            previous.extend(blocks.iter().cloned());
+            return;
+        }
+        messages.push(message);
     }
-}
-
-#[async_trait]
-impl StreamingProvider for AnthropicProvider {
-    type StreamItem = String;
-    type Stream = Pin<Box<dyn Stream<Item = Result<Self::StreamItem, Self::Error>> + Send>>;
 
-    async fn chat_stream(&self, request: ChatRequest) -> ProviderResult<Self::Stream, Self::Error> {
-        let mut anthropic_request = self.convert_chat_request(&request);
-        anthropic_request.stream = Some(true);
+    /// Attach tool definitions and the tool choice to a messages request.
+    fn apply_tools(
+        request: &mut AnthropicMessagesRequest,
+        tools: &[Tool],
+        parameters: &Parameters,
+    ) {
+        if !tools.is_empty() {
+            let disable_parallel_tool_use =
+                parameters.parallel_tool_calls.map(|parallel| !parallel);
+            request.tools = Some(tools.iter().map(|t| t.into()).collect());
+            request.tool_choice = Some(match parameters.tool_choice.clone().unwrap_or_default() {
+                ToolChoice::Auto => AnthropicToolChoice::Auto {
+                    disable_parallel_tool_use,
+                },
+                ToolChoice::None => AnthropicToolChoice::None,
+                ToolChoice::Required => AnthropicToolChoice::Any {
+                    disable_parallel_tool_use,
+                },
+                ToolChoice::Function { name } => AnthropicToolChoice::Tool {
+                    name,
+                    disable_parallel_tool_use,
+                },
+            });
+        }
+    }
 
+    /// Send a streaming messages request and parse the SSE response into events.
+    async fn stream_messages(
+        &self,
+        anthropic_request: AnthropicMessagesRequest,
+    ) -> Result<AnthropicStream, AnthropicError> {
         let response = self
-            .request_builder(reqwest::Method::POST, &self.config.messages_url())
-            .json(&anthropic_request)
-            .send()
-            .await
-            .map_err(|e| AnthropicError::Network { source: e })?;
-
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let body = response.text().await.unwrap_or_default();
-            return Err(AnthropicError::from_response(status, &body));
-        }
+            .post_stream(&self.config.messages_url(), &anthropic_request)
+            .await?;
 
         // Create a tokio channel for streaming
-        let (tx, rx) = tokio::sync::mpsc::channel::<Result<String, AnthropicError>>(100);
+        let (tx, rx) = tokio::sync::mpsc::channel::<Result<StreamEvent, AnthropicError>>(100);
 
         // Spawn a task to process the SSE stream
         let tx_clone = tx.clone();
         tokio::spawn(async move {
             let mut byte_stream = response.bytes_stream();
             let mut buffer = Vec::new();
+            let mut parser = StreamParser::new();
 
             while let Some(chunk_result) = byte_stream.next().await {
                 match chunk_result {
@@ -220,56 +309,17 @@ impl StreamingProvider for AnthropicProvider {
                         let mut start = 0;
                         while let Some(pos) = buffer[start..].iter().position(|&b| b == b'\n') {
                             let line_end = start + pos;
-                            let line = String::from_utf8_lossy(&buffer[start..line_end])
-                                .trim()
-                                .to_string();
+                            let line = String::from_utf8_lossy(&buffer[start..line_end]);
                             start = line_end + 1;
 
-                            // Process SSE format: "data: {json}" or "event: message_stop"
-                            if let Some(data) = line.strip_prefix("data: ") {
-                                // Try to parse the JSON chunk
-                                if let Ok(chunk) =
-                                    serde_json::from_str::<AnthropicStreamChunk>(data)
-                                {
-                                    match chunk {
-                                        AnthropicStreamChunk::ContentBlockDelta {
-                                            delta, ..
-                                        } => {
-                                            match delta {
-                                                AnthropicContentDelta::TextDelta { text } => {
-                                                    if !text.is_empty()
-                                                        && tx_clone.send(Ok(text)).await.is_err()
-                                                    {
-                                                        // Receiver dropped
-                                                        return;
-                                                    }
-                                                }
-                                                AnthropicContentDelta::InputJsonDelta {
-                                                    ..
-                                                } => {
-                                                    // TODO: Handle input json delta
-                                                }
-                                            }
-                                        }
-                                        AnthropicStreamChunk::MessageStop => {
-                                            // End of stream
-                                            drop(tx_clone);
-                                            return;
-                                        }
-                                        AnthropicStreamChunk::Error { error } => {
-                                            let _ = tx_clone
-                                                .send(Err(AnthropicError::Other {
-                                                    message: error.message,
-                                                }))
-                                                .await;
-                                            return;
-                                        }
-                                        _ => {} // Handle other chunk types if needed
-                                    }
+                            for event in parser.parse_line(&line) {
+                                if tx_clone.send(Ok(event)).await.is_err() {
+                                    // Receiver dropped
+                                    return;
                                 }
-                            } else if line.starts_with("event: message_stop") {
-                                // Alternative way to detect end of stream
-                                drop(tx_clone);
+                            }
+
+                            if parser.is_done() {
                                 return;
                             }
                         }
@@ -297,6 +347,63 @@ impl StreamingProvider for AnthropicProvider {
     }
 }
 
+#[async_trait]
+impl ChatProvider for AnthropicProvider {
+    type Config = AnthropicConfig;
+    type Response = AnthropicMessagesResponse;
+    type Error = AnthropicError;
+
+    async fn chat(&self, request: ChatRequest) -> ProviderResult<Self::Response, Self::Error> {
+        let anthropic_request = self.convert_chat_request(&request);
+
+        self.post_json(&self.config.messages_url(), &anthropic_request)
+            .await
+    }
+}
+
+#[async_trait]
+impl ModelCatalog for AnthropicProvider {
+    type Error = AnthropicError;
+
+    /// List all models, following pagination.
+    async fn list_models(&self) -> ProviderResult<Vec<ModelInfo>, Self::Error> {
+        let mut models = Vec::new();
+        let mut after_id: Option<String> = None;
+        loop {
+            let mut url = format!("{}?limit=1000", self.config.models_url());
+            if let Some(after_id) = &after_id {
+                url.push_str(&format!("&after_id={after_id}"));
+            }
+
+            let page: AnthropicModelList = self.get_json(&url).await?;
+            models.extend(page.data.into_iter().map(ModelInfo::from));
+            match page.last_id {
+                Some(last_id) if page.has_more => after_id = Some(last_id),
+                _ => return Ok(models),
+            }
+        }
+    }
+
+    async fn model_info(&self, model: &str) -> ProviderResult<ModelInfo, Self::Error> {
+        let url = format!("{}/{model}", self.config.models_url());
+        let model: AnthropicModel = self.get_json(&url).await?;
+        Ok(model.into())
+    }
+}
+
+#[async_trait]
+impl StreamingProvider for AnthropicProvider {
+    type StreamItem = StreamEvent;
+    type Stream = AnthropicStream;
+
+    async fn chat_stream(&self, request: ChatRequest) -> ProviderResult<Self::Stream, Self::Error> {
+        let mut anthropic_request = self.convert_chat_request(&request);
+        anthropic_request.stream = Some(true);
+
+        self.stream_messages(anthropic_request).await
+    }
+}
+
 #[async_trait]
 impl ToolProvider for AnthropicProvider {
     async fn chat_with_tools(
@@ -305,27 +412,55 @@ impl ToolProvider for AnthropicProvider {
         tools: &[Tool],
     ) -> ProviderResult<Self::Response, Self::Error> {
         let mut anthropic_request = self.convert_chat_request(&request);
+        Self::apply_tools(&mut anthropic_request, tools, &request.parameters);
 
-        if !tools.is_empty() {
-            anthropic_request.tools = Some(tools.iter().map(|t| t.into()).collect());
-            anthropic_request.tool_choice = Some(AnthropicToolChoice::Auto);
-        }
-
-        let response = self
-            .request_builder(reqwest::Method::POST, &self.config.messages_url())
-            .json(&anthropic_request)
-            .send()
+        self.post_json(&self.config.messages_url(), &anthropic_request)
             .await
-            .map_err(|e| AnthropicError::Network { source: e })?;
+    }
+}
+
+#[async_trait]
+impl StreamingToolProvider for AnthropicProvider {
+    async fn chat_stream_with_tools(
+        &self,
+        request: ChatRequest,
+        tools: &[Tool],
+    ) -> ProviderResult<Self::Stream, Self::Error> {
+        let mut anthropic_request = self.convert_chat_request(&request);
+        anthropic_request.stream = Some(true);
+        Self::apply_tools(&mut anthropic_request, tools, &request.parameters);
+
+        self.stream_messages(anthropic_request).await
+    }
+}
+
+#[async_trait]
+impl StructuredProvider for AnthropicProvider {
+    async fn chat_structured(
+        &self,
+        request: ChatRequest,
+        schema: &ResponseSchema,
+    ) -> ProviderResult<Self::Response, Self::Error> {
+        // Claude has no JSON mode, so force a call to a tool taking the schema
+        let mut anthropic_request = self.convert_chat_request(&request);
+        anthropic_request.tools = Some(vec![(&schema.as_tool()).into()]);
+        anthropic_request.tool_choice = Some(AnthropicToolChoice::Tool {
+            name: schema.name.clone(),
+            disable_parallel_tool_use: None,
+        });
 
-        self.handle_response(response).await
+        self.post_json(&self.config.messages_url(), &anthropic_request)
+            .await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ferrous_llm_core::{Message, Metadata, Parameters};
+    use ferrous_llm_core::{
+        ContentPart, FunctionCall, Message, MessageContent, Metadata, Parameters, Role, ToolCall,
+    };
+    use serde_json::json;
 
     fn create_test_config() -> AnthropicConfig {
         AnthropicConfig::new("sk-ant-test123456789", "claude-3-5-sonnet-20241022")
@@ -366,4 +501,130 @@ mod tests {
             Some("You are a helpful assistant".to_string())
         );
     }
+
+    #[test]
+    fn test_apply_tools_tool_choice() {
+        let provider = AnthropicProvider::new(create_test_config()).unwrap();
+        let tools = [Tool::function(
+            "extract",
+            "Extract fields",
+            json!({"type": "object"}),
+        )];
+
+        let request = ChatRequest::builder().user_message("Hi").build();
+        let mut anthropic_request = provider.convert_chat_request(&request);
+        AnthropicProvider::apply_tools(&mut anthropic_request, &tools, &request.parameters);
+        let body = serde_json::to_value(&anthropic_request).unwrap();
+        assert_eq!(body["tool_choice"], json!({"type": "auto"}));
+
+        let request = ChatRequest::builder()
+            .user_message("Hi")
+            .tool_choice(ToolChoice::Required)
+            .parallel_tool_calls(false)
+            .build();
+        let mut anthropic_request = provider.convert_chat_request(&request);
+        AnthropicProvider::apply_tools(&mut anthropic_request, &tools, &request.parameters);
+        let body = serde_json::to_value(&anthropic_request).unwrap();
+        assert_eq!(
+            body["tool_choice"],
+            json!({"type": "any", "disable_parallel_tool_use": true})
+        );
+
+        let request = ChatRequest::builder()
+            .user_message("Hi")
+            .tool_choice(ToolChoice::None)
+            .build();
+        let mut anthropic_request = provider.convert_chat_request(&request);
+        AnthropicProvider::apply_tools(&mut anthropic_request, &tools, &request.parameters);
+        let body = serde_json::to_value(&anthropic_request).unwrap();
+        assert_eq!(body["tool_choice"], json!({"type": "none"}));
+        assert_eq!(body["tools"][0]["name"], "extract");
+    }
+
+    #[test]
+    fn test_convert_image_sources() {
+        let message = Message {
+            role: Role::User,
+            content: MessageContent::Multimodal(vec![
+                ContentPart::image_url("https://example.com/cat.png"),
+                ContentPart::image_url("data:image/jpeg;base64,/9j/4AAQ"),
+            ]),
+        };
+
+        let body = serde_json::to_value(AnthropicMessage::from(&message)).unwrap();
+        assert_eq!(
+            body["content"],
+            json!([
+                {"type": "image", "source": {"type": "url", "url": "https://example.com/cat.png"}},
+                {"type": "image", "source": {"type": "base64", "media_type": "image/jpeg", "data": "/9j/4AAQ"}}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_convert_tool_history() {
+        let config = create_test_config();
+        let provider = AnthropicProvider::new(config).unwrap();
+
+        let tool_call = |id: &str, city: &str| ToolCall {
+            id: id.to_string(),
+            call_type: "function".to_string(),
+            function: FunctionCall {
+                name: "get_weather".to_string(),
+                arguments: format!(r#"{{"city": "{city}"}}"#),
+            },
+        };
+        let request = ChatRequest::builder()
+            .user_message("Weather in Paris and Rome?")
+            .assistant_with_tools(
+                "Let me check.",
+                vec![tool_call("toolu_1", "Paris"), tool_call("toolu_2", "Rome")],
+            )
+            .tool_response("Sunny", "toolu_1")
+            .tool_response("Rainy", "toolu_2")
+            .build();
+
+        let anthropic_request = provider.convert_chat_request(&request);
+        let body = serde_json::to_value(&anthropic_request.messages).unwrap();
+        assert_eq!(
+            body,
+            json!([
+                {"role": "user", "content": "Weather in Paris and Rome?"},
+                {"role": "assistant", "content": [
+                    {"type": "text", "text": "Let me check."},
+                    {"type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": {"city": "Paris"}},
+                    {"type": "tool_use", "id": "toolu_2", "name": "get_weather", "input": {"city": "Rome"}}
+                ]},
+                {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "toolu_1", "content": "Sunny"},
+                    {"type": "tool_result", "tool_use_id": "toolu_2", "content": "Rainy"}
+                ]}
+            ])
+        );
+
+        // Converting back restores one core message per tool result
+        let messages: Vec<Message> = anthropic_request
+            .messages
+            .iter()
+            .flat_map(AnthropicMessage::to_core_messages)
+            .collect();
+        assert_eq!(messages.len(), 4);
+        match &messages[1].content {
+            MessageContent::Tool(content) => {
+                assert_eq!(content.text.as_deref(), Some("Let me check."));
+                let calls = content.tool_calls.as_ref().unwrap();
+                assert_eq!(calls[1].id, "toolu_2");
+                assert_eq!(calls[1].function.arguments, r#"{"city":"Rome"}"#);
+            }
+            other => panic!("unexpected content: {other:?}"),
+        }
+        assert_eq!(messages[3].role, Role::Tool);
+        match &messages[3].content {
+            MessageContent::Tool(content) => {
+                assert_eq!(content.tool_call_id.as_deref(), Some("toolu_2"));
+                assert_eq!(content.text.as_deref(), Some("Rainy"));
+            }
+            other => panic!("unexpected content: {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,278 @@
+//! Parsing of Anthropic server-sent event streams into core stream events.
+
+use crate::types::{
+    AnthropicContentBlock, AnthropicContentDelta, AnthropicStreamChunk, AnthropicUsage,
+    map_stop_reason,
+};
+use ferrous_llm_core::{FunctionCall, StreamEvent, ToolCall, Usage};
+use std::collections::HashMap;
+
+/// Incremental parser for the Anthropic messages SSE format.
+#[derive(Debug, Default)]
+pub(crate) struct StreamParser {
+    input_tokens: u32,
+    /// Tool-use blocks in progress, keyed by content block index
+    tool_blocks: HashMap<u32, ToolBlock>,
+    tool_count: usize,
+    done: bool,
+}
+
+/// A `tool_use` content block whose input is still being streamed.
+#[derive(Debug)]
+struct ToolBlock {
+    index: usize,
+    id: String,
+    name: String,
+    initial_input: serde_json::Value,
+    partial_json: String,
+}
+
+impl ToolBlock {
+    fn into_tool_call(self) -> ToolCall {
+        // Tools without arguments stream no deltas; fall back to the initial input
+        let arguments = if self.partial_json.trim().is_empty() {
+            self.initial_input.to_string()
+        } else {
+            self.partial_json
+        };
+
+        ToolCall {
+            id: self.id,
+            call_type: "function".to_string(),
+            function: FunctionCall {
+                name: self.name,
+                arguments,
+            },
+        }
+    }
+}
+
+impl StreamParser {
+    /// Create a new stream parser.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the stream has terminated, either normally or with an error.
+    pub(crate) fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Parse a single SSE line and return the events it produces.
+    pub(crate) fn parse_line(&mut self, line: &str) -> Vec<StreamEvent> {
+        let Some(data) = line.trim().strip_prefix("data:").map(str::trim) else {
+            return Vec::new();
+        };
+
+        match serde_json::from_str::<AnthropicStreamChunk>(data) {
+            Ok(chunk) => self.parse_chunk(chunk),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn parse_chunk(&mut self, chunk: AnthropicStreamChunk) -> Vec<StreamEvent> {
+        match chunk {
+            AnthropicStreamChunk::MessageStart { message } => {
+                self.input_tokens = message.usage.input_tokens;
+                vec![StreamEvent::MessageStart {
+                    id: Some(message.id),
+                    model: Some(message.model),
+                }]
+            }
+            AnthropicStreamChunk::ContentBlockStart {
+                index,
+                content_block: AnthropicContentBlock::ToolUse { id, name, input },
+            } => {
+                let tool_index = self.tool_count;
+                self.tool_count += 1;
+                self.tool_blocks.insert(
+                    index,
+                    ToolBlock {
+                        index: tool_index,
+                        id: id.clone(),
+                        name: name.clone(),
+                        initial_input: input,
+                        partial_json: String::new(),
+                    },
+                );
+                vec![StreamEvent::ToolCallStart {
+                    index: tool_index,
+                    id,
+                    name,
+                }]
+            }
+            AnthropicStreamChunk::ContentBlockDelta {
+                delta: AnthropicContentDelta::TextDelta { text },
+                ..
+            } if !text.is_empty() => vec![StreamEvent::TextDelta { text }],
+            AnthropicStreamChunk::ContentBlockDelta {
+                index,
+                delta: AnthropicContentDelta::InputJsonDelta { partial_json },
+            } => match self.tool_blocks.get_mut(&index) {
+                Some(block) if !partial_json.is_empty() => {
+                    block.partial_json.push_str(&partial_json);
+                    vec![StreamEvent::ToolCallDelta {
+                        index: block.index,
+                        arguments: partial_json,
+                    }]
+                }
+                _ => Vec::new(),
+            },
+            AnthropicStreamChunk::ContentBlockStop { index } => {
+                match self.tool_blocks.remove(&index) {
+                    Some(block) => vec![StreamEvent::ToolCallEnd {
+                        index: block.index,
+                        tool_call: block.into_tool_call(),
+                    }],
+                    None => Vec::new(),
+                }
+            }
+            AnthropicStreamChunk::MessageDelta { delta, usage } => {
+                let mut events = vec![StreamEvent::Usage {
+                    usage: self.usage(&usage),
+                }];
+                if let Some(reason) = delta.stop_reason.as_deref().and_then(map_stop_reason) {
+                    events.push(StreamEvent::Finish { reason });
+                }
+                events
+            }
+            AnthropicStreamChunk::MessageStop => {
+                self.done = true;
+                vec![StreamEvent::Done]
+            }
+            AnthropicStreamChunk::Error { error } => {
+                self.done = true;
+                vec![StreamEvent::Error {
+                    message: error.message,
+                }]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Combine the input tokens reported at message start with the
+    /// cumulative output tokens reported by a message delta.
+    fn usage(&self, usage: &AnthropicUsage) -> Usage {
+        let input_tokens = self.input_tokens.max(usage.input_tokens);
+        Usage {
+            prompt_tokens: input_tokens,
+            completion_tokens: usage.output_tokens,
+            total_tokens: input_tokens + usage.output_tokens,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferrous_llm_core::FinishReason;
+
+    #[test]
+    fn test_text_stream() {
+        let mut parser = StreamParser::new();
+
+        let events = parser.parse_line(
+            r#"data: {"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-3-5-sonnet-20241022","stop_reason":null,"stop_sequence":null,"usage":{"input_tokens":25,"output_tokens":1}}}"#,
+        );
+        assert!(matches!(
+            events.as_slice(),
+            [StreamEvent::MessageStart { id: Some(id), .. }] if id == "msg_1"
+        ));
+
+        assert!(parser.parse_line("event: content_block_delta").is_empty());
+        let events = parser.parse_line(
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hello"}}"#,
+        );
+        assert_eq!(events[0].text(), Some("Hello"));
+
+        let events = parser.parse_line(
+            r#"data: {"type":"message_delta","delta":{"stop_reason":"end_turn","stop_sequence":null},"usage":{"output_tokens":15}}"#,
+        );
+        match events.as_slice() {
+            [
+                StreamEvent::Usage { usage },
+                StreamEvent::Finish {
+                    reason: FinishReason::Stop,
+                },
+            ] => {
+                assert_eq!(usage.prompt_tokens, 25);
+                assert_eq!(usage.completion_tokens, 15);
+                assert_eq!(usage.total_tokens, 40);
+            }
+            other => panic!("unexpected events: {other:?}"),
+        }
+
+        let events = parser.parse_line(r#"data: {"type":"message_stop"}"#);
+        assert!(events[0].is_done());
+        assert!(parser.is_done());
+    }
+
+    #[test]
+    fn test_tool_use_stream() {
+        let mut parser = StreamParser::new();
+        let lines = [
+            r#"data: {"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Checking."}}"#,
+            r#"data: {"type":"content_block_stop","index":0}"#,
+            r#"data: {"type":"content_block_start","index":1,"content_block":{"type":"tool_use","id":"toolu_1","name":"get_weather","input":{}}}"#,
+            r#"data: {"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":""}}"#,
+            r#"data: {"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"{\"city\": "}}"#,
+            r#"data: {"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"\"Paris\"}"}}"#,
+            r#"data: {"type":"content_block_stop","index":1}"#,
+            r#"data: {"type":"content_block_start","index":2,"content_block":{"type":"tool_use","id":"toolu_2","name":"get_time","input":{}}}"#,
+            r#"data: {"type":"content_block_stop","index":2}"#,
+        ];
+        let events: Vec<_> = lines
+            .iter()
+            .flat_map(|line| parser.parse_line(line))
+            .collect();
+
+        assert_eq!(events[0].text(), Some("Checking."));
+        assert!(matches!(
+            &events[1],
+            StreamEvent::ToolCallStart { index: 0, id, name } if id == "toolu_1" && name == "get_weather"
+        ));
+        assert!(matches!(
+            &events[2],
+            StreamEvent::ToolCallDelta { index: 0, .. }
+        ));
+        assert!(matches!(
+            &events[3],
+            StreamEvent::ToolCallDelta { index: 0, .. }
+        ));
+        match &events[4] {
+            StreamEvent::ToolCallEnd {
+                index: 0,
+                tool_call,
+            } => {
+                assert_eq!(tool_call.id, "toolu_1");
+                assert_eq!(tool_call.function.arguments, r#"{"city": "Paris"}"#);
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+        match &events[6] {
+            StreamEvent::ToolCallEnd {
+                index: 1,
+                tool_call,
+            } => {
+                assert_eq!(tool_call.function.name, "get_time");
+                assert_eq!(tool_call.function.arguments, "{}");
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+        assert_eq!(events.len(), 7);
+    }
+
+    #[test]
+    fn test_in_band_error() {
+        let mut parser = StreamParser::new();
+        let events = parser.parse_line(
+            r#"data: {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#,
+        );
+        assert!(matches!(
+            events.as_slice(),
+            [StreamEvent::Error { message }] if message == "Overloaded"
+        ));
+        assert!(parser.is_done());
+    }
+}
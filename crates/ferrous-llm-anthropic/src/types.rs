@@ -1,7 +1,10 @@
 //! Anthropic-specific request and response types.
 
-use chrono::Utc;
-use ferrous_llm_core::{ChatResponse, FinishReason, FunctionCall, Metadata, ToolCall, Usage};
+use chrono::{DateTime, Utc};
+use ferrous_llm_core::{
+    ChatResponse, FinishReason, FunctionCall, Metadata, ModelInfo, ToolCall, Usage, is_remote_url,
+    parse_data_url,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -61,7 +64,7 @@ pub enum AnthropicContentBlock {
     #[serde(rename = "tool_result")]
     ToolResult {
         tool_use_id: String,
-        content: String,
+        content: AnthropicContent,
         #[serde(skip_serializing_if = "Option::is_none")]
         is_error: Option<bool>,
     },
@@ -69,11 +72,15 @@ pub enum AnthropicContentBlock {
 
 /// Anthropic image source.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AnthropicImageSource {
-    #[serde(rename = "type")]
-    pub source_type: String, // "base64"
-    pub media_type: String, // "image/jpeg", "image/png", etc.
-    pub data: String,       // base64 encoded image data
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AnthropicImageSource {
+    /// Inline base64 image data
+    Base64 {
+        media_type: String, // "image/jpeg", "image/png", etc.
+        data: String,
+    },
+    /// Image fetched by Anthropic from a public URL
+    Url { url: String },
 }
 
 /// Anthropic tool definition.
@@ -86,11 +93,22 @@ pub struct AnthropicTool {
 
 /// Anthropic tool choice.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum AnthropicToolChoice {
-    Auto,
-    Any,
-    Tool { name: String },
+    Auto {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        disable_parallel_tool_use: Option<bool>,
+    },
+    Any {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        disable_parallel_tool_use: Option<bool>,
+    },
+    Tool {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        disable_parallel_tool_use: Option<bool>,
+    },
+    None,
 }
 
 /// Anthropic messages response.
@@ -110,10 +128,55 @@ pub struct AnthropicMessagesResponse {
 /// Anthropic usage statistics.
 #[derive(Debug, Clone, Deserialize)]
 pub struct AnthropicUsage {
+    /// Not reported on `message_delta` stream events.
+    #[serde(default)]
     pub input_tokens: u32,
     pub output_tokens: u32,
 }
 
+/// A model listed by the Anthropic models endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnthropicModel {
+    pub id: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// When the model was released
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// A page of the Anthropic model list.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnthropicModelList {
+    pub data: Vec<AnthropicModel>,
+    #[serde(default)]
+    pub has_more: bool,
+    #[serde(default)]
+    pub last_id: Option<String>,
+}
+
+impl From<AnthropicModel> for ModelInfo {
+    /// Describe a model, filling in its capabilities from the built-in table.
+    fn from(model: AnthropicModel) -> Self {
+        let mut info = ModelInfo::lookup("anthropic", &model.id);
+        info.display_name = model.display_name;
+        info.created = model.created_at;
+        info
+    }
+}
+
+/// Map an Anthropic `stop_reason` string to the core [`FinishReason`].
+pub(crate) fn map_stop_reason(reason: &str) -> Option<FinishReason> {
+    match reason {
+        "end_turn" => Some(FinishReason::Stop),
+        "max_tokens" => Some(FinishReason::Length),
+        "stop_sequence" => Some(FinishReason::StopSequence),
+        "tool_use" => Some(FinishReason::ToolCalls),
+        "refusal" => Some(FinishReason::ContentFilter),
+        _ => None,
+    }
+}
+
 /// Anthropic streaming response chunk.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(tag = "type")]
@@ -262,14 +325,8 @@ impl ChatResponse for AnthropicMessagesResponseWrapper {
     fn finish_reason(&self) -> Option<FinishReason> {
         self.response
             .stop_reason
-            .as_ref()
-            .and_then(|reason| match reason.as_str() {
-                "end_turn" => Some(FinishReason::Stop),
-                "max_tokens" => Some(FinishReason::Length),
-                "stop_sequence" => Some(FinishReason::Stop),
-                "tool_use" => Some(FinishReason::ToolCalls),
-                _ => None,
-            })
+            .as_deref()
+            .and_then(map_stop_reason)
     }
 
     fn metadata(&self) -> Metadata {
@@ -296,15 +353,7 @@ impl ChatResponse for AnthropicMessagesResponse {
     }
 
     fn finish_reason(&self) -> Option<FinishReason> {
-        self.stop_reason
-            .as_ref()
-            .and_then(|reason| match reason.as_str() {
-                "end_turn" => Some(FinishReason::Stop),
-                "max_tokens" => Some(FinishReason::Length),
-                "stop_sequence" => Some(FinishReason::StopSequence),
-                "tool_use" => Some(FinishReason::ToolCalls),
-                _ => None,
-            })
+        self.stop_reason.as_deref().and_then(map_stop_reason)
     }
 
     fn metadata(&self) -> Metadata {
@@ -343,36 +392,20 @@ impl From<&ferrous_llm_core::Message> for AnthropicMessage {
                         }
                         ferrous_llm_core::ContentPart::Image { image_source, .. } => {
                             let url: String = image_source.clone().into();
-                            // Parse the image URL to determine if it's a data URI or external URL
-                            if url.starts_with("data:") {
-                                // Parse data URI to extract media type and base64 data
-                                // Format: data:image/jpeg;base64,<data>
-                                let parts: Vec<&str> = url.splitn(2, ',').collect();
-                                if parts.len() == 2 {
-                                    let header = parts[0];
-                                    let data = parts[1];
-                                    let media_type = header
-                                        .strip_prefix("data:")
-                                        .and_then(|s| s.split(';').next())
-                                        .unwrap_or("image/jpeg");
-                                    AnthropicContentBlock::Image {
-                                        source: AnthropicImageSource {
-                                            source_type: "base64".to_string(),
-                                            media_type: media_type.to_string(),
-                                            data: data.to_string(),
-                                        },
-                                    }
-                                } else {
-                                    // Invalid data URI, convert to text
-                                    AnthropicContentBlock::Text {
-                                        text: "[Invalid image data URI]".to_string(),
-                                    }
+                            if let Some(image) = parse_data_url(&url) {
+                                AnthropicContentBlock::Image {
+                                    source: AnthropicImageSource::Base64 {
+                                        media_type: image.media_type,
+                                        data: image.data,
+                                    },
+                                }
+                            } else if is_remote_url(&url) {
+                                AnthropicContentBlock::Image {
+                                    source: AnthropicImageSource::Url { url },
                                 }
                             } else {
-                                // External URL - needs to be downloaded and converted to base64
-                                // For now, return a placeholder
                                 AnthropicContentBlock::Text {
-                                    text: format!("[Image URL not supported: {url}]"),
+                                    text: format!("[Unsupported image source: {url}]"),
                                 }
                             }
                         }
@@ -388,9 +421,7 @@ impl From<&ferrous_llm_core::Message> for AnthropicMessage {
                 AnthropicContent::Blocks(blocks)
             }
             ferrous_llm_core::MessageContent::Tool(tool_content) => {
-                // Handle tool content - use text if available, otherwise create a placeholder
-                let text = tool_content.text.as_deref().unwrap_or("[Tool response]");
-                AnthropicContent::Text(text.to_string())
+                tool_content_blocks(&message.role, tool_content)
             }
         };
 
@@ -398,6 +429,131 @@ impl From<&ferrous_llm_core::Message> for AnthropicMessage {
     }
 }
 
+/// Convert core tool content to `tool_use` or `tool_result` blocks.
+fn tool_content_blocks(
+    role: &ferrous_llm_core::Role,
+    tool_content: &ferrous_llm_core::ToolContent,
+) -> AnthropicContent {
+    let text = tool_content.text.clone().unwrap_or_default();
+
+    if *role == ferrous_llm_core::Role::Tool {
+        return match &tool_content.tool_call_id {
+            Some(tool_use_id) => {
+                AnthropicContent::Blocks(vec![AnthropicContentBlock::ToolResult {
+                    tool_use_id: tool_use_id.clone(),
+                    content: AnthropicContent::Text(text),
+                    is_error: None,
+                }])
+            }
+            None => AnthropicContent::Text(text),
+        };
+    }
+
+    let mut blocks = Vec::new();
+    if !text.is_empty() {
+        blocks.push(AnthropicContentBlock::Text { text });
+    }
+    for call in tool_content.tool_calls.iter().flatten() {
+        // Anthropic expects an object; arguments that aren't valid JSON become empty input
+        let input = match call.function.arguments.trim() {
+            "" => serde_json::json!({}),
+            arguments => serde_json::from_str(arguments).unwrap_or_else(|_| serde_json::json!({})),
+        };
+        blocks.push(AnthropicContentBlock::ToolUse {
+            id: call.id.clone(),
+            name: call.function.name.clone(),
+            input,
+        });
+    }
+    AnthropicContent::Blocks(blocks)
+}
+
+impl AnthropicMessage {
+    /// Whether this is a user turn made up only of `tool_result` blocks.
+    pub fn is_tool_result(&self) -> bool {
+        match &self.content {
+            AnthropicContent::Blocks(blocks) => {
+                self.role == "user"
+                    && !blocks.is_empty()
+                    && blocks
+                        .iter()
+                        .all(|block| matches!(block, AnthropicContentBlock::ToolResult { .. }))
+            }
+            AnthropicContent::Text(_) => false,
+        }
+    }
+
+    /// Convert this message back to core messages.
+    ///
+    /// Each `tool_result` block becomes its own [`Role::Tool`] message, so a
+    /// merged tool result turn may produce several messages.
+    ///
+    /// [`Role::Tool`]: ferrous_llm_core::Role::Tool
+    pub fn to_core_messages(&self) -> Vec<ferrous_llm_core::Message> {
+        use ferrous_llm_core::{ContentPart, Message, MessageContent, Role};
+
+        let blocks = match &self.content {
+            AnthropicContent::Text(text) if self.role == "assistant" => {
+                return vec![Message::assistant(text.clone())];
+            }
+            AnthropicContent::Text(text) => return vec![Message::user(text.clone())],
+            AnthropicContent::Blocks(blocks) => blocks,
+        };
+
+        if self.role == "assistant" {
+            let text = extract_text_content(blocks);
+            return match extract_tool_calls(blocks) {
+                Some(tool_calls) => vec![Message::assistant_with_tools(text, tool_calls)],
+                None => vec![Message::assistant(text)],
+            };
+        }
+
+        let mut messages = Vec::new();
+        let mut parts = Vec::new();
+        for block in blocks {
+            match block {
+                AnthropicContentBlock::ToolResult {
+                    tool_use_id,
+                    content,
+                    ..
+                } => messages.push(Message::tool_response(content.text(), tool_use_id.clone())),
+                AnthropicContentBlock::Text { text } => {
+                    parts.push(ContentPart::text(text.clone()));
+                }
+                AnthropicContentBlock::Image { source } => {
+                    let url = match source {
+                        AnthropicImageSource::Base64 { media_type, data } => {
+                            format!("data:{media_type};base64,{data}")
+                        }
+                        AnthropicImageSource::Url { url } => url.clone(),
+                    };
+                    parts.push(ContentPart::image_url(url));
+                }
+                AnthropicContentBlock::ToolUse { .. } => {}
+            }
+        }
+        match parts.as_slice() {
+            [] => {}
+            [ContentPart::Text { text }] => messages.push(Message::user(text.clone())),
+            _ => messages.push(Message {
+                role: Role::User,
+                content: MessageContent::Multimodal(parts),
+            }),
+        }
+        messages
+    }
+}
+
+impl AnthropicContent {
+    /// The text of this content, joining text blocks.
+    pub fn text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::Blocks(blocks) => extract_text_content(blocks),
+        }
+    }
+}
+
 impl From<&ferrous_llm_core::Tool> for AnthropicTool {
     fn from(tool: &ferrous_llm_core::Tool) -> Self {
         Self {
@@ -114,10 +114,12 @@ mod e2e {
 
         while let Some(chunk_result) = stream.next().await {
             match chunk_result {
-                Ok(chunk) => {
-                    content.push_str(&chunk);
-                    chunk_count += 1;
-                    print!("{}", chunk);
+                Ok(event) => {
+                    if let Some(text) = event.text() {
+                        content.push_str(text);
+                        chunk_count += 1;
+                        print!("{}", text);
+                    }
                 }
                 Err(e) => panic!("Stream error: {:?}", e),
             }
@@ -161,6 +163,38 @@ mod unit_tests {
         assert!(provider.is_ok());
     }
 
+    #[test]
+    fn test_tool_choice_serialization() {
+        use ferrous_llm_anthropic::AnthropicToolChoice;
+
+        assert_eq!(
+            serde_json::to_value(AnthropicToolChoice::Auto {
+                disable_parallel_tool_use: None
+            })
+            .unwrap(),
+            serde_json::json!({"type": "auto"})
+        );
+        assert_eq!(
+            serde_json::to_value(AnthropicToolChoice::Tool {
+                name: "get_weather".to_string(),
+                disable_parallel_tool_use: None
+            })
+            .unwrap(),
+            serde_json::json!({"type": "tool", "name": "get_weather"})
+        );
+        assert_eq!(
+            serde_json::to_value(AnthropicToolChoice::Any {
+                disable_parallel_tool_use: Some(true)
+            })
+            .unwrap(),
+            serde_json::json!({"type": "any", "disable_parallel_tool_use": true})
+        );
+        assert_eq!(
+            serde_json::to_value(AnthropicToolChoice::None).unwrap(),
+            serde_json::json!({"type": "none"})
+        );
+    }
+
     #[test]
     fn test_urls() {
         let config = AnthropicConfig::new("sk-ant-test123", "claude-3-5-sonnet-20241022");
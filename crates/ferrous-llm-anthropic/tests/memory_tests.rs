@@ -0,0 +1,108 @@
+//! Tests that memory-augmented requests keep the caller's system prompt when
+//! sent to the Anthropic provider against a local mock server.
+
+use ferrous_llm_anthropic::{AnthropicConfig, AnthropicProvider};
+use ferrous_llm_core::{ChatProvider, ChatRequest, Message, Metadata, Parameters};
+use ferrous_llm_memory::{
+    ContextPlacement, DEFAULT_SUMMARY_PROMPT, Memory, ScoredRecord, SummaryMemory, VectorRecord,
+    inject_context,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn text_reply(text: &str) -> serde_json::Value {
+    json!({
+        "id": "msg_1",
+        "type": "message",
+        "role": "assistant",
+        "model": "claude-3-5-haiku-20241022",
+        "content": [{"type": "text", "text": text}],
+        "stop_reason": "end_turn",
+        "usage": {"input_tokens": 20, "output_tokens": 5}
+    })
+}
+
+fn provider(server: &MockServer) -> AnthropicProvider {
+    let mut config = AnthropicConfig::new("sk-ant-test123", "claude-3-5-haiku-20241022");
+    config.base_url = Some(server.uri().parse().unwrap());
+    config.http.max_retries = 0;
+    AnthropicProvider::new(config).unwrap()
+}
+
+#[tokio::test]
+async fn test_injected_context_keeps_system_prompt() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(body_partial_json(json!({
+            "system": "Be brief.\n\nUse the following context if it is relevant:\n\n\
+                       [1] Rust 1.0 was released in 2015."
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(text_reply("2015")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut request = ChatRequest::builder()
+        .system_message("Be brief.")
+        .user_message("When was Rust 1.0 released?")
+        .build();
+    let snippets = [ScoredRecord {
+        record: VectorRecord {
+            id: "rust".to_string(),
+            text: "Rust 1.0 was released in 2015.".to_string(),
+            embedding: vec![1.0],
+            metadata: HashMap::new(),
+        },
+        score: 1.0,
+    }];
+    inject_context(&mut request, &snippets, ContextPlacement::System);
+
+    provider(&server).chat(request).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_summary_keeps_system_prompt() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(body_partial_json(json!({"system": DEFAULT_SUMMARY_PROMPT})))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(text_reply("The user greeted Claude.")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(body_partial_json(json!({
+            "system": "Be brief.\n\nSummary of the earlier conversation:\nThe user greeted Claude.",
+            "messages": [{"role": "user", "content": "What's new?"}]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(text_reply("Not much.")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut memory = SummaryMemory::new(provider(&server))
+        .max_messages(2)
+        .keep_recent(1);
+    for message in [
+        Message::system("Be brief."),
+        Message::user("Hi"),
+        Message::assistant("Hello!"),
+        Message::user("What's new?"),
+    ] {
+        memory.add_message(message).await.unwrap();
+    }
+    assert!(memory.summary().is_some());
+
+    let request = ChatRequest {
+        messages: memory.messages(),
+        parameters: Parameters::default(),
+        metadata: Metadata::default(),
+    };
+    provider(&server).chat(request).await.unwrap();
+}
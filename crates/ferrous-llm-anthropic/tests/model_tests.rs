@@ -0,0 +1,88 @@
+//! Model catalog tests for the Anthropic provider against a local mock server.
+
+use ferrous_llm_anthropic::{AnthropicConfig, AnthropicProvider};
+use ferrous_llm_core::ModelCatalog;
+use serde_json::json;
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+fn provider(server: &MockServer) -> AnthropicProvider {
+    let mut config = AnthropicConfig::new("sk-ant-test123", "claude-3-5-haiku-20241022");
+    config.base_url = Some(server.uri().parse().unwrap());
+    AnthropicProvider::new(config).unwrap()
+}
+
+#[tokio::test]
+async fn test_list_models_follows_pages() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .and(header("x-api-key", "sk-ant-test123"))
+        .and(|request: &Request| !request.url.query().unwrap_or("").contains("after_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{
+                "type": "model",
+                "id": "claude-sonnet-4-20250514",
+                "display_name": "Claude Sonnet 4",
+                "created_at": "2025-05-22T00:00:00Z"
+            }],
+            "has_more": true,
+            "first_id": "claude-sonnet-4-20250514",
+            "last_id": "claude-sonnet-4-20250514"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .and(query_param("after_id", "claude-sonnet-4-20250514"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{
+                "type": "model",
+                "id": "claude-3-5-haiku-20241022",
+                "display_name": "Claude Haiku 3.5",
+                "created_at": "2024-10-22T00:00:00Z"
+            }],
+            "has_more": false,
+            "first_id": "claude-3-5-haiku-20241022",
+            "last_id": "claude-3-5-haiku-20241022"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let models = provider(&server).list_models().await.unwrap();
+    assert_eq!(models.len(), 2);
+
+    let sonnet = &models[0];
+    assert_eq!(sonnet.provider, "anthropic");
+    assert_eq!(sonnet.display_name.as_deref(), Some("Claude Sonnet 4"));
+    assert_eq!(sonnet.max_output_tokens, Some(64_000));
+    assert!(sonnet.supports_vision);
+    assert!(sonnet.created.is_some());
+
+    assert_eq!(models[1].pricing.unwrap().input, 0.8);
+}
+
+#[tokio::test]
+async fn test_model_info() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models/claude-3-haiku-20240307"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "type": "model",
+            "id": "claude-3-haiku-20240307",
+            "display_name": "Claude Haiku 3",
+            "created_at": "2024-03-07T00:00:00Z"
+        })))
+        .mount(&server)
+        .await;
+
+    let info = provider(&server)
+        .model_info("claude-3-haiku-20240307")
+        .await
+        .unwrap();
+    assert_eq!(info.context_window, Some(200_000));
+    assert!(info.supports_tools);
+    assert!(!info.supports_json_mode);
+}
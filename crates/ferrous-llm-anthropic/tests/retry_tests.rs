@@ -0,0 +1,110 @@
+//! Retry behaviour tests for the Anthropic provider against a local mock server.
+
+use ferrous_llm_anthropic::{AnthropicConfig, AnthropicProvider};
+use ferrous_llm_core::{ChatProvider, ChatRequest, ChatResponse, ProviderError, StreamingProvider};
+use futures::StreamExt;
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn provider(server: &MockServer, max_retries: u32) -> AnthropicProvider {
+    let mut config = AnthropicConfig::new("sk-ant-test123", "claude-3-5-haiku-20241022");
+    config.base_url = Some(server.uri().parse().unwrap());
+    config.http.max_retries = max_retries;
+    config.http.retry_delay = Duration::from_millis(1);
+    config.http.max_retry_delay = Duration::from_millis(50);
+    AnthropicProvider::new(config).unwrap()
+}
+
+fn request() -> ChatRequest {
+    ChatRequest::builder().user_message("Hello").build()
+}
+
+#[tokio::test]
+async fn test_chat_retries_overloaded() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(ResponseTemplate::new(529).set_body_json(json!({
+            "type": "error",
+            "error": {"type": "overloaded_error", "message": "Overloaded"}
+        })))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "msg_1",
+            "type": "message",
+            "role": "assistant",
+            "content": [{"type": "text", "text": "Hi there"}],
+            "model": "claude-3-5-haiku-20241022",
+            "stop_reason": "end_turn",
+            "stop_sequence": null,
+            "usage": {"input_tokens": 5, "output_tokens": 2}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let response = provider(&server, 3).chat(request()).await.unwrap();
+    assert_eq!(response.content(), "Hi there");
+}
+
+#[tokio::test]
+async fn test_rate_limit_retry_after_header() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("retry-after", "12")
+                .set_body_json(json!({
+                    "type": "error",
+                    "error": {"type": "rate_limit_error", "message": "Slow down"}
+                })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let error = provider(&server, 0).chat(request()).await.unwrap_err();
+    assert!(error.is_rate_limited());
+    assert_eq!(error.retry_after(), Some(Duration::from_secs(12)));
+}
+
+#[tokio::test]
+async fn test_stream_connection_retries() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    let body = concat!(
+        "event: content_block_delta\n",
+        "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hi\"}}\n\n",
+        "event: message_stop\n",
+        "data: {\"type\":\"message_stop\"}\n\n",
+    );
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut stream = provider(&server, 2).chat_stream(request()).await.unwrap();
+    let mut text = String::new();
+    while let Some(event) = stream.next().await {
+        if let Some(delta) = event.unwrap().text() {
+            text.push_str(delta);
+        }
+    }
+    assert_eq!(text, "Hi");
+}
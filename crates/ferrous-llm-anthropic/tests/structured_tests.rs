@@ -0,0 +1,88 @@
+//! Structured output tests for the Anthropic provider against a local mock server.
+
+use ferrous_llm_anthropic::{AnthropicConfig, AnthropicProvider};
+use ferrous_llm_core::{ChatRequest, ResponseSchema, StructuredOutput};
+use serde::Deserialize;
+use serde_json::json;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[derive(Debug, Deserialize)]
+struct Weather {
+    city: String,
+    celsius: f32,
+}
+
+fn tool_use(id: &str, input: serde_json::Value) -> serde_json::Value {
+    json!({
+        "id": "msg_1",
+        "type": "message",
+        "role": "assistant",
+        "model": "claude-3-5-haiku-20241022",
+        "content": [{"type": "tool_use", "id": id, "name": "weather", "input": input}],
+        "stop_reason": "tool_use",
+        "usage": {"input_tokens": 50, "output_tokens": 20}
+    })
+}
+
+#[tokio::test]
+async fn test_structured_output_forces_tool_and_repairs() {
+    let server = MockServer::start().await;
+
+    // The repair is sent back as the result of the forced tool call
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(body_partial_json(json!({
+            "messages": [
+                {"role": "user"},
+                {"role": "assistant"},
+                {"role": "user", "content": [{"type": "tool_result", "tool_use_id": "toolu_1"}]}
+            ]
+        })))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(tool_use("toolu_2", json!({"city": "Oslo", "celsius": -3}))),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(body_partial_json(json!({
+            "tools": [{"name": "weather"}],
+            "tool_choice": {"type": "tool", "name": "weather"}
+        })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(tool_use("toolu_1", json!({"city": "Oslo"}))),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut config = AnthropicConfig::new("sk-ant-test123", "claude-3-5-haiku-20241022");
+    config.base_url = Some(server.uri().parse().unwrap());
+    let provider = AnthropicProvider::new(config).unwrap();
+
+    let schema = ResponseSchema::new(
+        "weather",
+        json!({
+            "type": "object",
+            "properties": {
+                "city": {"type": "string"},
+                "celsius": {"type": "number"}
+            },
+            "required": ["city", "celsius"]
+        }),
+    );
+    let request = ChatRequest::builder()
+        .user_message("What's the weather in Oslo?")
+        .build();
+
+    let result = StructuredOutput::new(&provider, schema)
+        .generate::<Weather>(request)
+        .await
+        .unwrap();
+    assert_eq!(result.value.city, "Oslo");
+    assert_eq!(result.value.celsius, -3.0);
+    assert_eq!(result.attempts, 2);
+}
@@ -0,0 +1,260 @@
+//! Model discovery and capability metadata.
+//!
+//! Providers that can list their models implement [`ModelCatalog`], which
+//! returns a [`ModelInfo`] for each model. Model APIs rarely report what a
+//! model can do, so providers fill in context windows, capabilities and
+//! pricing from a built-in table of well-known models, available directly
+//! through [`known_model`].
+
+use crate::error::ProviderError;
+use crate::types::Usage;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Price of a model in US dollars per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    /// Price per million input tokens
+    pub input: f64,
+    /// Price per million output tokens
+    pub output: f64,
+}
+
+impl ModelPricing {
+    /// Estimate the cost in US dollars of a request with the given usage.
+    pub fn cost(&self, usage: &Usage) -> f64 {
+        (usage.prompt_tokens as f64 * self.input + usage.completion_tokens as f64 * self.output)
+            / 1_000_000.0
+    }
+}
+
+/// What a model is and what it can do.
+///
+/// Capabilities that are unknown are reported as `false`, and limits and
+/// pricing that are unknown as `None`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelInfo {
+    /// Model identifier to use in requests
+    pub id: String,
+    /// Name of the provider serving the model, e.g. "openai"
+    pub provider: String,
+    /// Human-readable name
+    pub display_name: Option<String>,
+    /// When the model was released or created
+    pub created: Option<DateTime<Utc>>,
+    /// Maximum number of input and output tokens combined
+    pub context_window: Option<u32>,
+    /// Maximum number of tokens the model can generate
+    pub max_output_tokens: Option<u32>,
+    /// Whether the model can call tools
+    pub supports_tools: bool,
+    /// Whether the model accepts images
+    pub supports_vision: bool,
+    /// Whether responses can be streamed
+    pub supports_streaming: bool,
+    /// Whether the model can be constrained to produce JSON
+    pub supports_json_mode: bool,
+    /// Price per million tokens, if known
+    pub pricing: Option<ModelPricing>,
+    /// Provider-specific details, e.g. the owner or quantization
+    #[serde(default)]
+    pub metadata: HashMap<String, Value>,
+}
+
+impl ModelInfo {
+    /// Create information about a model with nothing known about it yet.
+    pub fn new(provider: impl Into<String>, id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            provider: provider.into(),
+            display_name: None,
+            created: None,
+            context_window: None,
+            max_output_tokens: None,
+            supports_tools: false,
+            supports_vision: false,
+            supports_streaming: false,
+            supports_json_mode: false,
+            pricing: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Create information about a model, filled in from the built-in table
+    /// when the model is well known.
+    pub fn lookup(provider: &str, id: &str) -> Self {
+        known_model(provider, id).unwrap_or_else(|| Self::new(provider, id))
+    }
+
+    /// Estimate the cost in US dollars of a request with the given usage.
+    pub fn cost(&self, usage: &Usage) -> Option<f64> {
+        self.pricing.map(|pricing| pricing.cost(usage))
+    }
+}
+
+/// Discovery of the models a provider offers.
+#[async_trait]
+pub trait ModelCatalog: Send + Sync {
+    /// Provider-specific error type
+    type Error: ProviderError;
+
+    /// List the models available to this provider.
+    async fn list_models(&self) -> Result<Vec<ModelInfo>, Self::Error>;
+
+    /// Get information about a single model.
+    async fn model_info(&self, model: &str) -> Result<ModelInfo, Self::Error>;
+}
+
+/// A row of the built-in capability table.
+struct KnownModel {
+    provider: &'static str,
+    /// Model id, or the prefix shared by its dated versions
+    id: &'static str,
+    context_window: u32,
+    max_output_tokens: u32,
+    tools: bool,
+    vision: bool,
+    json_mode: bool,
+    /// Input and output price per million tokens
+    pricing: (f64, f64),
+}
+
+const fn known(
+    provider: &'static str,
+    id: &'static str,
+    context_window: u32,
+    max_output_tokens: u32,
+    (tools, vision, json_mode): (bool, bool, bool),
+    pricing: (f64, f64),
+) -> KnownModel {
+    KnownModel {
+        provider,
+        id,
+        context_window,
+        max_output_tokens,
+        tools,
+        vision,
+        json_mode,
+        pricing,
+    }
+}
+
+// Capability flags: (tools, vision, json mode)
+const CHAT: (bool, bool, bool) = (true, false, true);
+const MULTIMODAL: (bool, bool, bool) = (true, true, true);
+const TOOLS: (bool, bool, bool) = (true, false, false);
+const TEXT: (bool, bool, bool) = (false, false, false);
+// Claude has no JSON mode; structured output goes through tools
+const CLAUDE: (bool, bool, bool) = (true, true, false);
+
+#[rustfmt::skip]
+static KNOWN_MODELS: &[KnownModel] = &[
+    known("openai", "gpt-4.1", 1_047_576, 32_768, MULTIMODAL, (2.0, 8.0)),
+    known("openai", "gpt-4.1-mini", 1_047_576, 32_768, MULTIMODAL, (0.4, 1.6)),
+    known("openai", "gpt-4.1-nano", 1_047_576, 32_768, MULTIMODAL, (0.1, 0.4)),
+    known("openai", "gpt-4o", 128_000, 16_384, MULTIMODAL, (2.5, 10.0)),
+    known("openai", "gpt-4o-mini", 128_000, 16_384, MULTIMODAL, (0.15, 0.6)),
+    known("openai", "gpt-4-turbo", 128_000, 4_096, MULTIMODAL, (10.0, 30.0)),
+    known("openai", "gpt-4", 8_192, 8_192, TOOLS, (30.0, 60.0)),
+    known("openai", "gpt-3.5-turbo", 16_385, 4_096, CHAT, (0.5, 1.5)),
+    known("openai", "o1", 200_000, 100_000, MULTIMODAL, (15.0, 60.0)),
+    known("openai", "o1-mini", 128_000, 65_536, TEXT, (1.1, 4.4)),
+    known("openai", "o3", 200_000, 100_000, MULTIMODAL, (2.0, 8.0)),
+    known("openai", "o3-mini", 200_000, 100_000, CHAT, (1.1, 4.4)),
+    known("openai", "o4-mini", 200_000, 100_000, MULTIMODAL, (1.1, 4.4)),
+    known("openai", "text-embedding-3-small", 8_191, 0, TEXT, (0.02, 0.0)),
+    known("openai", "text-embedding-3-large", 8_191, 0, TEXT, (0.13, 0.0)),
+    known("openai", "text-embedding-ada-002", 8_191, 0, TEXT, (0.1, 0.0)),
+    known("anthropic", "claude-opus-4", 200_000, 32_000, CLAUDE, (15.0, 75.0)),
+    known("anthropic", "claude-sonnet-4", 200_000, 64_000, CLAUDE, (3.0, 15.0)),
+    known("anthropic", "claude-3-7-sonnet", 200_000, 64_000, CLAUDE, (3.0, 15.0)),
+    known("anthropic", "claude-3-5-sonnet", 200_000, 8_192, CLAUDE, (3.0, 15.0)),
+    known("anthropic", "claude-3-5-haiku", 200_000, 8_192, TOOLS, (0.8, 4.0)),
+    known("anthropic", "claude-3-opus", 200_000, 4_096, CLAUDE, (15.0, 75.0)),
+    known("anthropic", "claude-3-sonnet", 200_000, 4_096, CLAUDE, (3.0, 15.0)),
+    known("anthropic", "claude-3-haiku", 200_000, 4_096, CLAUDE, (0.25, 1.25)),
+];
+
+/// Look up a well-known model in the built-in capability table.
+///
+/// Dated versions and aliases match their family, so
+/// "gpt-4o-2024-08-06" and "claude-3-5-sonnet-latest" are found, and the
+/// most specific entry wins: "gpt-4o-mini" is not mistaken for "gpt-4o".
+pub fn known_model(provider: &str, id: &str) -> Option<ModelInfo> {
+    let known = KNOWN_MODELS
+        .iter()
+        .filter(|known| known.provider == provider && matches_family(id, known.id))
+        .max_by_key(|known| known.id.len())?;
+
+    let is_embedding = known.max_output_tokens == 0;
+    Some(ModelInfo {
+        context_window: Some(known.context_window),
+        max_output_tokens: (!is_embedding).then_some(known.max_output_tokens),
+        supports_tools: known.tools,
+        supports_vision: known.vision,
+        supports_streaming: !is_embedding,
+        supports_json_mode: known.json_mode,
+        pricing: Some(ModelPricing {
+            input: known.pricing.0,
+            output: known.pricing.1,
+        }),
+        ..ModelInfo::new(provider, id)
+    })
+}
+
+/// Check whether `id` is `family` or a version of it, such as `family-2024-08-06`.
+fn matches_family(id: &str, family: &str) -> bool {
+    match id.strip_prefix(family) {
+        Some(rest) => rest.is_empty() || rest.starts_with(['-', ':', '@']),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_model_matches_versions() {
+        let info = known_model("openai", "gpt-4o-2024-08-06").unwrap();
+        assert_eq!(info.id, "gpt-4o-2024-08-06");
+        assert_eq!(info.context_window, Some(128_000));
+        assert!(info.supports_vision);
+
+        let mini = known_model("openai", "gpt-4o-mini-2024-07-18").unwrap();
+        assert_eq!(mini.pricing.unwrap().input, 0.15);
+
+        let claude = known_model("anthropic", "claude-3-5-sonnet-latest").unwrap();
+        assert!(claude.supports_tools);
+        assert!(!claude.supports_json_mode);
+
+        assert!(known_model("openai", "gpt-4.5-preview").is_none());
+        assert!(known_model("ollama", "gpt-4o").is_none());
+    }
+
+    #[test]
+    fn test_embedding_models() {
+        let info = known_model("openai", "text-embedding-3-small").unwrap();
+        assert_eq!(info.max_output_tokens, None);
+        assert!(!info.supports_streaming);
+        assert!(!info.supports_tools);
+    }
+
+    #[test]
+    fn test_lookup_and_cost() {
+        let unknown = ModelInfo::lookup("openai", "ft:custom");
+        let usage = Usage {
+            prompt_tokens: 1_000_000,
+            completion_tokens: 500_000,
+            total_tokens: 1_500_000,
+        };
+        assert_eq!(unknown, ModelInfo::new("openai", "ft:custom"));
+        assert_eq!(unknown.cost(&usage), None);
+
+        let info = ModelInfo::lookup("anthropic", "claude-3-haiku-20240307");
+        assert_eq!(info.cost(&usage), Some(0.25 + 0.625));
+    }
+}
@@ -4,7 +4,7 @@
 //! use to manage their settings, validation, and initialization.
 
 use crate::error::ConfigError;
-use serde::{Deserialize, Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::Debug;
 use std::time::Duration;
 
@@ -34,7 +34,11 @@ pub trait ProviderConfig: Clone + Debug + Send + Sync {
 /// This type ensures that sensitive values are not accidentally logged
 /// or displayed in debug output. It also redacts the value during serialization
 /// to avoid accidentally exposing secrets in configuration files or logs.
-#[derive(Clone, Deserialize)]
+///
+/// When deserializing, the value may be given inline as a string or as a
+/// reference to an environment variable, e.g. `api_key = { env = "OPENAI_API_KEY" }`,
+/// which is resolved at load time.
+#[derive(Clone)]
 pub struct SecretString(String);
 
 impl SecretString {
@@ -90,8 +94,30 @@ impl Serialize for SecretString {
     }
 }
 
+impl<'de> Deserialize<'de> for SecretString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum SecretSource {
+            Value(String),
+            Env { env: String },
+        }
+
+        match SecretSource::deserialize(deserializer)? {
+            SecretSource::Value(value) => Ok(Self(value)),
+            SecretSource::Env { env } => std::env::var(&env).map(Self).map_err(|_| {
+                serde::de::Error::custom(format!("environment variable {env} is not set"))
+            }),
+        }
+    }
+}
+
 /// Common HTTP client configuration options.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct HttpConfig {
     /// Request timeout duration
     pub timeout: Duration,
@@ -120,6 +146,7 @@ pub struct HttpConfig {
 
 /// Connection pool configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PoolConfig {
     /// Maximum number of connections per host
     pub max_connections_per_host: usize,
@@ -136,19 +163,26 @@ pub struct PoolConfig {
 
 /// Rate limiting configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RateLimitConfig {
-    /// Maximum requests per second
+    /// Maximum requests per second; zero or less disables the request limit
     pub requests_per_second: f64,
 
     /// Burst capacity (maximum requests that can be made at once)
     pub burst_capacity: u32,
 
+    /// Optional budget of tokens (prompt + completion) per minute; zero
+    /// disables the budget
+    #[serde(default)]
+    pub tokens_per_minute: Option<u32>,
+
     /// Whether rate limiting is enabled
     pub enabled: bool,
 }
 
 /// Retry configuration for failed requests.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RetryConfig {
     /// Maximum number of retry attempts
     pub max_attempts: u32,
@@ -200,6 +234,7 @@ impl Default for RateLimitConfig {
         Self {
             requests_per_second: 10.0,
             burst_capacity: 20,
+            tokens_per_minute: None,
             enabled: true,
         }
     }
@@ -430,6 +465,21 @@ mod tests {
         assert_eq!(secret.expose_secret(), "my_secret");
     }
 
+    #[test]
+    fn test_secret_string_deserialize() {
+        let secret: SecretString = serde_json::from_str(r#""inline""#).unwrap();
+        assert_eq!(secret.expose_secret(), "inline");
+
+        // Cargo sets this for the test process
+        let secret: SecretString = serde_json::from_str(r#"{"env": "CARGO_PKG_NAME"}"#).unwrap();
+        assert_eq!(secret.expose_secret(), env!("CARGO_PKG_NAME"));
+
+        let error = serde_json::from_str::<SecretString>(r#"{"env": "FERROUS_LLM_UNSET_VAR"}"#)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("FERROUS_LLM_UNSET_VAR"));
+    }
+
     #[test]
     fn test_validation_non_empty() {
         use validation::*;
@@ -0,0 +1,339 @@
+//! Object-safe provider traits for choosing a provider at runtime.
+//!
+//! The capability traits in [`crate::traits`] use associated types, so two
+//! providers can't be stored behind the same type. The `Dyn*` traits here box
+//! responses and streams and erase provider errors into [`DynError`], and are
+//! implemented for every provider automatically:
+//!
+//! ```rust,ignore
+//! let providers: Vec<Box<dyn DynChatProvider>> = vec![Box::new(openai), Box::new(ollama)];
+//! for provider in &providers {
+//!     println!("{}", provider.chat_dyn(request.clone()).await?.content());
+//! }
+//! ```
+
+use crate::error::{LlmError, ProviderError};
+use crate::structured::{ResponseSchema, StructuredProvider};
+use crate::traits::*;
+use crate::types::*;
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
+use std::time::Duration;
+
+/// A provider error with its concrete type erased.
+///
+/// All [`ProviderError`] queries are forwarded to the original error, which
+/// can be recovered with [`BoxedProviderError::downcast_ref`].
+#[derive(Debug)]
+pub struct BoxedProviderError(Box<dyn ProviderError>);
+
+impl BoxedProviderError {
+    /// Box a provider error.
+    pub fn new<E: ProviderError>(error: E) -> Self {
+        Self(Box::new(error))
+    }
+
+    /// Get the original error if it is of type `E`.
+    pub fn downcast_ref<E: ProviderError>(&self) -> Option<&E> {
+        let error: &(dyn Error + 'static) = self.0.as_ref();
+        error.downcast_ref()
+    }
+
+    /// Get the boxed error.
+    pub fn into_inner(self) -> Box<dyn ProviderError> {
+        self.0
+    }
+}
+
+impl fmt::Display for BoxedProviderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for BoxedProviderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+impl ProviderError for BoxedProviderError {
+    fn error_code(&self) -> Option<&str> {
+        self.0.error_code()
+    }
+
+    fn is_retryable(&self) -> bool {
+        self.0.is_retryable()
+    }
+
+    fn is_rate_limited(&self) -> bool {
+        self.0.is_rate_limited()
+    }
+
+    fn is_auth_error(&self) -> bool {
+        self.0.is_auth_error()
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        self.0.retry_after()
+    }
+
+    fn is_invalid_input(&self) -> bool {
+        self.0.is_invalid_input()
+    }
+
+    fn is_service_unavailable(&self) -> bool {
+        self.0.is_service_unavailable()
+    }
+
+    fn is_content_filtered(&self) -> bool {
+        self.0.is_content_filtered()
+    }
+}
+
+/// Error type shared by all dynamic providers.
+pub type DynError = LlmError<BoxedProviderError>;
+
+impl DynError {
+    /// Wrap a provider-specific error.
+    pub fn provider<E: ProviderError>(error: E) -> Self {
+        Self::Provider(BoxedProviderError::new(error))
+    }
+}
+
+/// Boxed chat response returned by dynamic providers.
+pub type BoxedChatResponse = Box<dyn ChatResponse>;
+
+/// Stream of events returned by dynamic streaming providers.
+pub type DynChatStream = Pin<Box<dyn Stream<Item = Result<StreamEvent, DynError>> + Send>>;
+
+impl<T: ChatResponse + ?Sized> ChatResponse for Box<T> {
+    fn content(&self) -> String {
+        (**self).content()
+    }
+
+    fn usage(&self) -> Option<Usage> {
+        (**self).usage()
+    }
+
+    fn finish_reason(&self) -> Option<FinishReason> {
+        (**self).finish_reason()
+    }
+
+    fn metadata(&self) -> Metadata {
+        (**self).metadata()
+    }
+
+    fn tool_calls(&self) -> Option<Vec<ToolCall>> {
+        (**self).tool_calls()
+    }
+
+    fn as_message(&self) -> Message {
+        (**self).as_message()
+    }
+}
+
+/// Object-safe counterpart of [`ChatProvider`].
+#[async_trait]
+pub trait DynChatProvider: Send + Sync {
+    /// Send a chat request and get a boxed response.
+    async fn chat_dyn(&self, request: ChatRequest) -> Result<BoxedChatResponse, DynError>;
+}
+
+/// Object-safe counterpart of [`StreamingProvider`].
+#[async_trait]
+pub trait DynStreamingProvider: DynChatProvider {
+    /// Send a chat request and receive a boxed stream of events.
+    async fn chat_stream_dyn(&self, request: ChatRequest) -> Result<DynChatStream, DynError>;
+}
+
+/// Object-safe counterpart of [`ToolProvider`].
+#[async_trait]
+pub trait DynToolProvider: DynChatProvider {
+    /// Send a chat request with available tools and get a boxed response.
+    async fn chat_with_tools_dyn(
+        &self,
+        request: ChatRequest,
+        tools: &[Tool],
+    ) -> Result<BoxedChatResponse, DynError>;
+}
+
+/// Object-safe counterpart of [`StructuredProvider`].
+#[async_trait]
+pub trait DynStructuredProvider: DynChatProvider {
+    /// Send a chat request constrained to a JSON schema and get a boxed response.
+    async fn chat_structured_dyn(
+        &self,
+        request: ChatRequest,
+        schema: &ResponseSchema,
+    ) -> Result<BoxedChatResponse, DynError>;
+}
+
+/// Object-safe counterpart of [`EmbeddingProvider`].
+#[async_trait]
+pub trait DynEmbeddingProvider: Send + Sync {
+    /// Generate embeddings for the given texts.
+    async fn embed_dyn(&self, texts: &[String]) -> Result<Vec<Embedding>, DynError>;
+}
+
+#[async_trait]
+impl<P> DynChatProvider for P
+where
+    P: ChatProvider,
+    P::Response: 'static,
+{
+    async fn chat_dyn(&self, request: ChatRequest) -> Result<BoxedChatResponse, DynError> {
+        match self.chat(request).await {
+            Ok(response) => Ok(Box::new(response)),
+            Err(error) => Err(DynError::provider(error)),
+        }
+    }
+}
+
+#[async_trait]
+impl<P> DynStreamingProvider for P
+where
+    P: StreamingProvider<StreamItem = StreamEvent>,
+    P::Response: 'static,
+{
+    async fn chat_stream_dyn(&self, request: ChatRequest) -> Result<DynChatStream, DynError> {
+        match self.chat_stream(request).await {
+            Ok(stream) => Ok(stream.map(|item| item.map_err(DynError::provider)).boxed()),
+            Err(error) => Err(DynError::provider(error)),
+        }
+    }
+}
+
+#[async_trait]
+impl<P> DynToolProvider for P
+where
+    P: ToolProvider,
+    P::Response: 'static,
+{
+    async fn chat_with_tools_dyn(
+        &self,
+        request: ChatRequest,
+        tools: &[Tool],
+    ) -> Result<BoxedChatResponse, DynError> {
+        match self.chat_with_tools(request, tools).await {
+            Ok(response) => Ok(Box::new(response)),
+            Err(error) => Err(DynError::provider(error)),
+        }
+    }
+}
+
+#[async_trait]
+impl<P> DynStructuredProvider for P
+where
+    P: StructuredProvider,
+    P::Response: 'static,
+{
+    async fn chat_structured_dyn(
+        &self,
+        request: ChatRequest,
+        schema: &ResponseSchema,
+    ) -> Result<BoxedChatResponse, DynError> {
+        match self.chat_structured(request, schema).await {
+            Ok(response) => Ok(Box::new(response)),
+            Err(error) => Err(DynError::provider(error)),
+        }
+    }
+}
+
+#[async_trait]
+impl<P: EmbeddingProvider> DynEmbeddingProvider for P {
+    async fn embed_dyn(&self, texts: &[String]) -> Result<Vec<Embedding>, DynError> {
+        self.embed(texts).await.map_err(DynError::provider)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{MockConfig, MockError, MockResponse};
+    use futures::stream;
+
+    struct MockProvider {
+        reply: Option<&'static str>,
+    }
+
+    #[async_trait]
+    impl ChatProvider for MockProvider {
+        type Config = MockConfig;
+        type Response = MockResponse;
+        type Error = MockError;
+
+        async fn chat(&self, _request: ChatRequest) -> Result<MockResponse, MockError> {
+            self.reply
+                .map(MockResponse::text)
+                .ok_or(MockError::Unavailable)
+        }
+    }
+
+    #[async_trait]
+    impl StreamingProvider for MockProvider {
+        type StreamItem = StreamEvent;
+        type Stream = stream::Iter<std::vec::IntoIter<Result<StreamEvent, MockError>>>;
+
+        async fn chat_stream(&self, _request: ChatRequest) -> Result<Self::Stream, MockError> {
+            let text = self.reply.ok_or(MockError::Unavailable)?;
+            Ok(stream::iter(vec![
+                Ok(StreamEvent::TextDelta {
+                    text: text.to_string(),
+                }),
+                Err(MockError::Unavailable),
+            ]))
+        }
+    }
+
+    fn request() -> ChatRequest {
+        ChatRequest::builder().user_message("Hello").build()
+    }
+
+    #[tokio::test]
+    async fn test_heterogeneous_providers() {
+        let providers: Vec<Box<dyn DynStreamingProvider>> = vec![
+            Box::new(MockProvider { reply: Some("one") }),
+            Box::new(MockProvider { reply: Some("two") }),
+        ];
+
+        let mut replies = Vec::new();
+        for provider in &providers {
+            let response = provider.chat_dyn(request()).await.unwrap();
+            assert_eq!(response.finish_reason(), Some(FinishReason::Stop));
+            replies.push(response.content());
+        }
+        assert_eq!(replies, ["one", "two"]);
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_erased_but_recoverable() {
+        let provider: Box<dyn DynChatProvider> = Box::new(MockProvider { reply: None });
+        let error = provider.chat_dyn(request()).await.err().unwrap();
+
+        assert!(error.is_retryable());
+        assert_eq!(error.error_code(), Some("mock"));
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(3)));
+        match error {
+            LlmError::Provider(boxed) => assert!(boxed.downcast_ref::<MockError>().is_some()),
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_errors_are_mapped() {
+        let provider: Box<dyn DynStreamingProvider> = Box::new(MockProvider { reply: Some("hi") });
+        let items: Vec<_> = provider
+            .chat_stream_dyn(request())
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(items[0].as_ref().unwrap().text(), Some("hi"));
+        assert!(matches!(items[1], Err(LlmError::Provider(_))));
+    }
+}
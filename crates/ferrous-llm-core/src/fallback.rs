@@ -0,0 +1,400 @@
+//! Failover across an ordered chain of providers.
+//!
+//! [`FallbackProvider`] sends each request to the first provider in its chain
+//! and moves on to the next one when the request fails with an error that
+//! another provider might not hit, such as rate limiting or an outage. The
+//! name of the provider that answered is reported in the response metadata
+//! under the `"provider"` extension, and by [`FallbackStream::provider`] for
+//! streaming requests.
+//!
+//! Streaming requests only fail over while the stream is being established.
+//! Once a provider has returned a stream, errors it yields are passed through
+//! to the caller unchanged, since part of the answer may already have been
+//! consumed.
+//!
+//! To mix different provider types in one chain, use a provider enum such as
+//! `ferrous_llm::AnyProvider`.
+
+use crate::error::ProviderError;
+use crate::traits::*;
+use crate::types::*;
+use async_trait::async_trait;
+use futures::Stream;
+use serde_json::Value;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// Metadata extension holding the name of the provider that answered.
+pub const PROVIDER_EXTENSION: &str = "provider";
+
+type FallbackPredicate = Arc<dyn Fn(&dyn ProviderError) -> bool + Send + Sync>;
+
+/// A provider that fails over to the next provider in an ordered chain.
+///
+/// By default the chain moves on when an error is retryable, rate limited or
+/// reports the service as unavailable; any other error, and the error from
+/// the last provider, is returned to the caller. Streaming requests only fail
+/// over while establishing the stream.
+#[derive(Clone)]
+pub struct FallbackProvider<P> {
+    providers: Vec<(String, P)>,
+    should_fallback: FallbackPredicate,
+}
+
+impl<P> FallbackProvider<P> {
+    /// Create a chain starting with the given primary provider.
+    pub fn new(name: impl Into<String>, primary: P) -> Self {
+        Self {
+            providers: vec![(name.into(), primary)],
+            should_fallback: Arc::new(|error| {
+                error.is_retryable() || error.is_rate_limited() || error.is_service_unavailable()
+            }),
+        }
+    }
+
+    /// Append a provider to try after the ones already in the chain.
+    pub fn with_fallback(mut self, name: impl Into<String>, provider: P) -> Self {
+        self.providers.push((name.into(), provider));
+        self
+    }
+
+    /// Replace the rule deciding whether an error moves on to the next provider.
+    pub fn fallback_on<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&dyn ProviderError) -> bool + Send + Sync + 'static,
+    {
+        self.should_fallback = Arc::new(predicate);
+        self
+    }
+
+    /// Names of the providers in the chain, in order.
+    pub fn provider_names(&self) -> impl Iterator<Item = &str> {
+        self.providers.iter().map(|(name, _)| name.as_str())
+    }
+
+    fn split_last(&self) -> (&(String, P), &[(String, P)]) {
+        self.providers
+            .split_last()
+            .expect("fallback chain always has a primary provider")
+    }
+}
+
+impl<P> fmt::Debug for FallbackProvider<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FallbackProvider")
+            .field("providers", &self.provider_names().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A response from a [`FallbackProvider`], tagged with the provider that answered.
+#[derive(Debug, Clone)]
+pub struct FallbackResponse<R> {
+    inner: R,
+    provider: String,
+}
+
+impl<R> FallbackResponse<R> {
+    /// Name of the provider that produced this response.
+    pub fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    /// Get the underlying provider response.
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Unwrap the underlying provider response.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: ChatResponse> ChatResponse for FallbackResponse<R> {
+    fn content(&self) -> String {
+        self.inner.content()
+    }
+
+    fn usage(&self) -> Option<Usage> {
+        self.inner.usage()
+    }
+
+    fn finish_reason(&self) -> Option<FinishReason> {
+        self.inner.finish_reason()
+    }
+
+    fn metadata(&self) -> Metadata {
+        let mut metadata = self.inner.metadata();
+        metadata.extensions.insert(
+            PROVIDER_EXTENSION.to_string(),
+            Value::String(self.provider.clone()),
+        );
+        metadata
+    }
+
+    fn tool_calls(&self) -> Option<Vec<ToolCall>> {
+        self.inner.tool_calls()
+    }
+}
+
+/// A stream from a [`FallbackProvider`], tagged with the provider that answered.
+pub struct FallbackStream<S> {
+    inner: Pin<Box<S>>,
+    provider: String,
+}
+
+impl<S> FallbackStream<S> {
+    fn new(inner: S, provider: &str) -> Self {
+        Self {
+            inner: Box::pin(inner),
+            provider: provider.to_string(),
+        }
+    }
+
+    /// Name of the provider that produced this stream.
+    pub fn provider(&self) -> &str {
+        &self.provider
+    }
+}
+
+impl<S: Stream> Stream for FallbackStream<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+impl<S> fmt::Debug for FallbackStream<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FallbackStream")
+            .field("provider", &self.provider)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl<P: ChatProvider> ChatProvider for FallbackProvider<P> {
+    type Config = P::Config;
+    type Response = FallbackResponse<P::Response>;
+    type Error = P::Error;
+
+    async fn chat(&self, request: ChatRequest) -> Result<Self::Response, Self::Error> {
+        let ((last_name, last), rest) = self.split_last();
+        for (name, provider) in rest {
+            match provider.chat(request.clone()).await {
+                Ok(inner) => {
+                    return Ok(FallbackResponse {
+                        inner,
+                        provider: name.clone(),
+                    });
+                }
+                Err(error) if (self.should_fallback)(&error) => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        let inner = last.chat(request).await?;
+        Ok(FallbackResponse {
+            inner,
+            provider: last_name.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl<P: StreamingProvider> StreamingProvider for FallbackProvider<P> {
+    type StreamItem = P::StreamItem;
+    type Stream = FallbackStream<P::Stream>;
+
+    async fn chat_stream(&self, request: ChatRequest) -> Result<Self::Stream, Self::Error> {
+        let ((last_name, last), rest) = self.split_last();
+        for (name, provider) in rest {
+            match provider.chat_stream(request.clone()).await {
+                Ok(stream) => return Ok(FallbackStream::new(stream, name)),
+                Err(error) if (self.should_fallback)(&error) => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        let stream = last.chat_stream(request).await?;
+        Ok(FallbackStream::new(stream, last_name))
+    }
+}
+
+#[async_trait]
+impl<P: ToolProvider> ToolProvider for FallbackProvider<P> {
+    async fn chat_with_tools(
+        &self,
+        request: ChatRequest,
+        tools: &[Tool],
+    ) -> Result<Self::Response, Self::Error> {
+        let ((last_name, last), rest) = self.split_last();
+        for (name, provider) in rest {
+            match provider.chat_with_tools(request.clone(), tools).await {
+                Ok(inner) => {
+                    return Ok(FallbackResponse {
+                        inner,
+                        provider: name.clone(),
+                    });
+                }
+                Err(error) if (self.should_fallback)(&error) => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        let inner = last.chat_with_tools(request, tools).await?;
+        Ok(FallbackResponse {
+            inner,
+            provider: last_name.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl<P: StreamingToolProvider> StreamingToolProvider for FallbackProvider<P> {
+    async fn chat_stream_with_tools(
+        &self,
+        request: ChatRequest,
+        tools: &[Tool],
+    ) -> Result<Self::Stream, Self::Error> {
+        let ((last_name, last), rest) = self.split_last();
+        for (name, provider) in rest {
+            match provider
+                .chat_stream_with_tools(request.clone(), tools)
+                .await
+            {
+                Ok(stream) => return Ok(FallbackStream::new(stream, name)),
+                Err(error) if (self.should_fallback)(&error) => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        let stream = last.chat_stream_with_tools(request, tools).await?;
+        Ok(FallbackStream::new(stream, last_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{MockConfig, MockError, MockResponse};
+    use futures::{StreamExt, stream};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct MockProvider {
+        result: Result<&'static str, MockError>,
+        calls: AtomicU32,
+    }
+
+    impl MockProvider {
+        fn new(result: Result<&'static str, MockError>) -> Self {
+            Self {
+                result,
+                calls: AtomicU32::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ChatProvider for MockProvider {
+        type Config = MockConfig;
+        type Response = MockResponse;
+        type Error = MockError;
+
+        async fn chat(&self, _request: ChatRequest) -> Result<MockResponse, MockError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.result.map(MockResponse::text)
+        }
+    }
+
+    #[async_trait]
+    impl StreamingProvider for MockProvider {
+        type StreamItem = StreamEvent;
+        type Stream = stream::Iter<std::vec::IntoIter<Result<StreamEvent, MockError>>>;
+
+        async fn chat_stream(&self, _request: ChatRequest) -> Result<Self::Stream, MockError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let text = self.result?;
+            Ok(stream::iter(vec![Ok(StreamEvent::TextDelta {
+                text: text.to_string(),
+            })]))
+        }
+    }
+
+    fn request() -> ChatRequest {
+        ChatRequest::builder().user_message("Hello").build()
+    }
+
+    fn calls(chain: &FallbackProvider<MockProvider>) -> Vec<u32> {
+        chain
+            .providers
+            .iter()
+            .map(|(_, provider)| provider.calls.load(Ordering::SeqCst))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_falls_over_on_unavailable() {
+        let chain =
+            FallbackProvider::new("anthropic", MockProvider::new(Err(MockError::Unavailable)))
+                .with_fallback("openai", MockProvider::new(Ok("from openai")))
+                .with_fallback("ollama", MockProvider::new(Ok("from ollama")));
+
+        let response = chain.chat(request()).await.unwrap();
+        assert_eq!(response.content(), "from openai");
+        assert_eq!(response.provider(), "openai");
+        assert_eq!(
+            response.metadata().extensions.get(PROVIDER_EXTENSION),
+            Some(&Value::String("openai".to_string()))
+        );
+        assert_eq!(calls(&chain), [1, 1, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_stops_on_non_recoverable_error() {
+        let chain =
+            FallbackProvider::new("anthropic", MockProvider::new(Err(MockError::Unauthorized)))
+                .with_fallback("openai", MockProvider::new(Ok("from openai")));
+
+        let error = chain.chat(request()).await.err().unwrap();
+        assert!(error.is_auth_error());
+        assert_eq!(calls(&chain), [1, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_returns_last_error_when_all_fail() {
+        let chain = FallbackProvider::new("a", MockProvider::new(Err(MockError::Unavailable)))
+            .with_fallback("b", MockProvider::new(Err(MockError::Unauthorized)));
+
+        let error = chain.chat(request()).await.err().unwrap();
+        assert!(matches!(error, MockError::Unauthorized));
+        assert_eq!(calls(&chain), [1, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_custom_predicate() {
+        let chain = FallbackProvider::new("a", MockProvider::new(Err(MockError::Unauthorized)))
+            .with_fallback("b", MockProvider::new(Ok("from b")))
+            .fallback_on(|error| error.is_auth_error());
+
+        let response = chain.chat(request()).await.unwrap();
+        assert_eq!(response.provider(), "b");
+    }
+
+    #[tokio::test]
+    async fn test_stream_reports_answering_provider() {
+        let chain =
+            FallbackProvider::new("anthropic", MockProvider::new(Err(MockError::Unavailable)))
+                .with_fallback("openai", MockProvider::new(Ok("from openai")));
+
+        let stream = chain.chat_stream(request()).await.unwrap();
+        assert_eq!(stream.provider(), "openai");
+        let items: Vec<_> = stream.collect().await;
+        assert_eq!(items[0].as_ref().unwrap().text(), Some("from openai"));
+        assert_eq!(calls(&chain), [1, 1]);
+    }
+}
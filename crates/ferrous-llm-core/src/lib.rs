@@ -4,16 +4,38 @@
 //! implement, including traits for chat, completion, streaming, and tool calling,
 //! as well as standardized request/response types and error handling.
 
+pub mod catalog;
 pub mod config;
+pub mod dynamic;
 pub mod error;
+pub mod fallback;
+pub mod media;
+pub mod rate_limit;
+pub mod retry;
+pub mod stream;
+pub mod structured;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod tool_schema;
+pub mod tools;
 pub mod traits;
 pub mod types;
 #[cfg(feature = "dynamic-image")]
 mod util;
 
 // Re-export core types for convenience
+pub use catalog::*;
 pub use config::*;
+pub use dynamic::*;
 pub use error::*;
+pub use fallback::*;
+pub use media::*;
+pub use rate_limit::*;
+pub use retry::*;
+pub use stream::*;
+pub use structured::*;
+pub use tool_schema::*;
+pub use tools::*;
 pub use traits::*;
 pub use types::*;
 
@@ -26,3 +48,13 @@ pub use serde_json::Value;
 pub use std::collections::HashMap;
 pub use std::error::Error;
 pub use std::time::Duration;
+
+#[cfg(feature = "schemars")]
+pub use schemars::{self, JsonSchema};
+
+/// Dependencies of code generated by `ferrous-llm-macros`.
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+    pub use serde_json;
+}
@@ -0,0 +1,181 @@
+//! Helpers for preparing image input for providers.
+//!
+//! Providers differ in how they accept images: some fetch any URL themselves,
+//! others only accept inline base64 data. These helpers parse and build data
+//! URLs and validate downloaded image bytes, so providers that need inline
+//! data can fetch remote images before sending a request.
+
+use base64::{Engine, engine::general_purpose::STANDARD as B64};
+use thiserror::Error;
+
+/// Default limit on the size of a downloaded image (20 MiB).
+pub const DEFAULT_MAX_IMAGE_BYTES: usize = 20 * 1024 * 1024;
+
+/// Error preparing an image for a provider.
+#[derive(Debug, Clone, Error)]
+pub enum MediaError {
+    /// The image exceeds the size limit
+    #[error("Image is larger than the {limit} byte limit")]
+    TooLarge { limit: usize },
+
+    /// The data is not a supported image format
+    #[error("Unsupported image type: {media_type}")]
+    UnsupportedType { media_type: String },
+
+    /// The image data could not be decoded
+    #[error("Invalid image data: {message}")]
+    InvalidData { message: String },
+}
+
+/// Image data encoded as base64, with its media type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineImage {
+    /// Media type, e.g. `image/png`
+    pub media_type: String,
+    /// Base64-encoded image bytes
+    pub data: String,
+}
+
+impl InlineImage {
+    /// Encode downloaded image bytes.
+    ///
+    /// The media type is sniffed from the bytes, falling back to the
+    /// `Content-Type` reported by the server when the format isn't recognised.
+    pub fn from_bytes(
+        bytes: &[u8],
+        content_type: Option<&str>,
+        max_bytes: usize,
+    ) -> Result<Self, MediaError> {
+        if bytes.len() > max_bytes {
+            return Err(MediaError::TooLarge { limit: max_bytes });
+        }
+
+        let media_type = match sniff_image_type(bytes) {
+            Some(media_type) => media_type.to_string(),
+            None => {
+                let content_type = content_type
+                    .and_then(|value| value.split(';').next())
+                    .map(|value| value.trim().to_ascii_lowercase())
+                    .unwrap_or_default();
+                if !content_type.starts_with("image/") {
+                    return Err(MediaError::UnsupportedType {
+                        media_type: content_type,
+                    });
+                }
+                content_type
+            }
+        };
+
+        Ok(Self {
+            media_type,
+            data: B64.encode(bytes),
+        })
+    }
+
+    /// Format as a `data:` URL.
+    pub fn to_data_url(&self) -> String {
+        format!("data:{};base64,{}", self.media_type, self.data)
+    }
+
+    /// Decode the base64 data.
+    pub fn decode(&self) -> Result<Vec<u8>, MediaError> {
+        decode_base64(&self.data)
+    }
+}
+
+/// Decode standard base64 data.
+pub fn decode_base64(data: &str) -> Result<Vec<u8>, MediaError> {
+    B64.decode(data.trim())
+        .map_err(|error| MediaError::InvalidData {
+            message: error.to_string(),
+        })
+}
+
+/// Parse a base64 `data:` URL such as `data:image/png;base64,iVBORw0...`.
+///
+/// Returns `None` for anything that isn't a base64 data URL.
+pub fn parse_data_url(url: &str) -> Option<InlineImage> {
+    let (header, data) = url.strip_prefix("data:")?.split_once(',')?;
+    let media_type = header.strip_suffix(";base64")?;
+    let media_type = if media_type.is_empty() {
+        "application/octet-stream"
+    } else {
+        media_type
+    };
+
+    Some(InlineImage {
+        media_type: media_type.to_string(),
+        data: data.to_string(),
+    })
+}
+
+/// Whether a URL points at a remote `http` or `https` resource.
+pub fn is_remote_url(url: &str) -> bool {
+    url.split_once("://").is_some_and(|(scheme, _)| {
+        scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https")
+    })
+}
+
+/// Detect the media type of image data from its magic bytes.
+///
+/// Recognises the formats accepted by the major vision APIs: PNG, JPEG, GIF
+/// and WebP.
+pub fn sniff_image_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0];
+
+    #[test]
+    fn test_parse_data_url() {
+        let image = parse_data_url("data:image/png;base64,iVBORw0KGgo=").unwrap();
+        assert_eq!(image.media_type, "image/png");
+        assert_eq!(image.data, "iVBORw0KGgo=");
+        assert_eq!(image.to_data_url(), "data:image/png;base64,iVBORw0KGgo=");
+
+        assert!(parse_data_url("data:text/plain,hello").is_none());
+        assert!(parse_data_url("https://example.com/cat.png").is_none());
+    }
+
+    #[test]
+    fn test_is_remote_url() {
+        assert!(is_remote_url("https://example.com/cat.png"));
+        assert!(is_remote_url("HTTP://example.com/cat.png"));
+        assert!(!is_remote_url("data:image/png;base64,AAAA"));
+        assert!(!is_remote_url("file:///tmp/cat.png"));
+    }
+
+    #[test]
+    fn test_from_bytes_sniffs_type() {
+        // Sniffed type wins over a generic content type
+        let image = InlineImage::from_bytes(PNG, Some("application/octet-stream"), 1024).unwrap();
+        assert_eq!(image.media_type, "image/png");
+        assert_eq!(image.data, B64.encode(PNG));
+
+        let image = InlineImage::from_bytes(b"unknown", Some("image/avif; q=1"), 1024).unwrap();
+        assert_eq!(image.media_type, "image/avif");
+
+        assert!(matches!(
+            InlineImage::from_bytes(b"<html>", Some("text/html"), 1024),
+            Err(MediaError::UnsupportedType { .. })
+        ));
+        assert!(matches!(
+            InlineImage::from_bytes(PNG, None, 4),
+            Err(MediaError::TooLarge { limit: 4 })
+        ));
+    }
+}
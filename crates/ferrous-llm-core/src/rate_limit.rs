@@ -0,0 +1,404 @@
+//! Client-side rate limiting for provider requests.
+//!
+//! [`RateLimiter`] is a token bucket driven by [`RateLimitConfig`], with an
+//! optional tokens-per-minute budget that is charged from response [`Usage`].
+//! Limiters are cheap to clone and clones share the same bucket, so a single
+//! limiter can throttle every clone of a provider, or several providers that
+//! share one account quota. A `requests_per_second` that isn't positive or a
+//! `tokens_per_minute` of zero disables that limit rather than blocking
+//! forever.
+//!
+//! [`RateLimitedProvider`] wraps any provider and acquires a permit from its
+//! limiter before every request.
+
+use crate::config::RateLimitConfig;
+use crate::traits::*;
+use crate::types::*;
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A token-bucket rate limiter shared across clones.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    state: Arc<Mutex<BucketState>>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    /// Request permits currently available
+    requests: f64,
+    /// Tokens currently available in the per-minute budget; may go negative
+    /// when a response uses more than was left
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Create a limiter with a full bucket.
+    pub fn new(config: RateLimitConfig) -> Self {
+        let state = BucketState {
+            requests: f64::from(config.burst_capacity.max(1)),
+            tokens: tokens_per_minute(&config).unwrap_or_default(),
+            last_refill: Instant::now(),
+        };
+        Self {
+            config,
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Get the configuration this limiter was created with.
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// Wait until a request may be sent, then consume one request permit.
+    pub async fn acquire(&self) {
+        while let Err(wait) = self.try_acquire() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Consume a request permit if one is available right now.
+    ///
+    /// On failure, returns how long to wait before a permit is expected to
+    /// become available.
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        let mut state = self.lock();
+        self.refill(&mut state);
+
+        let mut wait = 0.0_f64;
+        if let Some(per_second) = requests_per_second(&self.config)
+            && state.requests < 1.0
+        {
+            wait = wait.max((1.0 - state.requests) / per_second);
+        }
+        if let Some(per_second) = self.tokens_per_second()
+            && state.tokens < 1.0
+        {
+            wait = wait.max((1.0 - state.tokens) / per_second);
+        }
+
+        if wait > 0.0 {
+            return Err(Duration::try_from_secs_f64(wait).unwrap_or(Duration::from_secs(60)));
+        }
+
+        state.requests -= 1.0;
+        Ok(())
+    }
+
+    /// Charge the tokens used by a completed request against the
+    /// tokens-per-minute budget.
+    pub fn record_usage(&self, usage: &Usage) {
+        if !self.config.enabled || tokens_per_minute(&self.config).is_none() {
+            return;
+        }
+
+        let mut state = self.lock();
+        self.refill(&mut state);
+        state.tokens -= f64::from(usage.total_tokens);
+    }
+
+    fn tokens_per_second(&self) -> Option<f64> {
+        tokens_per_minute(&self.config).map(|tokens| tokens / 60.0)
+    }
+
+    fn refill(&self, state: &mut BucketState) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+        state.last_refill = now;
+
+        if let Some(per_second) = requests_per_second(&self.config) {
+            let capacity = f64::from(self.config.burst_capacity.max(1));
+            state.requests = (state.requests + elapsed * per_second).min(capacity);
+        }
+
+        if let Some(tokens_per_minute) = tokens_per_minute(&self.config) {
+            state.tokens =
+                (state.tokens + elapsed * tokens_per_minute / 60.0).min(tokens_per_minute);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BucketState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// The request rate, or `None` if it doesn't limit anything.
+fn requests_per_second(config: &RateLimitConfig) -> Option<f64> {
+    let rate = config.requests_per_second;
+    (rate.is_finite() && rate > 0.0).then_some(rate)
+}
+
+/// The token budget, or `None` if there is none.
+fn tokens_per_minute(config: &RateLimitConfig) -> Option<f64> {
+    config
+        .tokens_per_minute
+        .filter(|&tokens| tokens > 0)
+        .map(f64::from)
+}
+
+impl From<RateLimitConfig> for RateLimiter {
+    fn from(config: RateLimitConfig) -> Self {
+        Self::new(config)
+    }
+}
+
+/// A provider wrapper that waits on a [`RateLimiter`] before every request.
+///
+/// Chat, completion and streaming responses report their [`Usage`] back to
+/// the limiter, so a tokens-per-minute budget is enforced across requests.
+#[derive(Debug, Clone)]
+pub struct RateLimitedProvider<P> {
+    inner: P,
+    limiter: RateLimiter,
+}
+
+impl<P> RateLimitedProvider<P> {
+    /// Wrap a provider with a new limiter built from `config`.
+    pub fn new(inner: P, config: RateLimitConfig) -> Self {
+        Self::with_limiter(inner, RateLimiter::new(config))
+    }
+
+    /// Wrap a provider with an existing, possibly shared, limiter.
+    pub fn with_limiter(inner: P, limiter: RateLimiter) -> Self {
+        Self { inner, limiter }
+    }
+
+    /// Get the wrapped provider.
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Get the limiter used by this provider.
+    pub fn limiter(&self) -> &RateLimiter {
+        &self.limiter
+    }
+
+    /// Unwrap the inner provider.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+/// Stream returned by a [`RateLimitedProvider`].
+pub type RateLimitedStream<E> = Pin<Box<dyn Stream<Item = Result<StreamEvent, E>> + Send>>;
+
+impl<P> RateLimitedProvider<P>
+where
+    P: StreamingProvider<StreamItem = StreamEvent>,
+{
+    /// Wrap a stream so usage events are charged against the limiter.
+    fn track_usage(&self, stream: P::Stream) -> RateLimitedStream<P::Error> {
+        let limiter = self.limiter.clone();
+        stream
+            .inspect(move |item| {
+                if let Ok(StreamEvent::Usage { usage }) = item {
+                    limiter.record_usage(usage);
+                }
+            })
+            .boxed()
+    }
+}
+
+#[async_trait]
+impl<P: ChatProvider> ChatProvider for RateLimitedProvider<P> {
+    type Config = P::Config;
+    type Response = P::Response;
+    type Error = P::Error;
+
+    async fn chat(&self, request: ChatRequest) -> Result<Self::Response, Self::Error> {
+        self.limiter.acquire().await;
+        let response = self.inner.chat(request).await?;
+        if let Some(usage) = response.usage() {
+            self.limiter.record_usage(&usage);
+        }
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl<P: CompletionProvider> CompletionProvider for RateLimitedProvider<P> {
+    type Config = P::Config;
+    type Response = P::Response;
+    type Error = P::Error;
+
+    async fn complete(&self, request: CompletionRequest) -> Result<Self::Response, Self::Error> {
+        self.limiter.acquire().await;
+        let response = self.inner.complete(request).await?;
+        if let Some(usage) = response.usage() {
+            self.limiter.record_usage(&usage);
+        }
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl<P> StreamingProvider for RateLimitedProvider<P>
+where
+    P: StreamingProvider<StreamItem = StreamEvent>,
+{
+    type StreamItem = StreamEvent;
+    type Stream = RateLimitedStream<P::Error>;
+
+    async fn chat_stream(&self, request: ChatRequest) -> Result<Self::Stream, Self::Error> {
+        self.limiter.acquire().await;
+        let stream = self.inner.chat_stream(request).await?;
+        Ok(self.track_usage(stream))
+    }
+}
+
+#[async_trait]
+impl<P: ToolProvider> ToolProvider for RateLimitedProvider<P> {
+    async fn chat_with_tools(
+        &self,
+        request: ChatRequest,
+        tools: &[Tool],
+    ) -> Result<Self::Response, Self::Error> {
+        self.limiter.acquire().await;
+        let response = self.inner.chat_with_tools(request, tools).await?;
+        if let Some(usage) = response.usage() {
+            self.limiter.record_usage(&usage);
+        }
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl<P> StreamingToolProvider for RateLimitedProvider<P>
+where
+    P: StreamingToolProvider + StreamingProvider<StreamItem = StreamEvent>,
+{
+    async fn chat_stream_with_tools(
+        &self,
+        request: ChatRequest,
+        tools: &[Tool],
+    ) -> Result<Self::Stream, Self::Error> {
+        self.limiter.acquire().await;
+        let stream = self.inner.chat_stream_with_tools(request, tools).await?;
+        Ok(self.track_usage(stream))
+    }
+}
+
+#[async_trait]
+impl<P: EmbeddingProvider> EmbeddingProvider for RateLimitedProvider<P> {
+    type Config = P::Config;
+    type Error = P::Error;
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Embedding>, Self::Error> {
+        self.limiter.acquire().await;
+        self.inner.embed(texts).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(requests_per_second: f64, burst_capacity: u32) -> RateLimitConfig {
+        RateLimitConfig {
+            requests_per_second,
+            burst_capacity,
+            tokens_per_minute: None,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_burst_then_throttle() {
+        let limiter = RateLimiter::new(config(1.0, 3));
+        for _ in 0..3 {
+            assert!(limiter.try_acquire().is_ok());
+        }
+        let wait = limiter.try_acquire().unwrap_err();
+        assert!(wait > Duration::from_millis(900) && wait <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_disabled_never_waits() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            enabled: false,
+            ..config(0.001, 1)
+        });
+        for _ in 0..100 {
+            assert!(limiter.try_acquire().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_clones_share_bucket() {
+        let limiter = RateLimiter::new(config(0.001, 2));
+        let clone = limiter.clone();
+        assert!(limiter.try_acquire().is_ok());
+        assert!(clone.try_acquire().is_ok());
+        assert!(limiter.try_acquire().is_err());
+        assert!(clone.try_acquire().is_err());
+    }
+
+    #[test]
+    fn test_token_budget() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            tokens_per_minute: Some(600),
+            ..config(100.0, 10)
+        });
+        assert!(limiter.try_acquire().is_ok());
+
+        limiter.record_usage(&Usage {
+            prompt_tokens: 400,
+            completion_tokens: 300,
+            total_tokens: 700,
+        });
+
+        // 101 tokens over budget at 10 tokens per second
+        let wait = limiter.try_acquire().unwrap_err();
+        assert!(wait > Duration::from_secs(9) && wait <= Duration::from_millis(10_100));
+    }
+
+    #[test]
+    fn test_zero_limits_are_disabled() {
+        let limiter = RateLimiter::new(config(0.0, 1));
+        for _ in 0..10 {
+            assert!(limiter.try_acquire().is_ok());
+        }
+
+        let limiter = RateLimiter::new(RateLimitConfig {
+            tokens_per_minute: Some(0),
+            ..config(-1.0, 1)
+        });
+        assert!(limiter.try_acquire().is_ok());
+        limiter.record_usage(&Usage {
+            prompt_tokens: 400,
+            completion_tokens: 300,
+            total_tokens: 700,
+        });
+        assert!(limiter.try_acquire().is_ok());
+
+        // Only the token budget is disabled; requests are still limited
+        let limiter = RateLimiter::new(RateLimitConfig {
+            tokens_per_minute: Some(0),
+            ..config(0.001, 1)
+        });
+        assert!(limiter.try_acquire().is_ok());
+        assert!(limiter.try_acquire().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_refill() {
+        let limiter = RateLimiter::new(config(50.0, 1));
+        limiter.acquire().await;
+
+        let start = Instant::now();
+        limiter.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(15));
+    }
+}
@@ -0,0 +1,256 @@
+//! Retry with exponential backoff for provider requests.
+//!
+//! Providers wrap their HTTP calls in [`retry`], which re-runs an operation
+//! while it fails with a [`ProviderError::is_retryable`] error, honouring any
+//! [`ProviderError::retry_after`] hint reported by the server.
+
+use crate::config::{HttpConfig, RetryConfig};
+use crate::error::ProviderError;
+use chrono::{DateTime, Utc};
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+impl From<&HttpConfig> for RetryConfig {
+    fn from(http: &HttpConfig) -> Self {
+        Self {
+            max_attempts: http.max_retries,
+            base_delay: http.retry_delay,
+            max_delay: http.max_retry_delay,
+            enabled: http.max_retries > 0,
+            ..Default::default()
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Compute the backoff delay before the given retry (zero-based).
+    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let exponent = i32::try_from(attempt).unwrap_or(i32::MAX);
+        let delay = self.base_delay.as_secs_f64() * self.backoff_multiplier.powi(exponent);
+        let delay = delay.min(self.max_delay.as_secs_f64());
+
+        let delay = if self.jitter {
+            // Keep at least half of the computed delay so retries still back off
+            delay / 2.0 + delay / 2.0 * random_fraction()
+        } else {
+            delay
+        };
+
+        Duration::try_from_secs_f64(delay).unwrap_or(self.max_delay)
+    }
+
+    /// Compute the delay before retrying after `error`, preferring the
+    /// server-provided hint (capped at `max_delay`) over the backoff schedule.
+    pub fn delay_for_error<E: ProviderError>(&self, error: &E, attempt: u32) -> Duration {
+        match error.retry_after() {
+            Some(retry_after) => retry_after.min(self.max_delay),
+            None => self.delay_for_attempt(attempt),
+        }
+    }
+}
+
+/// Run `operation`, retrying retryable errors according to `config`.
+///
+/// The operation runs at most `config.max_attempts + 1` times. Errors that are
+/// not retryable, and the error from the final attempt, are returned as-is.
+pub async fn retry<T, E, F, Fut>(config: &RetryConfig, mut operation: F) -> Result<T, E>
+where
+    E: ProviderError,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 0;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(error)
+                if config.enabled && attempt < config.max_attempts && error.is_retryable() =>
+            {
+                tokio::time::sleep(config.delay_for_error(&error, attempt)).await;
+                attempt += 1;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// Parse the value of an HTTP `Retry-After` header.
+///
+/// Accepts either a number of seconds or an HTTP date. Dates in the past
+/// yield a zero duration.
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<f64>() {
+        return Duration::try_from_secs_f64(seconds).ok();
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    let remaining = date.with_timezone(&Utc) - Utc::now();
+    Some(remaining.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// A random number in `[0, 1)`, seeded from the standard library's hasher keys.
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("test error")]
+    struct TestError {
+        retryable: bool,
+        retry_after: Option<Duration>,
+    }
+
+    impl ProviderError for TestError {
+        fn error_code(&self) -> Option<&str> {
+            None
+        }
+
+        fn is_retryable(&self) -> bool {
+            self.retryable
+        }
+
+        fn is_rate_limited(&self) -> bool {
+            false
+        }
+
+        fn is_auth_error(&self) -> bool {
+            false
+        }
+
+        fn retry_after(&self) -> Option<Duration> {
+            self.retry_after
+        }
+    }
+
+    fn fast_config(max_attempts: u32) -> RetryConfig {
+        RetryConfig {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            jitter: false,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_from_http_config() {
+        let http = HttpConfig {
+            max_retries: 5,
+            retry_delay: Duration::from_millis(250),
+            ..Default::default()
+        };
+        let config = RetryConfig::from(&http);
+        assert_eq!(config.max_attempts, 5);
+        assert_eq!(config.base_delay, Duration::from_millis(250));
+        assert!(config.enabled);
+
+        let http = HttpConfig {
+            max_retries: 0,
+            ..Default::default()
+        };
+        assert!(!RetryConfig::from(&http).enabled);
+    }
+
+    #[test]
+    fn test_backoff_delays() {
+        let config = RetryConfig {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(350),
+            jitter: false,
+            ..Default::default()
+        };
+        assert_eq!(config.delay_for_attempt(0), Duration::from_millis(100));
+        assert_eq!(config.delay_for_attempt(1), Duration::from_millis(200));
+        assert_eq!(config.delay_for_attempt(2), Duration::from_millis(350));
+        assert_eq!(
+            config.delay_for_attempt(u32::MAX),
+            Duration::from_millis(350)
+        );
+
+        let jittered = RetryConfig {
+            jitter: true,
+            ..config
+        };
+        for attempt in 0..5 {
+            let delay = jittered.delay_for_attempt(attempt);
+            let full = config.delay_for_attempt(attempt);
+            assert!(delay >= full / 2 && delay <= full);
+        }
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("3"), Some(Duration::from_secs(3)));
+        assert_eq!(
+            parse_retry_after(" 1.5 "),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon"), None);
+        assert_eq!(parse_retry_after("-1"), None);
+    }
+
+    #[tokio::test]
+    async fn test_retry_until_success() {
+        let calls = AtomicU32::new(0);
+        let result = retry(&fast_config(3), || async {
+            if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(TestError {
+                    retryable: true,
+                    retry_after: Some(Duration::from_millis(1)),
+                })
+            } else {
+                Ok("done")
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), "done");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = retry(&fast_config(2), || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(TestError {
+                retryable: true,
+                retry_after: None,
+            })
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = retry(&fast_config(2), || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(TestError {
+                retryable: false,
+                retry_after: None,
+            })
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,298 @@
+//! Helpers for consuming streaming chat responses.
+//!
+//! [`StreamAggregator`] folds a sequence of [`StreamEvent`]s back into a
+//! complete response, so streaming and non-streaming code paths can share the
+//! same post-processing through the [`ChatResponse`] trait.
+
+use crate::types::{
+    ChatResponse, FinishReason, FunctionCall, Metadata, StreamEvent, ToolCall, Usage,
+};
+use futures::{Stream, StreamExt};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::future::Future;
+
+/// Accumulates stream events into a complete chat response.
+#[derive(Debug, Default)]
+pub struct StreamAggregator {
+    id: Option<String>,
+    model: Option<String>,
+    content: String,
+    tool_calls: BTreeMap<usize, PartialToolCall>,
+    usage: Option<Usage>,
+    finish_reason: Option<FinishReason>,
+    error: Option<String>,
+}
+
+/// A tool call that is still being streamed.
+#[derive(Debug, Default)]
+struct PartialToolCall {
+    id: String,
+    name: String,
+    arguments: String,
+    complete: Option<ToolCall>,
+}
+
+impl PartialToolCall {
+    fn into_tool_call(self) -> ToolCall {
+        self.complete.unwrap_or(ToolCall {
+            id: self.id,
+            call_type: "function".to_string(),
+            function: FunctionCall {
+                name: self.name,
+                arguments: self.arguments,
+            },
+        })
+    }
+}
+
+impl StreamAggregator {
+    /// Create a new, empty aggregator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold a single stream event into the aggregated response.
+    pub fn push(&mut self, event: StreamEvent) {
+        match event {
+            StreamEvent::MessageStart { id, model } => {
+                self.id = id.or(self.id.take());
+                self.model = model.or(self.model.take());
+            }
+            StreamEvent::TextDelta { text } => self.content.push_str(&text),
+            StreamEvent::ToolCallStart { index, id, name } => {
+                let call = self.tool_calls.entry(index).or_default();
+                call.id = id;
+                call.name = name;
+            }
+            StreamEvent::ToolCallDelta { index, arguments } => {
+                self.tool_calls
+                    .entry(index)
+                    .or_default()
+                    .arguments
+                    .push_str(&arguments);
+            }
+            StreamEvent::ToolCallEnd { index, tool_call } => {
+                self.tool_calls.entry(index).or_default().complete = Some(tool_call);
+            }
+            StreamEvent::Usage { usage } => self.usage = Some(usage),
+            StreamEvent::Finish { reason } => self.finish_reason = Some(reason),
+            StreamEvent::Error { message } => {
+                self.finish_reason = Some(FinishReason::Error);
+                self.error = Some(message);
+            }
+            StreamEvent::Done => {}
+        }
+    }
+
+    /// Get the text accumulated so far.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Finish aggregation and build the response.
+    pub fn finish(self) -> AggregatedResponse {
+        let mut metadata = Metadata {
+            request_id: self.id,
+            ..Default::default()
+        };
+        if let Some(model) = self.model {
+            metadata
+                .extensions
+                .insert("model".to_string(), Value::String(model));
+        }
+
+        AggregatedResponse {
+            content: self.content,
+            tool_calls: self
+                .tool_calls
+                .into_values()
+                .map(PartialToolCall::into_tool_call)
+                .collect(),
+            usage: self.usage,
+            finish_reason: self.finish_reason,
+            error: self.error,
+            metadata,
+        }
+    }
+}
+
+/// A chat response rebuilt from a stream of events.
+#[derive(Debug, Clone)]
+pub struct AggregatedResponse {
+    /// The concatenated text content
+    pub content: String,
+    /// Tool calls made by the model, in stream order
+    pub tool_calls: Vec<ToolCall>,
+    /// Token usage, if the provider reported it
+    pub usage: Option<Usage>,
+    /// Why generation stopped
+    pub finish_reason: Option<FinishReason>,
+    /// Error message reported by the provider inside the stream
+    pub error: Option<String>,
+    /// Response metadata
+    pub metadata: Metadata,
+}
+
+impl ChatResponse for AggregatedResponse {
+    fn content(&self) -> String {
+        self.content.clone()
+    }
+
+    fn usage(&self) -> Option<Usage> {
+        self.usage.clone()
+    }
+
+    fn finish_reason(&self) -> Option<FinishReason> {
+        self.finish_reason.clone()
+    }
+
+    fn metadata(&self) -> Metadata {
+        self.metadata.clone()
+    }
+
+    fn tool_calls(&self) -> Option<Vec<ToolCall>> {
+        if self.tool_calls.is_empty() {
+            None
+        } else {
+            Some(self.tool_calls.clone())
+        }
+    }
+}
+
+/// Extension methods for streams of [`StreamEvent`]s.
+pub trait ChatStreamExt<E>: Stream<Item = Result<StreamEvent, E>> + Send + Sized {
+    /// Consume the stream and aggregate it into a complete response.
+    ///
+    /// Stops at the first transport error, or once [`StreamEvent::Done`] is seen.
+    fn aggregate(self) -> impl Future<Output = Result<AggregatedResponse, E>> + Send
+    where
+        E: Send,
+    {
+        async move {
+            let mut stream = std::pin::pin!(self);
+            let mut aggregator = StreamAggregator::new();
+
+            while let Some(event) = stream.next().await {
+                let event = event?;
+                let done = event.is_done();
+                aggregator.push(event);
+                if done {
+                    break;
+                }
+            }
+
+            Ok(aggregator.finish())
+        }
+    }
+}
+
+impl<S, E> ChatStreamExt<E> for S where S: Stream<Item = Result<StreamEvent, E>> + Send + Sized {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MessageContent, Role};
+    use futures::stream;
+
+    fn text_events() -> Vec<StreamEvent> {
+        vec![
+            StreamEvent::MessageStart {
+                id: Some("msg_1".to_string()),
+                model: Some("test-model".to_string()),
+            },
+            StreamEvent::text_delta("Hello, "),
+            StreamEvent::text_delta("world"),
+            StreamEvent::Usage {
+                usage: Usage {
+                    prompt_tokens: 3,
+                    completion_tokens: 2,
+                    total_tokens: 5,
+                },
+            },
+            StreamEvent::Finish {
+                reason: FinishReason::Stop,
+            },
+            StreamEvent::Done,
+        ]
+    }
+
+    #[test]
+    fn test_aggregate_text() {
+        let mut aggregator = StreamAggregator::new();
+        for event in text_events() {
+            aggregator.push(event);
+        }
+        let response = aggregator.finish();
+
+        assert_eq!(response.content(), "Hello, world");
+        assert_eq!(response.usage().unwrap().total_tokens, 5);
+        assert!(matches!(response.finish_reason(), Some(FinishReason::Stop)));
+        assert_eq!(response.metadata().request_id.as_deref(), Some("msg_1"));
+        assert!(response.tool_calls().is_none());
+
+        let message = response.as_message();
+        assert!(matches!(message.role, Role::Assistant));
+        assert!(
+            matches!(message.content, MessageContent::Text(ref text) if text == "Hello, world")
+        );
+    }
+
+    #[test]
+    fn test_aggregate_tool_calls() {
+        let mut aggregator = StreamAggregator::new();
+        aggregator.push(StreamEvent::ToolCallStart {
+            index: 1,
+            id: "call_b".to_string(),
+            name: "get_time".to_string(),
+        });
+        aggregator.push(StreamEvent::ToolCallStart {
+            index: 0,
+            id: "call_a".to_string(),
+            name: "get_weather".to_string(),
+        });
+        aggregator.push(StreamEvent::ToolCallDelta {
+            index: 0,
+            arguments: "{\"city\":".to_string(),
+        });
+        aggregator.push(StreamEvent::ToolCallDelta {
+            index: 0,
+            arguments: "\"Paris\"}".to_string(),
+        });
+        aggregator.push(StreamEvent::ToolCallDelta {
+            index: 1,
+            arguments: "{}".to_string(),
+        });
+
+        let tool_calls = aggregator.finish().tool_calls().unwrap();
+        assert_eq!(tool_calls.len(), 2);
+        assert_eq!(tool_calls[0].id, "call_a");
+        assert_eq!(tool_calls[0].function.arguments, "{\"city\":\"Paris\"}");
+        assert_eq!(tool_calls[1].function.name, "get_time");
+    }
+
+    #[test]
+    fn test_aggregate_in_band_error() {
+        let mut aggregator = StreamAggregator::new();
+        aggregator.push(StreamEvent::text_delta("partial"));
+        aggregator.push(StreamEvent::Error {
+            message: "overloaded".to_string(),
+        });
+        let response = aggregator.finish();
+
+        assert_eq!(response.content, "partial");
+        assert_eq!(response.error.as_deref(), Some("overloaded"));
+        assert!(matches!(response.finish_reason, Some(FinishReason::Error)));
+    }
+
+    #[tokio::test]
+    async fn test_stream_aggregate() {
+        let events = text_events().into_iter().map(Ok::<_, String>);
+        let response = stream::iter(events).aggregate().await.unwrap();
+        assert_eq!(response.content(), "Hello, world");
+
+        let events = vec![Ok(StreamEvent::text_delta("a")), Err("boom".to_string())];
+        let result = stream::iter(events).aggregate().await;
+        assert_eq!(result.unwrap_err(), "boom");
+    }
+}
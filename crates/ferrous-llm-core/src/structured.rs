@@ -0,0 +1,405 @@
+//! Structured output constrained by a JSON schema.
+//!
+//! Providers that implement [`StructuredProvider`] constrain the model to
+//! answer with JSON matching a [`ResponseSchema`], using their native
+//! mechanism: a JSON schema response format, a schema-constrained format, or
+//! a forced call to a tool whose parameters are the schema.
+//!
+//! [`StructuredOutput`] parses the answer into a typed value. When the model
+//! still produces something that doesn't parse, the error is sent back to it
+//! and it is asked to try again, up to a limit.
+
+use crate::error::{LlmError, ResponseError};
+use crate::traits::ChatProvider;
+use crate::types::*;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A JSON schema the model's answer must match.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseSchema {
+    /// Name of the schema, used as the tool or format name
+    pub name: String,
+    /// Description of what the answer should contain
+    pub description: Option<String>,
+    /// The JSON schema
+    pub schema: Value,
+    /// Whether the provider should enforce the schema exactly, where supported.
+    ///
+    /// OpenAI's strict mode requires every property to be listed as required
+    /// and `additionalProperties` to be false.
+    pub strict: bool,
+}
+
+impl ResponseSchema {
+    /// Create a schema with the given name.
+    pub fn new(name: impl Into<String>, schema: Value) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            schema,
+            strict: false,
+        }
+    }
+
+    /// Derive the schema of a type.
+    ///
+    /// The name is the type's schema name and the description its doc comment.
+    #[cfg(feature = "schemars")]
+    pub fn of<T: schemars::JsonSchema>() -> Self {
+        let mut schema = schemars::schema_for!(T).to_value();
+        let description = schema
+            .get("description")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        if let Some(object) = schema.as_object_mut() {
+            object.remove("$schema");
+            object.remove("title");
+        }
+
+        let name: String = T::schema_name()
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '-' => c,
+                _ => '_',
+            })
+            .collect();
+        Self {
+            name,
+            description,
+            schema,
+            strict: false,
+        }
+    }
+
+    /// Set the description.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set whether the schema is enforced exactly.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// A tool taking the schema as its parameters, for providers that
+    /// produce structured output by forcing a tool call.
+    pub fn as_tool(&self) -> Tool {
+        Tool::function(
+            self.name.clone(),
+            self.description
+                .clone()
+                .unwrap_or_else(|| "Respond with the structured answer.".to_string()),
+            self.schema.clone(),
+        )
+    }
+
+    /// Get the JSON answer from a response.
+    ///
+    /// This is the arguments of a call to the tool named after the schema
+    /// if there is one, and the response text otherwise.
+    pub fn answer(&self, response: &impl ChatResponse) -> String {
+        response
+            .tool_calls()
+            .unwrap_or_default()
+            .into_iter()
+            .find(|call| call.function.name == self.name)
+            .map(|call| call.function.arguments)
+            .unwrap_or_else(|| response.content())
+    }
+}
+
+/// Trait for providers that can constrain responses to a JSON schema.
+#[async_trait]
+pub trait StructuredProvider: ChatProvider {
+    /// Send a chat request whose answer must be JSON matching `schema`.
+    ///
+    /// Use [`ResponseSchema::answer`] to get the JSON from the response, or
+    /// [`StructuredOutput`] to parse and repair it.
+    async fn chat_structured(
+        &self,
+        request: ChatRequest,
+        schema: &ResponseSchema,
+    ) -> Result<Self::Response, Self::Error>;
+}
+
+/// A parsed structured answer.
+#[derive(Debug, Clone)]
+pub struct Structured<T, R> {
+    /// The parsed answer
+    pub value: T,
+    /// The response the answer was parsed from
+    pub response: R,
+    /// Number of requests made, including repairs
+    pub attempts: usize,
+}
+
+/// Requests structured output and parses it, asking the model to repair
+/// answers that don't parse.
+#[derive(Debug)]
+pub struct StructuredOutput<'a, P> {
+    provider: &'a P,
+    schema: ResponseSchema,
+    max_repairs: usize,
+}
+
+impl<'a, P: StructuredProvider> StructuredOutput<'a, P> {
+    /// Create a runner with the default limit of 2 repairs.
+    pub fn new(provider: &'a P, schema: ResponseSchema) -> Self {
+        Self {
+            provider,
+            schema,
+            max_repairs: 2,
+        }
+    }
+
+    /// Create a runner for the schema derived from `T`.
+    #[cfg(feature = "schemars")]
+    pub fn of<T: schemars::JsonSchema>(provider: &'a P) -> Self {
+        Self::new(provider, ResponseSchema::of::<T>())
+    }
+
+    /// Set the maximum number of times the model is asked to fix its answer.
+    pub fn max_repairs(mut self, max_repairs: usize) -> Self {
+        self.max_repairs = max_repairs;
+        self
+    }
+
+    /// Get the schema.
+    pub fn schema(&self) -> &ResponseSchema {
+        &self.schema
+    }
+
+    /// Send the request and parse the answer as `T`.
+    ///
+    /// Fails with [`ResponseError::ParseError`] if the answer still doesn't
+    /// parse after `max_repairs` repairs.
+    pub async fn generate<T: DeserializeOwned>(
+        &self,
+        mut request: ChatRequest,
+    ) -> Result<Structured<T, P::Response>, LlmError<P::Error>> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let response = self
+                .provider
+                .chat_structured(request.clone(), &self.schema)
+                .await
+                .map_err(LlmError::Provider)?;
+
+            let error = match parse_json(&self.schema.answer(&response)) {
+                Ok(value) => {
+                    return Ok(Structured {
+                        value,
+                        response,
+                        attempts: attempt,
+                    });
+                }
+                Err(error) => error,
+            };
+            if attempt > self.max_repairs {
+                return Err(LlmError::Response(ResponseError::ParseError {
+                    message: format!(
+                        "answer did not match schema '{}' after {attempt} attempts: {error}",
+                        self.schema.name
+                    ),
+                }));
+            }
+
+            self.push_repair(&mut request, &response, &error);
+        }
+    }
+
+    /// Append the rejected answer and a request to fix it.
+    fn push_repair(&self, request: &mut ChatRequest, response: &P::Response, error: &str) {
+        let feedback = format!(
+            "Your answer could not be parsed: {error}. Answer again with only JSON matching \
+             the {} schema.",
+            self.schema.name
+        );
+
+        let message = response.as_message();
+        let call_id = response
+            .tool_calls()
+            .unwrap_or_default()
+            .into_iter()
+            .find(|call| call.function.name == self.schema.name)
+            .map(|call| call.id);
+        request.messages.push(message);
+
+        // A tool call has to be answered with its result
+        match call_id {
+            Some(call_id) => request
+                .messages
+                .push(Message::tool_response(feedback, call_id)),
+            None => request.messages.push(Message::user(feedback)),
+        }
+    }
+}
+
+/// Parse a JSON answer, tolerating Markdown code fences and text around it.
+pub fn parse_json<T: DeserializeOwned>(text: &str) -> Result<T, String> {
+    let text = text.trim();
+    let error = match serde_json::from_str(text) {
+        Ok(value) => return Ok(value),
+        Err(error) => error.to_string(),
+    };
+
+    // Fall back to the outermost object or array
+    let start = text.find(['{', '[']);
+    let end = text.rfind(['}', ']']);
+    if let (Some(start), Some(end)) = (start, end)
+        && start < end
+        && let Ok(value) = serde_json::from_str(&text[start..=end])
+    {
+        return Ok(value);
+    }
+
+    Err(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{MockResponse, ScriptedProvider};
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Weather {
+        city: String,
+        celsius: f32,
+    }
+
+    fn schema() -> ResponseSchema {
+        ResponseSchema::new(
+            "weather",
+            json!({
+                "type": "object",
+                "properties": {
+                    "city": {"type": "string"},
+                    "celsius": {"type": "number"}
+                },
+                "required": ["city", "celsius"]
+            }),
+        )
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let weather: Weather = parse_json(r#"{"city": "Oslo", "celsius": -3}"#).unwrap();
+        assert_eq!(weather.city, "Oslo");
+
+        let fenced = "Here you go:\n```json\n{\"city\": \"Rome\", \"celsius\": 21.5}\n```";
+        let weather: Weather = parse_json(fenced).unwrap();
+        assert_eq!(weather.celsius, 21.5);
+
+        assert!(parse_json::<Weather>(r#"{"city": "Oslo"}"#).is_err());
+        assert!(parse_json::<Weather>("sunny").is_err());
+    }
+
+    #[test]
+    fn test_answer_prefers_schema_tool_call() {
+        let schema = schema();
+        let response = MockResponse::tool_call("weather", r#"{"city":"Oslo","celsius":1}"#);
+        assert_eq!(schema.answer(&response), r#"{"city":"Oslo","celsius":1}"#);
+
+        let response = MockResponse::tool_call("other", "{}");
+        assert_eq!(schema.answer(&response), "");
+        assert_eq!(schema.as_tool().function.name, "weather");
+    }
+
+    #[tokio::test]
+    async fn test_generate_repairs_invalid_answer() {
+        let provider = ScriptedProvider::new(vec![
+            MockResponse::text(r#"{"city": "Oslo"}"#),
+            MockResponse::text(r#"{"city": "Oslo", "celsius": -3}"#),
+        ]);
+        let request = ChatRequest::builder()
+            .user_message("Weather in Oslo?")
+            .build();
+
+        let result = StructuredOutput::new(&provider, schema())
+            .generate::<Weather>(request)
+            .await
+            .unwrap();
+        assert_eq!(result.value.celsius, -3.0);
+        assert_eq!(result.attempts, 2);
+
+        let requests = provider.requests.lock().unwrap();
+        let repaired = &requests[1].messages;
+        assert_eq!(repaired.len(), 3);
+        assert_eq!(repaired[1].role, Role::Assistant);
+        assert_eq!(repaired[2].role, Role::User);
+        let MessageContent::Text(feedback) = &repaired[2].content else {
+            panic!("expected text feedback");
+        };
+        assert!(feedback.contains("missing field `celsius`"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_repairs_tool_call_with_tool_result() {
+        let provider = ScriptedProvider::new(vec![
+            MockResponse::tool_call("weather", r#"{"city": 7}"#),
+            MockResponse::tool_call("weather", r#"{"city": "Oslo", "celsius": 2}"#),
+        ]);
+        let request = ChatRequest::builder().user_message("Weather?").build();
+
+        let result = StructuredOutput::new(&provider, schema())
+            .generate::<Weather>(request)
+            .await
+            .unwrap();
+        assert_eq!(result.value.city, "Oslo");
+
+        let requests = provider.requests.lock().unwrap();
+        let MessageContent::Tool(tool_content) = &requests[1].messages[2].content else {
+            panic!("expected a tool result");
+        };
+        assert_eq!(tool_content.tool_call_id.as_deref(), Some("call_1"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_gives_up_after_max_repairs() {
+        let provider = ScriptedProvider::new(vec![
+            MockResponse::text("sunny"),
+            MockResponse::text("still sunny"),
+        ]);
+        let request = ChatRequest::builder().user_message("Weather?").build();
+
+        let error = StructuredOutput::new(&provider, schema())
+            .max_repairs(1)
+            .generate::<Weather>(request)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            LlmError::Response(ResponseError::ParseError { .. })
+        ));
+        assert_eq!(provider.requests.lock().unwrap().len(), 2);
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schema_of_type() {
+        /// Current weather in a city.
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        struct CityWeather {
+            city: String,
+            celsius: f32,
+        }
+
+        let schema = ResponseSchema::of::<CityWeather>();
+        assert_eq!(schema.name, "CityWeather");
+        assert_eq!(
+            schema.description.as_deref(),
+            Some("Current weather in a city.")
+        );
+        assert_eq!(schema.schema["type"], "object");
+        assert!(schema.schema.get("$schema").is_none());
+        assert_eq!(schema.schema["required"], json!(["city", "celsius"]));
+    }
+}
@@ -0,0 +1,179 @@
+//! Mock providers shared by the unit tests.
+
+use crate::config::ProviderConfig;
+use crate::error::{ConfigError, ProviderError};
+use crate::structured::{ResponseSchema, StructuredProvider};
+use crate::traits::{ChatProvider, ToolProvider};
+use crate::types::*;
+use async_trait::async_trait;
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+pub(crate) enum MockError {
+    /// A failure that is neither retryable nor an auth error
+    #[error("mock failure")]
+    Failed,
+    #[error("service unavailable")]
+    Unavailable,
+    #[error("invalid api key")]
+    Unauthorized,
+}
+
+impl ProviderError for MockError {
+    fn error_code(&self) -> Option<&str> {
+        Some("mock")
+    }
+
+    fn is_retryable(&self) -> bool {
+        matches!(self, Self::Unavailable)
+    }
+
+    fn is_rate_limited(&self) -> bool {
+        false
+    }
+
+    fn is_auth_error(&self) -> bool {
+        matches!(self, Self::Unauthorized)
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        matches!(self, Self::Unavailable).then_some(Duration::from_secs(3))
+    }
+
+    fn is_service_unavailable(&self) -> bool {
+        matches!(self, Self::Unavailable)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct MockConfig;
+
+impl ProviderConfig for MockConfig {
+    type Provider = ScriptedProvider;
+
+    fn build(self) -> Result<Self::Provider, ConfigError> {
+        Err(ConfigError::validation_failed("not buildable"))
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct MockResponse {
+    pub content: String,
+    pub tool_calls: Vec<ToolCall>,
+}
+
+impl MockResponse {
+    pub fn text(content: &str) -> Self {
+        Self {
+            content: content.to_string(),
+            tool_calls: Vec::new(),
+        }
+    }
+
+    pub fn tool_calls(tool_calls: Vec<ToolCall>) -> Self {
+        Self {
+            content: String::new(),
+            tool_calls,
+        }
+    }
+
+    pub fn tool_call(name: &str, arguments: &str) -> Self {
+        Self::tool_calls(vec![tool_call("call_1", name, arguments)])
+    }
+}
+
+impl ChatResponse for MockResponse {
+    fn content(&self) -> String {
+        self.content.clone()
+    }
+
+    fn usage(&self) -> Option<Usage> {
+        None
+    }
+
+    fn finish_reason(&self) -> Option<FinishReason> {
+        if self.tool_calls.is_empty() {
+            Some(FinishReason::Stop)
+        } else {
+            Some(FinishReason::ToolCalls)
+        }
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata::default()
+    }
+
+    fn tool_calls(&self) -> Option<Vec<ToolCall>> {
+        Some(self.tool_calls.clone()).filter(|calls| !calls.is_empty())
+    }
+}
+
+pub(crate) fn tool_call(id: &str, name: &str, arguments: &str) -> ToolCall {
+    ToolCall {
+        id: id.to_string(),
+        call_type: "function".to_string(),
+        function: FunctionCall {
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+        },
+    }
+}
+
+/// Replays canned responses and records the requests it receives.
+pub(crate) struct ScriptedProvider {
+    responses: Mutex<Vec<MockResponse>>,
+    pub requests: Mutex<Vec<ChatRequest>>,
+}
+
+impl ScriptedProvider {
+    pub fn new(mut responses: Vec<MockResponse>) -> Self {
+        responses.reverse();
+        Self {
+            responses: Mutex::new(responses),
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl ChatProvider for ScriptedProvider {
+    type Config = MockConfig;
+    type Response = MockResponse;
+    type Error = MockError;
+
+    async fn chat(&self, request: ChatRequest) -> Result<MockResponse, MockError> {
+        self.requests.lock().unwrap().push(request);
+        self.responses
+            .lock()
+            .unwrap()
+            .pop()
+            .ok_or(MockError::Failed)
+    }
+}
+
+#[async_trait]
+impl ToolProvider for ScriptedProvider {
+    async fn chat_with_tools(
+        &self,
+        request: ChatRequest,
+        _tools: &[Tool],
+    ) -> Result<MockResponse, MockError> {
+        self.chat(request).await
+    }
+}
+
+#[async_trait]
+impl StructuredProvider for ScriptedProvider {
+    async fn chat_structured(
+        &self,
+        request: ChatRequest,
+        _schema: &ResponseSchema,
+    ) -> Result<MockResponse, MockError> {
+        self.chat(request).await
+    }
+}
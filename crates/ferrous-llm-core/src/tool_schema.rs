@@ -0,0 +1,208 @@
+//! JSON schemas for typed tool arguments.
+//!
+//! [`ToolSchema`] describes a Rust type as the JSON schema of a tool
+//! parameter. It is implemented for strings, numbers, booleans, options,
+//! vectors, maps and [`Value`], and derived for structs and enums with
+//! `#[derive(ToolSchema)]` from `ferrous-llm-macros`. [`ObjectSchema`] builds
+//! the schema of an object from typed fields, so parameter types and the
+//! schema sent to the model can't disagree.
+
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, HashMap};
+
+/// A type that can be described as the JSON schema of a tool parameter.
+pub trait ToolSchema {
+    /// The JSON schema of the type.
+    fn tool_schema() -> Value;
+
+    /// Whether a parameter of this type must be present.
+    fn required() -> bool {
+        true
+    }
+}
+
+macro_rules! impl_tool_schema {
+    ($schema:tt => $($ty:ty),+) => {
+        $(
+            impl ToolSchema for $ty {
+                fn tool_schema() -> Value {
+                    json!($schema)
+                }
+            }
+        )+
+    };
+}
+
+impl_tool_schema!({"type": "string"} => String, char);
+impl_tool_schema!({"type": "boolean"} => bool);
+impl_tool_schema!({"type": "integer"} => i8, i16, i32, i64, i128, isize);
+impl_tool_schema!({"type": "integer", "minimum": 0} => u8, u16, u32, u64, u128, usize);
+impl_tool_schema!({"type": "number"} => f32, f64);
+impl_tool_schema!({} => Value);
+
+impl<T: ToolSchema> ToolSchema for Option<T> {
+    fn tool_schema() -> Value {
+        T::tool_schema()
+    }
+
+    fn required() -> bool {
+        false
+    }
+}
+
+impl<T: ToolSchema> ToolSchema for Box<T> {
+    fn tool_schema() -> Value {
+        T::tool_schema()
+    }
+
+    fn required() -> bool {
+        T::required()
+    }
+}
+
+impl<T: ToolSchema> ToolSchema for Vec<T> {
+    fn tool_schema() -> Value {
+        json!({"type": "array", "items": T::tool_schema()})
+    }
+}
+
+impl<T: ToolSchema> ToolSchema for HashMap<String, T> {
+    fn tool_schema() -> Value {
+        json!({"type": "object", "additionalProperties": T::tool_schema()})
+    }
+}
+
+impl<T: ToolSchema> ToolSchema for BTreeMap<String, T> {
+    fn tool_schema() -> Value {
+        json!({"type": "object", "additionalProperties": T::tool_schema()})
+    }
+}
+
+/// Builder for the schema of a JSON object with typed properties.
+///
+/// ```rust
+/// use ferrous_llm_core::ObjectSchema;
+///
+/// let schema = ObjectSchema::new()
+///     .field::<String>("city", "Name of the city")
+///     .field::<Option<u32>>("days", "Number of days to forecast")
+///     .build();
+/// assert_eq!(schema["required"], serde_json::json!(["city"]));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ObjectSchema {
+    description: Option<String>,
+    properties: Map<String, Value>,
+    required: Vec<String>,
+    additional_properties: Option<bool>,
+}
+
+impl ObjectSchema {
+    /// Create an object schema with no properties.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the description of the object.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        let description = description.into();
+        self.description = (!description.is_empty()).then_some(description);
+        self
+    }
+
+    /// Add a property of type `T`; an empty description is omitted.
+    pub fn field<T: ToolSchema>(self, name: &str, description: &str) -> Self {
+        self.property(name, description, T::tool_schema(), T::required())
+    }
+
+    /// Add a property with an explicit schema.
+    pub fn property(
+        mut self,
+        name: &str,
+        description: &str,
+        mut schema: Value,
+        required: bool,
+    ) -> Self {
+        if !description.is_empty()
+            && let Some(object) = schema.as_object_mut()
+        {
+            object.insert("description".to_string(), json!(description));
+        }
+        self.properties.insert(name.to_string(), schema);
+        if required {
+            self.required.push(name.to_string());
+        }
+        self
+    }
+
+    /// Set whether properties other than the listed ones are allowed.
+    pub fn additional_properties(mut self, allowed: bool) -> Self {
+        self.additional_properties = Some(allowed);
+        self
+    }
+
+    /// Build the JSON schema.
+    pub fn build(self) -> Value {
+        let mut schema = json!({
+            "type": "object",
+            "properties": self.properties,
+            "required": self.required,
+        });
+        if let Some(description) = self.description {
+            schema["description"] = json!(description);
+        }
+        if let Some(allowed) = self.additional_properties {
+            schema["additionalProperties"] = json!(allowed);
+        }
+        schema
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitive_schemas() {
+        assert_eq!(String::tool_schema(), json!({"type": "string"}));
+        assert_eq!(u8::tool_schema(), json!({"type": "integer", "minimum": 0}));
+        assert_eq!(
+            Vec::<f64>::tool_schema(),
+            json!({"type": "array", "items": {"type": "number"}})
+        );
+        assert_eq!(Option::<bool>::tool_schema(), json!({"type": "boolean"}));
+        assert!(!Option::<bool>::required());
+        assert_eq!(
+            HashMap::<String, i32>::tool_schema(),
+            json!({"type": "object", "additionalProperties": {"type": "integer"}})
+        );
+    }
+
+    #[test]
+    fn test_object_schema() {
+        let schema = ObjectSchema::new()
+            .description("A forecast request")
+            .field::<String>("city", "Name of the city")
+            .field::<Option<Vec<String>>>("fields", "")
+            .build();
+
+        assert_eq!(
+            schema,
+            json!({
+                "type": "object",
+                "description": "A forecast request",
+                "properties": {
+                    "city": {"type": "string", "description": "Name of the city"},
+                    "fields": {"type": "array", "items": {"type": "string"}}
+                },
+                "required": ["city"]
+            })
+        );
+
+        let schema = ObjectSchema::new()
+            .field::<bool>("dry_run", "")
+            .additional_properties(false)
+            .build();
+        assert_eq!(schema["additionalProperties"], json!(false));
+    }
+}
@@ -0,0 +1,544 @@
+//! Tool registry and an agent loop that executes tool calls.
+//!
+//! [`ToolRegistry`] maps tool names to their definitions and async handlers.
+//! [`ToolRunner`] drives a [`ToolProvider`] conversation to completion: it
+//! sends the request with the registered tools, executes any tool calls the
+//! model makes, appends the results to the conversation and asks again, until
+//! the model answers without calling a tool.
+//!
+//! Tool failures are not fatal. Unknown tools, malformed arguments and handler
+//! errors are reported back to the model as the tool result so it can recover.
+//!
+//! Definitions can be derived from Rust code so they can't drift from their
+//! handlers: [`ToolArgs`] types carry their own schema and [`TypedTool`]s
+//! bundle a definition with the handler, usually generated by the
+//! `#[derive(ToolArgs)]` and `#[tool]` macros of `ferrous-llm-macros`.
+
+use crate::error::LlmError;
+use crate::tool_schema::ToolSchema;
+use crate::traits::ToolProvider;
+use crate::types::*;
+use async_trait::async_trait;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Error returned by a tool handler.
+#[derive(Debug, Clone, Error)]
+#[error("{message}")]
+pub struct ToolError {
+    /// Description of the failure, reported back to the model
+    pub message: String,
+}
+
+impl ToolError {
+    /// Create a tool error with the given message.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl From<serde_json::Error> for ToolError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::new(format!("Invalid arguments: {error}"))
+    }
+}
+
+/// An async function that executes a tool call.
+///
+/// Implemented for async closures taking the parsed JSON arguments.
+#[async_trait]
+pub trait ToolHandler: Send + Sync {
+    /// Execute the tool with the arguments supplied by the model.
+    async fn call(&self, arguments: Value) -> Result<Value, ToolError>;
+}
+
+#[async_trait]
+impl<F, Fut> ToolHandler for F
+where
+    F: Fn(Value) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<Value, ToolError>> + Send,
+{
+    async fn call(&self, arguments: Value) -> Result<Value, ToolError> {
+        self(arguments).await
+    }
+}
+
+/// Arguments of a tool, which describe the tool they belong to.
+///
+/// Usually derived with `#[derive(ToolArgs)]`, which takes the name from the
+/// type, the description from its doc comment and the schema from its fields.
+pub trait ToolArgs: ToolSchema + DeserializeOwned + Send + 'static {
+    /// Name of the tool
+    const NAME: &'static str;
+    /// Description of what the tool does
+    const DESCRIPTION: &'static str;
+
+    /// The tool definition to send to the provider.
+    fn definition() -> Tool {
+        Tool::function(Self::NAME, Self::DESCRIPTION, Self::tool_schema())
+    }
+}
+
+/// A tool handler that knows its own definition.
+///
+/// Generated by the `#[tool]` attribute from an async function.
+pub trait TypedTool: ToolHandler + 'static {
+    /// The tool definition to send to the provider.
+    fn definition(&self) -> Tool;
+}
+
+/// Adapts a handler taking typed arguments to a [`ToolHandler`].
+struct TypedHandler<A, F> {
+    handler: F,
+    arguments: PhantomData<fn(A)>,
+}
+
+#[async_trait]
+impl<A, F, Fut, R> ToolHandler for TypedHandler<A, F>
+where
+    A: ToolArgs,
+    F: Fn(A) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<R, ToolError>> + Send,
+    R: Serialize,
+{
+    async fn call(&self, arguments: Value) -> Result<Value, ToolError> {
+        let arguments = serde_json::from_value(arguments)?;
+        let output = (self.handler)(arguments).await?;
+        serde_json::to_value(output)
+            .map_err(|e| ToolError::new(format!("Invalid tool output: {e}")))
+    }
+}
+
+#[derive(Clone)]
+struct RegisteredTool {
+    definition: Tool,
+    handler: Arc<dyn ToolHandler>,
+}
+
+/// A set of tools the model may call, with the handlers that execute them.
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+    tools: BTreeMap<String, RegisteredTool>,
+}
+
+impl ToolRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a tool, replacing any existing tool with the same name.
+    pub fn register(&mut self, definition: Tool, handler: impl ToolHandler + 'static) -> &mut Self {
+        self.tools.insert(
+            definition.function.name.clone(),
+            RegisteredTool {
+                definition,
+                handler: Arc::new(handler),
+            },
+        );
+        self
+    }
+
+    /// Register a tool, returning the registry for chaining.
+    pub fn with_tool(mut self, definition: Tool, handler: impl ToolHandler + 'static) -> Self {
+        self.register(definition, handler);
+        self
+    }
+
+    /// Register a tool that knows its own definition, such as one generated by `#[tool]`.
+    pub fn register_tool(&mut self, tool: impl TypedTool) -> &mut Self {
+        let definition = tool.definition();
+        self.register(definition, tool)
+    }
+
+    /// Register a handler taking typed arguments, with the definition
+    /// derived from the argument type.
+    pub fn register_typed<A, F, Fut, R>(&mut self, handler: F) -> &mut Self
+    where
+        A: ToolArgs,
+        F: Fn(A) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R, ToolError>> + Send + 'static,
+        R: Serialize + 'static,
+    {
+        self.register(
+            A::definition(),
+            TypedHandler {
+                handler,
+                arguments: PhantomData,
+            },
+        )
+    }
+
+    /// Definitions of all registered tools, to send to the provider.
+    pub fn tools(&self) -> Vec<Tool> {
+        self.tools
+            .values()
+            .map(|tool| tool.definition.clone())
+            .collect()
+    }
+
+    /// Whether a tool with the given name is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.tools.contains_key(name)
+    }
+
+    /// Number of registered tools.
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    /// Whether the registry has no tools.
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    /// Execute a tool call made by the model.
+    pub async fn call(&self, tool_call: &ToolCall) -> Result<Value, ToolError> {
+        let name = &tool_call.function.name;
+        let tool = self
+            .tools
+            .get(name)
+            .ok_or_else(|| ToolError::new(format!("Unknown tool: {name}")))?;
+
+        let arguments = match tool_call.function.arguments.trim() {
+            "" => Value::Object(Default::default()),
+            arguments => serde_json::from_str(arguments)?,
+        };
+        tool.handler.call(arguments).await
+    }
+}
+
+impl fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field("tools", &self.tools.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// The outcome of executing a single tool call.
+#[derive(Debug, Clone)]
+pub struct ToolExecution {
+    /// The call made by the model
+    pub tool_call: ToolCall,
+    /// The handler's result
+    pub output: Result<Value, ToolError>,
+    /// How long the handler took
+    pub duration: Duration,
+}
+
+impl ToolExecution {
+    /// The text sent back to the model as the tool result.
+    pub fn result_text(&self) -> String {
+        match &self.output {
+            Ok(Value::String(text)) => text.clone(),
+            Ok(value) => value.to_string(),
+            Err(error) => json!({ "error": error.message }).to_string(),
+        }
+    }
+}
+
+/// One round of the agent loop: a model turn and the tools it called.
+#[derive(Debug, Clone)]
+pub struct ToolStep {
+    /// Zero-based iteration number
+    pub iteration: usize,
+    /// The assistant message containing the tool calls
+    pub message: Message,
+    /// The executed tool calls, in the order the model made them
+    pub executions: Vec<ToolExecution>,
+}
+
+/// The result of running a conversation to completion.
+#[derive(Debug, Clone)]
+pub struct ToolRun<R> {
+    /// The final response, which contains no tool calls
+    pub response: R,
+    /// The full conversation, including tool calls, results and the final answer
+    pub messages: Vec<Message>,
+    /// Every round of tool execution
+    pub steps: Vec<ToolStep>,
+}
+
+/// Drives the tool-calling loop for a [`ToolProvider`].
+#[derive(Debug)]
+pub struct ToolRunner<'a, P> {
+    provider: &'a P,
+    registry: &'a ToolRegistry,
+    max_iterations: usize,
+    parallel: bool,
+}
+
+impl<'a, P: ToolProvider> ToolRunner<'a, P> {
+    /// Create a runner with the default limit of 10 iterations.
+    pub fn new(provider: &'a P, registry: &'a ToolRegistry) -> Self {
+        Self {
+            provider,
+            registry,
+            max_iterations: 10,
+            parallel: true,
+        }
+    }
+
+    /// Set the maximum number of model turns that may call tools.
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set whether tool calls from the same turn run concurrently.
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Run the conversation until the model answers without calling tools.
+    ///
+    /// Fails with [`LlmError::ToolExecution`] if the model still calls tools
+    /// after `max_iterations` rounds.
+    pub async fn run(
+        &self,
+        mut request: ChatRequest,
+    ) -> Result<ToolRun<P::Response>, LlmError<P::Error>> {
+        let tools = self.registry.tools();
+        let mut steps = Vec::new();
+
+        for iteration in 0..=self.max_iterations {
+            let response = self
+                .provider
+                .chat_with_tools(request.clone(), &tools)
+                .await
+                .map_err(LlmError::Provider)?;
+
+            let tool_calls = response.tool_calls().unwrap_or_default();
+            if tool_calls.is_empty() {
+                request.messages.push(response.as_message());
+                return Ok(ToolRun {
+                    response,
+                    messages: request.messages,
+                    steps,
+                });
+            }
+            if iteration == self.max_iterations {
+                break;
+            }
+
+            let message = response.as_message();
+            request.messages.push(message.clone());
+
+            let executions = self.execute(tool_calls).await;
+            for execution in &executions {
+                request.messages.push(Message::tool_response(
+                    execution.result_text(),
+                    execution.tool_call.id.clone(),
+                ));
+            }
+
+            steps.push(ToolStep {
+                iteration,
+                message,
+                executions,
+            });
+        }
+
+        Err(LlmError::ToolExecution {
+            message: format!(
+                "Model still requested tools after {} iterations",
+                self.max_iterations
+            ),
+        })
+    }
+
+    async fn execute(&self, tool_calls: Vec<ToolCall>) -> Vec<ToolExecution> {
+        if self.parallel {
+            futures::future::join_all(tool_calls.into_iter().map(|call| self.execute_one(call)))
+                .await
+        } else {
+            let mut executions = Vec::with_capacity(tool_calls.len());
+            for call in tool_calls {
+                executions.push(self.execute_one(call).await);
+            }
+            executions
+        }
+    }
+
+    async fn execute_one(&self, tool_call: ToolCall) -> ToolExecution {
+        let start = Instant::now();
+        let output = self.registry.call(&tool_call).await;
+        ToolExecution {
+            tool_call,
+            output,
+            duration: start.elapsed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{MockResponse, ScriptedProvider, tool_call};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn registry() -> ToolRegistry {
+        ToolRegistry::new()
+            .with_tool(
+                Tool::function(
+                    "add",
+                    "Add two numbers",
+                    json!({"type": "object", "properties": {"a": {"type": "number"}, "b": {"type": "number"}}}),
+                ),
+                |arguments: Value| async move {
+                    let a = arguments["a"].as_f64().ok_or(ToolError::new("missing a"))?;
+                    let b = arguments["b"].as_f64().ok_or(ToolError::new("missing b"))?;
+                    Ok(json!(a + b))
+                },
+            )
+            .with_tool(
+                Tool::function("greet", "Say hello", json!({"type": "object"})),
+                |_arguments: Value| async move { Ok(json!("hello")) },
+            )
+    }
+
+    fn request() -> ChatRequest {
+        ChatRequest::builder()
+            .user_message("What is 1 + 2?")
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_runs_tools_until_answer() {
+        let provider = ScriptedProvider::new(vec![
+            MockResponse::tool_calls(vec![
+                tool_call("call_1", "add", r#"{"a": 1, "b": 2}"#),
+                tool_call("call_2", "greet", ""),
+            ]),
+            MockResponse::text("The answer is 3"),
+        ]);
+        let registry = registry();
+
+        let run = ToolRunner::new(&provider, &registry)
+            .run(request())
+            .await
+            .unwrap();
+
+        assert_eq!(run.response.content(), "The answer is 3");
+        assert_eq!(run.steps.len(), 1);
+        assert_eq!(run.steps[0].executions.len(), 2);
+
+        // user, assistant tool calls, two tool results, final answer
+        assert_eq!(run.messages.len(), 5);
+        assert!(matches!(run.messages[1].role, Role::Assistant));
+        match &run.messages[2].content {
+            MessageContent::Tool(content) => {
+                assert_eq!(content.tool_call_id.as_deref(), Some("call_1"));
+                assert_eq!(content.text.as_deref(), Some("3.0"));
+            }
+            other => panic!("unexpected content: {other:?}"),
+        }
+        match &run.messages[3].content {
+            MessageContent::Tool(content) => assert_eq!(content.text.as_deref(), Some("hello")),
+            other => panic!("unexpected content: {other:?}"),
+        }
+        assert!(matches!(run.messages[4].role, Role::Assistant));
+        match &run.messages[4].content {
+            MessageContent::Text(text) => assert_eq!(text, "The answer is 3"),
+            other => panic!("unexpected content: {other:?}"),
+        }
+
+        let requests = provider.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].messages.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_tool_errors_are_reported_to_model() {
+        let provider = ScriptedProvider::new(vec![
+            MockResponse::tool_calls(vec![
+                tool_call("call_1", "missing", "{}"),
+                tool_call("call_2", "add", "not json"),
+                tool_call("call_3", "add", r#"{"a": 1}"#),
+            ]),
+            MockResponse::text("Sorry"),
+        ]);
+        let registry = registry();
+
+        let run = ToolRunner::new(&provider, &registry)
+            .parallel(false)
+            .run(request())
+            .await
+            .unwrap();
+
+        let results: Vec<_> = run.steps[0]
+            .executions
+            .iter()
+            .map(ToolExecution::result_text)
+            .collect();
+        assert!(results[0].contains("Unknown tool: missing"));
+        assert!(results[1].contains("Invalid arguments"));
+        assert_eq!(results[2], r#"{"error":"missing b"}"#);
+    }
+
+    #[tokio::test]
+    async fn test_max_iterations() {
+        let provider = ScriptedProvider::new(vec![
+            MockResponse::tool_calls(vec![tool_call("call_1", "greet", "{}")]),
+            MockResponse::tool_calls(vec![tool_call("call_2", "greet", "{}")]),
+            MockResponse::tool_calls(vec![tool_call("call_3", "greet", "{}")]),
+        ]);
+        let registry = registry();
+
+        let result = ToolRunner::new(&provider, &registry)
+            .max_iterations(2)
+            .run(request())
+            .await;
+
+        assert!(matches!(result, Err(LlmError::ToolExecution { .. })));
+        assert_eq!(provider.requests.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_execution() {
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let registry = ToolRegistry::new().with_tool(
+            Tool::function("sleep", "Sleep briefly", json!({"type": "object"})),
+            {
+                let (active, peak) = (active.clone(), peak.clone());
+                move |_arguments: Value| {
+                    let (active, peak) = (active.clone(), peak.clone());
+                    async move {
+                        let running = active.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(running, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        active.fetch_sub(1, Ordering::SeqCst);
+                        Ok(Value::Null)
+                    }
+                }
+            },
+        );
+        let provider = ScriptedProvider::new(vec![
+            MockResponse::tool_calls(
+                (0..4)
+                    .map(|i| tool_call(&format!("call_{i}"), "sleep", "{}"))
+                    .collect(),
+            ),
+            MockResponse::text("done"),
+        ]);
+
+        ToolRunner::new(&provider, &registry)
+            .run(request())
+            .await
+            .unwrap();
+        assert_eq!(peak.load(Ordering::SeqCst), 4);
+    }
+}
@@ -101,6 +101,27 @@ pub trait ToolProvider: ChatProvider {
     ) -> Result<Self::Response, Self::Error>;
 }
 
+/// Optional trait for providers that support tool calling with streaming responses.
+///
+/// Tool calls are emitted as [`StreamEvent::ToolCallStart`],
+/// [`StreamEvent::ToolCallDelta`] and [`StreamEvent::ToolCallEnd`] events.
+#[async_trait]
+pub trait StreamingToolProvider: StreamingProvider + ToolProvider {
+    /// Send a chat request with available tools and receive a streaming response.
+    ///
+    /// # Arguments
+    /// * `request` - The chat request containing messages and parameters
+    /// * `tools` - Available tools that the model can call
+    ///
+    /// # Returns
+    /// A result containing a stream of response chunks or an error
+    async fn chat_stream_with_tools(
+        &self,
+        request: ChatRequest,
+        tools: &[Tool],
+    ) -> Result<Self::Stream, Self::Error>;
+}
+
 /// Trait for providers that support text embeddings.
 ///
 /// This is a separate capability from chat/completion as not all providers
@@ -194,3 +215,27 @@ pub trait TextToSpeechProvider: Send + Sync {
         request: TextToSpeechRequest,
     ) -> Result<Self::Response, Self::Error>;
 }
+
+/// Optional trait for providers that can stream synthesized speech.
+///
+/// This extends TextToSpeechProvider so playback can begin before synthesis
+/// finishes. Since a long synthesis can take longer than the configured
+/// request timeout, implementations should only time out when the server stops
+/// sending audio, not when the whole response takes too long.
+#[async_trait]
+pub trait StreamingTextToSpeechProvider: TextToSpeechProvider {
+    /// Stream type for the audio chunks
+    type AudioStream: Stream<Item = Result<Vec<u8>, Self::Error>> + Send + Unpin + 'static;
+
+    /// Convert text to speech, streaming audio chunks as they are produced.
+    ///
+    /// # Arguments
+    /// * `request` - The text-to-speech request
+    ///
+    /// # Returns
+    /// A result containing the audio format and a stream of audio chunks or an error
+    async fn text_to_speech_stream(
+        &self,
+        request: TextToSpeechRequest,
+    ) -> Result<SpeechStream<Self::AudioStream>, Self::Error>;
+}
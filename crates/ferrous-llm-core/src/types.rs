@@ -3,10 +3,18 @@
 //! This module defines standardized types that are used across all providers,
 //! including request/response structures, messages, and common data types.
 
+use crate::media::{MediaError, decode_base64, parse_data_url};
 use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::{collections::HashMap, fmt};
+use std::{
+    collections::HashMap,
+    fmt,
+    path::PathBuf,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 #[cfg(feature = "specta")]
 use specta::Type;
@@ -54,6 +62,10 @@ pub struct Parameters {
     pub frequency_penalty: Option<f32>,
     /// Number between -2.0 and 2.0. Positive values penalize new tokens based on whether they appear in the text so far
     pub presence_penalty: Option<f32>,
+    /// Which of the provided tools the model may or must call
+    pub tool_choice: Option<ToolChoice>,
+    /// Whether the model may call several tools in one turn
+    pub parallel_tool_calls: Option<bool>,
 }
 
 /// Metadata for requests, including provider-specific extensions.
@@ -326,6 +338,29 @@ pub struct Tool {
     pub function: Function,
 }
 
+/// Controls which tools the model calls.
+#[cfg_attr(feature = "specta", derive(Type))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ToolChoice {
+    /// The model decides whether to call tools
+    #[default]
+    Auto,
+    /// The model must not call tools
+    None,
+    /// The model must call at least one tool
+    Required,
+    /// The model must call the named function
+    Function { name: String },
+}
+
+impl ToolChoice {
+    /// Require a call to the named function.
+    pub fn function(name: impl Into<String>) -> Self {
+        Self::Function { name: name.into() }
+    }
+}
+
 /// Definition of a function that can be called.
 #[cfg_attr(feature = "specta", derive(Type))]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -339,6 +374,7 @@ pub struct Function {
 }
 
 /// Usage statistics for a request.
+#[cfg_attr(feature = "specta", derive(Type))]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Usage {
     /// Number of tokens in the prompt
@@ -350,6 +386,7 @@ pub struct Usage {
 }
 
 /// Reason why the model stopped generating.
+#[cfg_attr(feature = "specta", derive(Type))]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum FinishReason {
@@ -367,6 +404,91 @@ pub enum FinishReason {
     Error,
 }
 
+/// An incremental event emitted by a streaming chat response.
+///
+/// Providers translate their wire-level streaming formats into this common
+/// event type so that text, tool calls, usage and completion information can
+/// be consumed uniformly while the response is still being generated.
+#[cfg_attr(feature = "specta", derive(Type))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamEvent {
+    /// The provider started generating a message
+    MessageStart {
+        /// Provider-assigned message ID, if any
+        id: Option<String>,
+        /// Model generating the message, if reported
+        model: Option<String>,
+    },
+    /// A fragment of generated text
+    TextDelta {
+        /// Text to append to the message
+        text: String,
+    },
+    /// The model started a tool call
+    ToolCallStart {
+        /// Position of the tool call within the response
+        index: usize,
+        /// Unique identifier for the tool call
+        id: String,
+        /// Name of the function being called
+        name: String,
+    },
+    /// A fragment of a tool call's JSON arguments
+    ///
+    /// Always preceded by a [`StreamEvent::ToolCallStart`] with the same index.
+    ToolCallDelta {
+        /// Position of the tool call within the response
+        index: usize,
+        /// Partial JSON arguments to append
+        arguments: String,
+    },
+    /// A tool call is complete
+    ToolCallEnd {
+        /// Position of the tool call within the response
+        index: usize,
+        /// The fully assembled tool call
+        tool_call: ToolCall,
+    },
+    /// Token usage for the request
+    Usage {
+        /// Tokens used so far, or in total once the response is complete
+        usage: Usage,
+    },
+    /// The reason why the model stopped generating
+    Finish {
+        /// Why generation stopped
+        reason: FinishReason,
+    },
+    /// An error reported by the provider inside the stream
+    Error {
+        /// Error message from the provider
+        message: String,
+    },
+    /// The stream completed successfully
+    Done,
+}
+
+impl StreamEvent {
+    /// Create a text delta event
+    pub fn text_delta(text: impl Into<String>) -> Self {
+        Self::TextDelta { text: text.into() }
+    }
+
+    /// Get the text carried by this event, if it is a text delta
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            Self::TextDelta { text } => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Check whether this event marks the end of the stream
+    pub fn is_done(&self) -> bool {
+        matches!(self, Self::Done)
+    }
+}
+
 /// An embedding vector.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Embedding {
@@ -396,16 +518,85 @@ pub struct ImageRequest {
     pub metadata: Metadata,
 }
 
+/// Audio to transcribe, either in memory or on disk.
+#[cfg_attr(feature = "specta", derive(Type))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AudioInput {
+    /// Raw audio bytes
+    Bytes {
+        /// Encoded audio data
+        data: Vec<u8>,
+        /// File name to upload the audio as (optional)
+        file_name: Option<String>,
+    },
+    /// An audio file on disk
+    Path {
+        /// Path to the audio file
+        path: PathBuf,
+    },
+}
+
+impl AudioInput {
+    /// Create audio input from raw bytes.
+    pub fn bytes(data: impl Into<Vec<u8>>) -> Self {
+        Self::Bytes {
+            data: data.into(),
+            file_name: None,
+        }
+    }
+
+    /// Create audio input that reads the file at `path`.
+    pub fn path(path: impl Into<PathBuf>) -> Self {
+        Self::Path { path: path.into() }
+    }
+
+    /// Set the file name the audio is uploaded as.
+    ///
+    /// Files read from disk always use their own file name.
+    pub fn with_file_name(self, name: impl Into<String>) -> Self {
+        match self {
+            Self::Bytes { data, .. } => Self::Bytes {
+                data,
+                file_name: Some(name.into()),
+            },
+            path => path,
+        }
+    }
+
+    /// Get the file name of the audio, if known.
+    pub fn file_name(&self) -> Option<String> {
+        match self {
+            Self::Bytes { file_name, .. } => file_name.clone(),
+            Self::Path { path } => path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned()),
+        }
+    }
+
+    /// Load the audio bytes, reading the file if necessary.
+    pub async fn read(&self) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Bytes { data, .. } => Ok(data.clone()),
+            Self::Path { path } => tokio::fs::read(path).await,
+        }
+    }
+}
+
 /// Request for speech-to-text conversion.
 #[cfg_attr(feature = "specta", derive(Type))]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpeechToTextRequest {
-    /// Audio data (base64 encoded or file path)
-    pub audio: String,
+    /// Audio to transcribe
+    pub audio: AudioInput,
     /// Audio format (mp3, wav, etc.)
     pub format: String,
     /// Language of the audio (optional)
     pub language: Option<String>,
+    /// Response format (e.g. json, verbose_json, text, srt, vtt)
+    pub response_format: Option<String>,
+    /// Timestamp granularities to include (segment, word)
+    pub timestamp_granularities: Option<Vec<String>>,
     /// Additional metadata
     pub metadata: Metadata,
 }
@@ -503,6 +694,45 @@ pub struct GeneratedImage {
     pub revised_prompt: Option<String>,
 }
 
+impl GeneratedImage {
+    /// Decode the image bytes from `b64_json` or a base64 `data:` URL.
+    ///
+    /// Fails for images only available as a remote URL; request the
+    /// `b64_json` response format to receive the data inline.
+    pub fn bytes(&self) -> Result<Vec<u8>, MediaError> {
+        if let Some(data) = &self.b64_json {
+            return decode_base64(data);
+        }
+        match self.url.as_deref().and_then(parse_data_url) {
+            Some(image) => image.decode(),
+            None => Err(MediaError::InvalidData {
+                message: "image has no inline data".to_string(),
+            }),
+        }
+    }
+
+    /// Decode the image into a [`image::DynamicImage`].
+    #[cfg(feature = "dynamic-image")]
+    pub fn to_dynamic_image(&self) -> Result<image::DynamicImage, MediaError> {
+        let bytes = self.bytes()?;
+        image::load_from_memory(&bytes).map_err(|error| MediaError::InvalidData {
+            message: error.to_string(),
+        })
+    }
+}
+
+/// A timed segment of a transcription.
+#[cfg_attr(feature = "specta", derive(Type))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptionSegment {
+    /// Start time in seconds
+    pub start: f64,
+    /// End time in seconds
+    pub end: f64,
+    /// Text spoken in the segment
+    pub text: String,
+}
+
 /// Trait for speech-to-text response types.
 pub trait SpeechToTextResponse: Send + Sync {
     /// Get the transcribed text
@@ -511,6 +741,11 @@ pub trait SpeechToTextResponse: Send + Sync {
     /// Get the detected language if available
     fn language(&self) -> Option<String>;
 
+    /// Get timed segments if the provider returned them
+    fn segments(&self) -> Option<Vec<TranscriptionSegment>> {
+        None
+    }
+
     /// Get response metadata
     fn metadata(&self) -> Metadata;
 }
@@ -527,6 +762,51 @@ pub trait TextToSpeechResponse: Send + Sync {
     fn metadata(&self) -> Metadata;
 }
 
+/// Audio streamed from a text-to-speech provider.
+///
+/// The format is known as soon as the response starts, so playback can be set
+/// up before the first chunk arrives. The stream yields chunks of encoded
+/// audio as they are synthesized.
+pub struct SpeechStream<S> {
+    /// Audio format, e.g. "mp3" or "pcm"
+    pub format: String,
+    /// Content type reported by the provider, e.g. "audio/mpeg"
+    pub content_type: Option<String>,
+    /// Chunks of encoded audio
+    pub chunks: S,
+}
+
+impl<S, E> SpeechStream<S>
+where
+    S: Stream<Item = Result<Vec<u8>, E>> + Unpin,
+{
+    /// Read the remaining audio into a single buffer.
+    pub async fn into_audio(mut self) -> Result<Vec<u8>, E> {
+        let mut audio = Vec::new();
+        while let Some(chunk) = self.chunks.next().await {
+            audio.extend_from_slice(&chunk?);
+        }
+        Ok(audio)
+    }
+}
+
+impl<S: Stream + Unpin> Stream for SpeechStream<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.chunks).poll_next(cx)
+    }
+}
+
+impl<S> fmt::Debug for SpeechStream<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpeechStream")
+            .field("format", &self.format)
+            .field("content_type", &self.content_type)
+            .finish_non_exhaustive()
+    }
+}
+
 // Convenience constructors
 impl Message {
     /// Create a user message with text content
@@ -599,6 +879,24 @@ impl Message {
     }
 }
 
+impl Tool {
+    /// Create a function tool definition
+    pub fn function(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        parameters: Value,
+    ) -> Self {
+        Self {
+            tool_type: "function".to_string(),
+            function: Function {
+                name: name.into(),
+                description: description.into(),
+                parameters,
+            },
+        }
+    }
+}
+
 impl Default for Metadata {
     fn default() -> Self {
         Self {
@@ -669,6 +967,18 @@ impl ChatRequestBuilder {
         self
     }
 
+    /// Set which tools the model may or must call
+    pub fn tool_choice(mut self, tool_choice: ToolChoice) -> Self {
+        self.parameters.tool_choice = Some(tool_choice);
+        self
+    }
+
+    /// Set whether the model may call several tools in one turn
+    pub fn parallel_tool_calls(mut self, parallel_tool_calls: bool) -> Self {
+        self.parameters.parallel_tool_calls = Some(parallel_tool_calls);
+        self
+    }
+
     pub fn request_id(mut self, request_id: String) -> Self {
         self.metadata.request_id = Some(request_id);
         self
@@ -223,13 +223,19 @@ fn test_image_request() {
 #[test]
 fn test_speech_to_text_request() {
     let request = SpeechToTextRequest {
-        audio: "base64_encoded_audio_data".to_string(),
+        audio: AudioInput::bytes(b"ID3".to_vec()).with_file_name("speech.mp3"),
         format: "mp3".to_string(),
         language: Some("en".to_string()),
+        response_format: None,
+        timestamp_granularities: None,
         metadata: Metadata::default(),
     };
 
-    assert_eq!(request.audio, "base64_encoded_audio_data");
+    assert_eq!(request.audio.file_name(), Some("speech.mp3".to_string()));
+    assert_eq!(
+        AudioInput::path("/tmp/clip.wav").file_name(),
+        Some("clip.wav".to_string())
+    );
     assert_eq!(request.format, "mp3");
     assert_eq!(request.language, Some("en".to_string()));
 }
@@ -250,6 +256,19 @@ fn test_text_to_speech_request() {
     assert_eq!(request.speed, Some(1.0));
 }
 
+#[tokio::test]
+async fn test_speech_stream_into_audio() {
+    let chunks: Vec<Result<Vec<u8>, std::io::Error>> = vec![Ok(vec![1, 2]), Ok(vec![3])];
+    let stream = SpeechStream {
+        format: "pcm".to_string(),
+        content_type: None,
+        chunks: futures::stream::iter(chunks),
+    };
+
+    assert_eq!(stream.format, "pcm");
+    assert_eq!(stream.into_audio().await.unwrap(), vec![1, 2, 3]);
+}
+
 #[test]
 fn test_generated_image() {
     let image = GeneratedImage {
@@ -0,0 +1,231 @@
+//! Parsing of doc comments and serde attributes.
+
+use proc_macro_crate::{FoundCrate, crate_name};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::meta::ParseNestedMeta;
+use syn::{Attribute, Expr, Ident, Lit, LitStr, Meta, Token, ext::IdentExt, token};
+
+/// Path to the crate that defines the tool traits, as seen from the caller.
+pub(crate) fn core_path() -> TokenStream {
+    for name in ["ferrous-llm-core", "ferrous-llm"] {
+        match crate_name(name) {
+            Ok(FoundCrate::Itself) => return quote!(crate),
+            Ok(FoundCrate::Name(name)) => {
+                let ident = format_ident!("{name}");
+                return quote!(::#ident);
+            }
+            Err(_) => {}
+        }
+    }
+    quote!(::ferrous_llm_core)
+}
+
+/// Join the lines of a doc comment, keeping blank lines as paragraph breaks.
+pub(crate) fn doc_comment(attrs: &[Attribute]) -> String {
+    let mut paragraphs: Vec<String> = vec![String::new()];
+    for attr in attrs {
+        let Meta::NameValue(meta) = &attr.meta else {
+            continue;
+        };
+        if !meta.path.is_ident("doc") {
+            continue;
+        }
+        let Expr::Lit(expr) = &meta.value else {
+            continue;
+        };
+        let Lit::Str(line) = &expr.lit else {
+            continue;
+        };
+
+        let line = line.value();
+        let line = line.trim();
+        let paragraph = paragraphs.last_mut().expect("at least one paragraph");
+        if line.is_empty() {
+            if !paragraph.is_empty() {
+                paragraphs.push(String::new());
+            }
+        } else {
+            if !paragraph.is_empty() {
+                paragraph.push(' ');
+            }
+            paragraph.push_str(line);
+        }
+    }
+    paragraphs.retain(|paragraph| !paragraph.is_empty());
+    paragraphs.join("\n\n")
+}
+
+/// Remove doc comments, which aren't allowed on function parameters.
+pub(crate) fn strip_docs(attrs: &mut Vec<Attribute>) {
+    attrs.retain(|attr| !attr.path().is_ident("doc"));
+}
+
+/// The serde attributes that change the shape of the JSON.
+#[derive(Default)]
+pub(crate) struct SerdeAttrs {
+    pub rename: Option<String>,
+    pub rename_all: Option<String>,
+    pub default: bool,
+    pub skip: bool,
+    pub flatten: bool,
+    pub deny_unknown_fields: bool,
+}
+
+impl SerdeAttrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut serde = Self::default();
+        for attr in attrs {
+            if !attr.path().is_ident("serde") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    serde.rename = deserialize_name(&meta)?.or(serde.rename.take());
+                } else if meta.path.is_ident("rename_all") {
+                    serde.rename_all = deserialize_name(&meta)?.or(serde.rename_all.take());
+                } else if meta.path.is_ident("default") {
+                    serde.default = true;
+                    skip_meta(&meta)?;
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                    serde.skip = true;
+                } else if meta.path.is_ident("flatten") {
+                    serde.flatten = true;
+                } else if meta.path.is_ident("deny_unknown_fields") {
+                    serde.deny_unknown_fields = true;
+                } else {
+                    skip_meta(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(serde)
+    }
+}
+
+/// Options of the `#[tool(...)]` and `#[tool_args(...)]` attributes.
+#[derive(Default)]
+pub(crate) struct ToolAttrs {
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+impl ToolAttrs {
+    pub(crate) fn parse_meta(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("name") {
+            self.name = Some(meta.value()?.parse::<LitStr>()?.value());
+        } else if meta.path.is_ident("description") {
+            self.description = Some(meta.value()?.parse::<LitStr>()?.value());
+        } else {
+            return Err(meta.error("expected `name` or `description`"));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn parse(attrs: &[Attribute], attr_name: &str) -> syn::Result<Self> {
+        let mut tool = Self::default();
+        for attr in attrs {
+            if attr.path().is_ident(attr_name) {
+                attr.parse_nested_meta(|meta| tool.parse_meta(meta))?;
+            }
+        }
+        Ok(tool)
+    }
+}
+
+/// Read `= "name"` or `(deserialize = "name")`.
+fn deserialize_name(meta: &ParseNestedMeta) -> syn::Result<Option<String>> {
+    if meta.input.peek(Token![=]) {
+        return Ok(Some(meta.value()?.parse::<LitStr>()?.value()));
+    }
+    let mut name = None;
+    meta.parse_nested_meta(|nested| {
+        let value = nested.value()?.parse::<LitStr>()?.value();
+        if nested.path.is_ident("deserialize") {
+            name = Some(value);
+        }
+        Ok(())
+    })?;
+    Ok(name)
+}
+
+/// Skip the value of an attribute we don't need.
+fn skip_meta(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(token::Paren) {
+        meta.parse_nested_meta(|nested| skip_meta(&nested))?;
+    }
+    Ok(())
+}
+
+/// JSON name of a field or variant, applying serde renames.
+pub(crate) fn json_name(
+    ident: &Ident,
+    serde: &SerdeAttrs,
+    rename_all: Option<&str>,
+) -> syn::Result<String> {
+    if let Some(rename) = &serde.rename {
+        return Ok(rename.clone());
+    }
+    let name = ident.unraw().to_string();
+    match rename_all {
+        Some(rule) => apply_rename_rule(rule, &name)
+            .ok_or_else(|| syn::Error::new(ident.span(), format!("unknown rename rule `{rule}`"))),
+        None => Ok(name),
+    }
+}
+
+/// Apply a serde `rename_all` rule to a snake_case field or PascalCase variant name.
+fn apply_rename_rule(rule: &str, name: &str) -> Option<String> {
+    let words = split_words(name);
+    let capitalize = |word: &String| {
+        let mut chars = word.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+            .unwrap_or_default()
+    };
+
+    Some(match rule {
+        "lowercase" => name.to_lowercase(),
+        "UPPERCASE" => name.to_uppercase(),
+        "PascalCase" => words.iter().map(capitalize).collect(),
+        "camelCase" => {
+            let mut words = words.iter();
+            let first = words.next().cloned().unwrap_or_default();
+            first + &words.map(capitalize).collect::<String>()
+        }
+        "snake_case" => words.join("_"),
+        "SCREAMING_SNAKE_CASE" => words.join("_").to_uppercase(),
+        "kebab-case" => words.join("-"),
+        "SCREAMING-KEBAB-CASE" => words.join("-").to_uppercase(),
+        _ => return None,
+    })
+}
+
+/// Split a snake_case or PascalCase name into lowercase words.
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for part in name.split('_').filter(|part| !part.is_empty()) {
+        let mut word = String::new();
+        for c in part.chars() {
+            if c.is_uppercase() && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            word.extend(c.to_lowercase());
+        }
+        words.push(word);
+    }
+    words
+}
+
+/// Convert a snake_case name to PascalCase.
+pub(crate) fn pascal_case(name: &str) -> String {
+    apply_rename_rule("PascalCase", name).unwrap_or_default()
+}
+
+/// Convert a PascalCase name to snake_case.
+pub(crate) fn snake_case(name: &str) -> String {
+    apply_rename_rule("snake_case", name).unwrap_or_default()
+}
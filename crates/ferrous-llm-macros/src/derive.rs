@@ -0,0 +1,148 @@
+//! `#[derive(ToolSchema)]` and `#[derive(ToolArgs)]`.
+
+use crate::attrs::{SerdeAttrs, ToolAttrs, core_path, doc_comment, json_name, snake_case};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, parse_quote, spanned::Spanned};
+
+pub(crate) fn tool_schema(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let core = core_path();
+    let schema = schema_body(input, &core)?;
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(#core::ToolSchema));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #core::ToolSchema for #name #ty_generics #where_clause {
+            fn tool_schema() -> #core::__private::serde_json::Value {
+                #schema
+            }
+        }
+    })
+}
+
+pub(crate) fn tool_args(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Struct(_) = &input.data else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "ToolArgs can only be derived for structs",
+        ));
+    };
+
+    let core = core_path();
+    let tool = ToolAttrs::parse(&input.attrs, "tool_args")?;
+    let tool_name = tool
+        .name
+        .unwrap_or_else(|| snake_case(&input.ident.to_string()));
+    let description = tool
+        .description
+        .unwrap_or_else(|| doc_comment(&input.attrs));
+    if description.is_empty() {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "add a doc comment or #[tool_args(description = \"...\")] describing the tool",
+        ));
+    }
+
+    let schema = tool_schema(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #schema
+
+        impl #impl_generics #core::ToolArgs for #name #ty_generics #where_clause {
+            const NAME: &'static str = #tool_name;
+            const DESCRIPTION: &'static str = #description;
+        }
+    })
+}
+
+/// Expression building the schema of a struct or enum.
+fn schema_body(input: &DeriveInput, core: &TokenStream) -> syn::Result<TokenStream> {
+    let serde = SerdeAttrs::parse(&input.attrs)?;
+    let rename_all = serde.rename_all.as_deref();
+    let description = doc_comment(&input.attrs);
+
+    match &input.data {
+        Data::Struct(data) => {
+            let Fields::Named(fields) = &data.fields else {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "tool schemas can only be derived for structs with named fields",
+                ));
+            };
+
+            let mut properties = Vec::new();
+            for field in &fields.named {
+                let field_serde = SerdeAttrs::parse(&field.attrs)?;
+                if field_serde.skip {
+                    continue;
+                }
+                if field_serde.flatten {
+                    return Err(syn::Error::new(
+                        field.span(),
+                        "#[serde(flatten)] is not supported in tool schemas",
+                    ));
+                }
+
+                let ident = field.ident.as_ref().expect("named field");
+                let name = json_name(ident, &field_serde, rename_all)?;
+                let doc = doc_comment(&field.attrs);
+                let ty = &field.ty;
+                properties.push(if serde.default || field_serde.default {
+                    quote! {
+                        .property(#name, #doc, <#ty as #core::ToolSchema>::tool_schema(), false)
+                    }
+                } else {
+                    quote!(.field::<#ty>(#name, #doc))
+                });
+            }
+
+            let additional_properties = serde
+                .deny_unknown_fields
+                .then(|| quote!(.additional_properties(false)));
+            Ok(quote! {
+                #core::ObjectSchema::new()
+                    .description(#description)
+                    #(#properties)*
+                    #additional_properties
+                    .build()
+            })
+        }
+        Data::Enum(data) => {
+            let mut values = Vec::new();
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(syn::Error::new(
+                        variant.span(),
+                        "tool schemas can only be derived for enums with unit variants",
+                    ));
+                }
+                let variant_serde = SerdeAttrs::parse(&variant.attrs)?;
+                if !variant_serde.skip {
+                    values.push(json_name(&variant.ident, &variant_serde, rename_all)?);
+                }
+            }
+
+            Ok(quote! {{
+                let mut schema = #core::__private::serde_json::json!({
+                    "type": "string",
+                    "enum": [#(#values),*],
+                });
+                if !#description.is_empty() {
+                    schema["description"] = #core::__private::serde_json::json!(#description);
+                }
+                schema
+            }})
+        }
+        Data::Union(_) => Err(syn::Error::new(
+            input.ident.span(),
+            "tool schemas can't be derived for unions",
+        )),
+    }
+}
@@ -0,0 +1,86 @@
+//! Procedural macros for ferrous-llm tool definitions.
+//!
+//! These macros derive [`Tool`](ferrous_llm_core::Tool) definitions from Rust
+//! code, so the JSON schema sent to the model always matches the types the
+//! handler deserializes:
+//!
+//! - `#[tool]` turns an async function into a tool that can be registered
+//!   with [`ToolRegistry::register_tool`](ferrous_llm_core::ToolRegistry::register_tool).
+//! - `#[derive(ToolArgs)]` makes a struct the arguments of a tool, for
+//!   [`ToolRegistry::register_typed`](ferrous_llm_core::ToolRegistry::register_typed).
+//! - `#[derive(ToolSchema)]` describes a struct or enum used as a parameter.
+//!
+//! Descriptions come from doc comments, and serde's `rename`, `rename_all`,
+//! `default`, `skip` and `deny_unknown_fields` attributes are reflected in the
+//! schema.
+
+use proc_macro::TokenStream;
+use syn::{DeriveInput, ItemFn, parse_macro_input};
+
+mod attrs;
+mod derive;
+mod tool_fn;
+
+/// Turn an async function into a tool.
+///
+/// Generates a unit struct named after the function (`get_weather` becomes
+/// `GetWeatherTool`) implementing `TypedTool`. The tool name defaults to the
+/// function name and the description to its doc comment; both can be set
+/// with `#[tool(name = "...", description = "...")]`. Doc comments on
+/// parameters become their descriptions and `Option` parameters are optional.
+///
+/// The function may return any `Serialize` value, or a `Result` whose error
+/// is reported back to the model.
+///
+/// ```rust
+/// use ferrous_llm_core::ToolRegistry;
+/// use ferrous_llm_macros::tool;
+///
+/// /// Get the current weather for a city.
+/// #[tool]
+/// async fn get_weather(
+///     /// Name of the city
+///     city: String,
+///     /// Temperature unit, celsius by default
+///     unit: Option<String>,
+/// ) -> String {
+///     format!("22 degrees {} in {city}", unit.unwrap_or("celsius".into()))
+/// }
+///
+/// let mut registry = ToolRegistry::new();
+/// registry.register_tool(GetWeatherTool);
+/// assert_eq!(registry.tools()[0].function.name, "get_weather");
+/// ```
+#[proc_macro_attribute]
+pub fn tool(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut attrs = attrs::ToolAttrs::default();
+    let parser = syn::meta::parser(|meta| attrs.parse_meta(meta));
+    parse_macro_input!(attr with parser);
+    let function = parse_macro_input!(item as ItemFn);
+
+    tool_fn::expand(attrs, function)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `ToolArgs` and `ToolSchema` for the arguments of a tool.
+///
+/// The tool name defaults to the struct name in snake_case and the
+/// description to its doc comment; both can be set with
+/// `#[tool_args(name = "...", description = "...")]`.
+#[proc_macro_derive(ToolArgs, attributes(tool_args, serde))]
+pub fn derive_tool_args(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive::tool_args(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `ToolSchema` for a struct with named fields or an enum of unit variants.
+#[proc_macro_derive(ToolSchema, attributes(serde))]
+pub fn derive_tool_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive::tool_schema(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
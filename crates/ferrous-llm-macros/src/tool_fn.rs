@@ -0,0 +1,135 @@
+//! `#[tool]` on async functions.
+
+use crate::attrs::{ToolAttrs, core_path, doc_comment, pascal_case, strip_docs};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{FnArg, ItemFn, Pat, ReturnType, Type, spanned::Spanned};
+
+pub(crate) fn expand(attrs: ToolAttrs, mut function: ItemFn) -> syn::Result<TokenStream> {
+    let core = core_path();
+    let sig = &function.sig;
+    if sig.asyncness.is_none() {
+        return Err(syn::Error::new(
+            sig.fn_token.span(),
+            "#[tool] functions must be async",
+        ));
+    }
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            sig.generics.span(),
+            "#[tool] functions can't be generic",
+        ));
+    }
+
+    let fn_name = sig.ident.clone();
+    let tool_name = attrs.name.unwrap_or_else(|| fn_name.to_string());
+    let description = attrs
+        .description
+        .unwrap_or_else(|| doc_comment(&function.attrs));
+    if description.is_empty() {
+        return Err(syn::Error::new(
+            fn_name.span(),
+            "add a doc comment or #[tool(description = \"...\")] describing the tool",
+        ));
+    }
+
+    let mut names = Vec::new();
+    let mut fields = Vec::new();
+    let mut properties = Vec::new();
+    for input in function.sig.inputs.iter_mut() {
+        let FnArg::Typed(param) = input else {
+            return Err(syn::Error::new(
+                input.span(),
+                "#[tool] functions can't take self",
+            ));
+        };
+        let Pat::Ident(pat) = &*param.pat else {
+            return Err(syn::Error::new(
+                param.pat.span(),
+                "#[tool] parameters must be plain identifiers",
+            ));
+        };
+        if matches!(&*param.ty, Type::Reference(_)) {
+            return Err(syn::Error::new(
+                param.ty.span(),
+                "#[tool] parameters must be owned types",
+            ));
+        }
+
+        let ident = &pat.ident;
+        let name = ident.to_string();
+        let name = name.trim_start_matches("r#");
+        let doc = doc_comment(&param.attrs);
+        let ty = &param.ty;
+        names.push(ident.clone());
+        fields.push(quote!(#[serde(rename = #name)] #ident: #ty));
+        properties.push(quote!(.field::<#ty>(#name, #doc)));
+        strip_docs(&mut param.attrs);
+    }
+
+    let returns_result = match &function.sig.output {
+        ReturnType::Type(_, ty) => match &**ty {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Result"),
+            _ => false,
+        },
+        ReturnType::Default => false,
+    };
+    let output = if returns_result {
+        quote! {
+            #fn_name(#(#names),*)
+                .await
+                .map_err(|e| #core::ToolError::new(e.to_string()))?
+        }
+    } else {
+        quote!(#fn_name(#(#names),*).await)
+    };
+
+    let vis = &function.vis;
+    let struct_name = format_ident!("{}Tool", pascal_case(&fn_name.to_string()));
+    let struct_doc = format!("The `{tool_name}` tool, calling [`{fn_name}`].");
+    let serde_crate = format!("{}::__private::serde", core).replace(' ', "");
+
+    Ok(quote! {
+        #function
+
+        #[doc = #struct_doc]
+        #[derive(Debug, Clone, Copy, Default)]
+        #vis struct #struct_name;
+
+        impl #core::TypedTool for #struct_name {
+            fn definition(&self) -> #core::Tool {
+                #core::Tool::function(
+                    #tool_name,
+                    #description,
+                    #core::ObjectSchema::new()
+                        #(#properties)*
+                        .build(),
+                )
+            }
+        }
+
+        #[#core::async_trait]
+        impl #core::ToolHandler for #struct_name {
+            async fn call(
+                &self,
+                arguments: #core::__private::serde_json::Value,
+            ) -> ::std::result::Result<#core::__private::serde_json::Value, #core::ToolError> {
+                #[derive(#core::__private::serde::Deserialize)]
+                #[serde(crate = #serde_crate)]
+                struct Arguments {
+                    #(#fields),*
+                }
+
+                let Arguments { #(#names),* } =
+                    #core::__private::serde_json::from_value(arguments)?;
+                let output = #output;
+                #core::__private::serde_json::to_value(output)
+                    .map_err(|e| #core::ToolError::new(format!("Invalid tool output: {e}")))
+            }
+        }
+    })
+}
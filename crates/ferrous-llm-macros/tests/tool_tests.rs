@@ -0,0 +1,235 @@
+//! Tests for the tool definition macros.
+
+use ferrous_llm_core::*;
+use ferrous_llm_macros::{ToolArgs, ToolSchema, tool};
+use serde_json::json;
+
+/// Get the current weather for a city.
+///
+/// Temperatures are rounded to whole degrees.
+#[tool]
+async fn get_weather(
+    /// Name of the city
+    city: String,
+    /// Temperature unit
+    unit: Option<Unit>,
+) -> Forecast {
+    Forecast {
+        city,
+        temperature: 22,
+        unit: unit.unwrap_or(Unit::Celsius),
+    }
+}
+
+#[tool(name = "divide", description = "Divide two numbers")]
+async fn checked_divide(a: f64, b: f64) -> Result<f64, String> {
+    if b == 0.0 {
+        Err("division by zero".to_string())
+    } else {
+        Ok(a / b)
+    }
+}
+
+/// Unit of temperature.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize, ToolSchema)]
+#[serde(rename_all = "lowercase")]
+enum Unit {
+    Celsius,
+    Fahrenheit,
+}
+
+#[derive(serde::Serialize)]
+struct Forecast {
+    city: String,
+    temperature: i32,
+    unit: Unit,
+}
+
+/// Search the knowledge base.
+#[derive(Debug, serde::Deserialize, ToolArgs)]
+#[serde(rename_all = "camelCase")]
+struct SearchDocuments {
+    /// Text to search for
+    query: String,
+    /// Maximum number of results
+    #[serde(default)]
+    max_results: u32,
+    #[serde(rename = "tags")]
+    labels: Vec<String>,
+    #[serde(skip)]
+    #[allow(dead_code)]
+    internal: bool,
+}
+
+/// List open tickets.
+#[derive(Debug, Default, serde::Deserialize, ToolArgs)]
+#[serde(default, deny_unknown_fields)]
+struct ListTickets {
+    /// Maximum number of tickets
+    limit: u32,
+    assignee: Option<String>,
+}
+
+fn call(name: &str, arguments: serde_json::Value) -> ToolCall {
+    ToolCall {
+        id: "call_1".to_string(),
+        call_type: "function".to_string(),
+        function: FunctionCall {
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+        },
+    }
+}
+
+#[test]
+fn test_tool_definition_from_function() {
+    let tool = GetWeatherTool.definition();
+
+    assert_eq!(tool.tool_type, "function");
+    assert_eq!(tool.function.name, "get_weather");
+    assert_eq!(
+        tool.function.description,
+        "Get the current weather for a city.\n\nTemperatures are rounded to whole degrees."
+    );
+    assert_eq!(
+        tool.function.parameters,
+        json!({
+            "type": "object",
+            "properties": {
+                "city": {"type": "string", "description": "Name of the city"},
+                "unit": {
+                    "type": "string",
+                    "enum": ["celsius", "fahrenheit"],
+                    "description": "Temperature unit"
+                }
+            },
+            "required": ["city"]
+        })
+    );
+
+    let tool = CheckedDivideTool.definition();
+    assert_eq!(tool.function.name, "divide");
+    assert_eq!(tool.function.description, "Divide two numbers");
+    assert_eq!(tool.function.parameters["required"], json!(["a", "b"]));
+}
+
+#[test]
+fn test_tool_args_derive() {
+    assert_eq!(SearchDocuments::NAME, "search_documents");
+    assert_eq!(SearchDocuments::DESCRIPTION, "Search the knowledge base.");
+    assert_eq!(
+        SearchDocuments::tool_schema(),
+        json!({
+            "type": "object",
+            "description": "Search the knowledge base.",
+            "properties": {
+                "query": {"type": "string", "description": "Text to search for"},
+                "maxResults": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Maximum number of results"
+                },
+                "tags": {"type": "array", "items": {"type": "string"}}
+            },
+            "required": ["query", "tags"]
+        })
+    );
+}
+
+#[test]
+fn test_container_serde_attributes() {
+    assert_eq!(
+        ListTickets::tool_schema(),
+        json!({
+            "type": "object",
+            "description": "List open tickets.",
+            "properties": {
+                "limit": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Maximum number of tickets"
+                },
+                "assignee": {"type": "string"}
+            },
+            "required": [],
+            "additionalProperties": false
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_registry_dispatch() {
+    let mut registry = ToolRegistry::new();
+    registry
+        .register_tool(GetWeatherTool)
+        .register_tool(CheckedDivideTool)
+        .register_typed(|args: SearchDocuments| async move {
+            Ok::<_, ToolError>(
+                json!({"query": args.query, "limit": args.max_results, "tags": args.labels}),
+            )
+        })
+        .register_typed(|args: ListTickets| async move {
+            Ok::<_, ToolError>(json!({"limit": args.limit, "assignee": args.assignee}))
+        });
+
+    let names: Vec<_> = registry
+        .tools()
+        .into_iter()
+        .map(|tool| tool.function.name)
+        .collect();
+    assert_eq!(
+        names,
+        ["divide", "get_weather", "list_tickets", "search_documents"]
+    );
+
+    let output = registry
+        .call(&call("get_weather", json!({"city": "Paris"})))
+        .await
+        .unwrap();
+    assert_eq!(
+        output,
+        json!({"city": "Paris", "temperature": 22, "unit": "celsius"})
+    );
+
+    let output = registry
+        .call(&call("divide", json!({"a": 1.0, "b": 4.0})))
+        .await
+        .unwrap();
+    assert_eq!(output, json!(0.25));
+
+    let error = registry
+        .call(&call("divide", json!({"a": 1.0, "b": 0.0})))
+        .await
+        .unwrap_err();
+    assert_eq!(error.message, "division by zero");
+
+    let error = registry
+        .call(&call("get_weather", json!({"unit": "kelvin"})))
+        .await
+        .unwrap_err();
+    assert!(error.message.starts_with("Invalid arguments"));
+
+    let output = registry
+        .call(&call(
+            "search_documents",
+            json!({"query": "rust", "tags": ["lang"]}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(
+        output,
+        json!({"query": "rust", "limit": 0, "tags": ["lang"]})
+    );
+
+    let output = registry
+        .call(&call("list_tickets", json!({})))
+        .await
+        .unwrap();
+    assert_eq!(output, json!({"limit": 0, "assignee": null}));
+
+    let error = registry
+        .call(&call("list_tickets", json!({"limit": 5, "status": "open"})))
+        .await
+        .unwrap_err();
+    assert!(error.message.contains("unknown field `status`"));
+}
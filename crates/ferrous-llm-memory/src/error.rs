@@ -0,0 +1,54 @@
+//! Memory error types.
+
+use ferrous_llm_core::{LlmError, ProviderError};
+use thiserror::Error;
+
+/// Errors produced while maintaining conversation memory.
+#[derive(Debug, Error)]
+pub enum MemoryError {
+    /// The provider failed to summarize older messages
+    #[error("Summarization failed: {message}")]
+    Summarization { message: String },
+
+    /// The embedding provider failed
+    #[error("Embedding failed: {message}")]
+    Embedding { message: String },
+
+    /// An embedding doesn't have the dimensions of the index
+    #[error("Embedding has {actual} dimensions, expected {expected}")]
+    DimensionMismatch { expected: usize, actual: usize },
+
+    /// Reading or writing a file failed
+    #[error("I/O error: {source}")]
+    Io {
+        #[from]
+        source: std::io::Error,
+    },
+
+    /// A stored message could not be serialized or parsed
+    #[error("JSON error: {source}")]
+    Json {
+        #[from]
+        source: serde_json::Error,
+    },
+
+    /// The SQLite database returned an error
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite error: {source}")]
+    Sqlite {
+        #[from]
+        source: rusqlite::Error,
+    },
+
+    /// The storage backend failed
+    #[error("Storage error: {message}")]
+    Storage { message: String },
+}
+
+impl<E: ProviderError> From<MemoryError> for LlmError<E> {
+    fn from(error: MemoryError) -> Self {
+        LlmError::Memory {
+            message: error.to_string(),
+        }
+    }
+}
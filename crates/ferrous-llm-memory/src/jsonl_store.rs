@@ -0,0 +1,251 @@
+//! Conversation store backed by a JSON-lines file.
+
+use crate::error::MemoryError;
+use crate::store::{ConversationKey, ConversationStore, StoredMessage};
+use crate::util::write_atomic;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::io::{ErrorKind, SeekFrom};
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::Mutex;
+
+/// One line of the file: a message and the conversation it belongs to.
+#[derive(Serialize, Deserialize)]
+struct Record {
+    #[serde(flatten)]
+    key: ConversationKey,
+    #[serde(flatten)]
+    message: StoredMessage,
+}
+
+/// Conversation store that appends every message to a JSON-lines file.
+///
+/// Appending is cheap, while loading reads the whole file, which suits
+/// single-process applications with modest histories. The file is plain
+/// text and easy to inspect or post-process. A record torn by a crash during
+/// an append is ignored when reading and cut off by the next append.
+#[derive(Debug)]
+pub struct JsonlStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl JsonlStore {
+    /// Use the file at `path`, which is created on the first append.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Get the path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn read_records(&self) -> Result<Vec<Record>, MemoryError> {
+        let contents = match tokio::fs::read_to_string(&self.path).await {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut lines = contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .peekable();
+        let mut records = Vec::new();
+        while let Some(line) = lines.next() {
+            match serde_json::from_str(line) {
+                Ok(record) => records.push(record),
+                // A crash during append can leave the last record torn
+                Err(_) if lines.peek().is_none() && !contents.ends_with('\n') => break,
+                Err(error) => return Err(error.into()),
+            }
+        }
+        Ok(records)
+    }
+
+    /// Cut off a record left torn by a crash, so new records start on their
+    /// own line.
+    async fn truncate_torn_record(file: &mut tokio::fs::File) -> Result<(), MemoryError> {
+        let len = file.metadata().await?.len();
+        if len == 0 {
+            return Ok(());
+        }
+        let mut last = [0u8];
+        file.seek(SeekFrom::Start(len - 1)).await?;
+        file.read_exact(&mut last).await?;
+        if last[0] == b'\n' {
+            return Ok(());
+        }
+
+        let mut contents = Vec::new();
+        file.seek(SeekFrom::Start(0)).await?;
+        file.read_to_end(&mut contents).await?;
+        let end = contents
+            .iter()
+            .rposition(|&byte| byte == b'\n')
+            .map_or(0, |position| position + 1);
+        file.set_len(end as u64).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ConversationStore for JsonlStore {
+    async fn append(
+        &self,
+        key: &ConversationKey,
+        messages: &[StoredMessage],
+    ) -> Result<(), MemoryError> {
+        let mut lines = String::new();
+        for message in messages {
+            let record = Record {
+                key: key.clone(),
+                message: message.clone(),
+            };
+            lines.push_str(&serde_json::to_string(&record)?);
+            lines.push('\n');
+        }
+
+        let _guard = self.lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        Self::truncate_torn_record(&mut file).await?;
+        file.write_all(lines.as_bytes()).await?;
+        file.flush().await?;
+        Ok(())
+    }
+
+    async fn load(&self, key: &ConversationKey) -> Result<Vec<StoredMessage>, MemoryError> {
+        let _guard = self.lock.lock().await;
+        let records = self.read_records().await?;
+        Ok(records
+            .into_iter()
+            .filter(|record| &record.key == key)
+            .map(|record| record.message)
+            .collect())
+    }
+
+    async fn list(&self, user_id: Option<&str>) -> Result<Vec<String>, MemoryError> {
+        let _guard = self.lock.lock().await;
+        let records = self.read_records().await?;
+        let ids: BTreeSet<String> = records
+            .into_iter()
+            .filter(|record| record.key.user_id.as_deref() == user_id)
+            .map(|record| record.key.conversation_id)
+            .collect();
+        Ok(ids.into_iter().collect())
+    }
+
+    /// Delete a conversation by rewriting the file without it.
+    async fn delete(&self, key: &ConversationKey) -> Result<(), MemoryError> {
+        let _guard = self.lock.lock().await;
+        let records = self.read_records().await?;
+        if !records.iter().any(|record| &record.key == key) {
+            return Ok(());
+        }
+
+        let mut lines = String::new();
+        for record in records.iter().filter(|record| &record.key != key) {
+            lines.push_str(&serde_json::to_string(record)?);
+            lines.push('\n');
+        }
+
+        write_atomic(&self.path, lines).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::tests::{check_store, conversation};
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "ferrous-llm-memory-{}-{name}.jsonl",
+            std::process::id()
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_jsonl_store() {
+        let path = temp_path("contract");
+        check_store(&JsonlStore::new(&path)).await;
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_jsonl_store_survives_reopen() {
+        let path = temp_path("reopen");
+        let key = ConversationKey::for_user("trip", "ada");
+        JsonlStore::new(&path)
+            .append(&key, &conversation())
+            .await
+            .unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 4);
+        assert!(contents.starts_with(r#"{"conversation_id":"trip","user_id":"ada","#));
+
+        let reopened = JsonlStore::new(&path);
+        assert_eq!(reopened.load(&key).await.unwrap().len(), 4);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_jsonl_store_missing_file() {
+        let store = JsonlStore::new(temp_path("missing"));
+        assert!(store.list(None).await.unwrap().is_empty());
+        store.delete(&ConversationKey::new("none")).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_jsonl_store_recovers_from_torn_append() {
+        let path = temp_path("torn");
+        let key = ConversationKey::new("trip");
+        let store = JsonlStore::new(&path);
+        store.append(&key, &conversation()).await.unwrap();
+
+        // Simulate a crash halfway through writing a record
+        let record = serde_json::to_string(&Record {
+            key: key.clone(),
+            message: conversation().remove(0),
+        })
+        .unwrap();
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        std::io::Write::write_all(&mut file, &record.as_bytes()[..record.len() / 2]).unwrap();
+
+        assert_eq!(store.load(&key).await.unwrap().len(), 4);
+        assert_eq!(store.list(None).await.unwrap().len(), 1);
+
+        store.append(&key, &conversation()[..1]).await.unwrap();
+        assert_eq!(store.load(&key).await.unwrap().len(), 5);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_jsonl_store_rejects_corrupt_record() {
+        let path = temp_path("corrupt");
+        let key = ConversationKey::new("trip");
+        let store = JsonlStore::new(&path);
+        store.append(&key, &conversation()[..1]).await.unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, format!("not json\n{contents}")).unwrap();
+
+        assert!(store.load(&key).await.is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1 +1,36 @@
+//! Conversation memory for the LLM library.
+//!
+//! This crate provides strategies for deciding which messages of a
+//! conversation are sent with the next request, from keeping the whole
+//! history to windowing it by message count or token budget, or compressing
+//! older turns into a summary written by the model itself. Conversation
+//! stores persist full histories in memory, in a JSON-lines file or, with the
+//! `sqlite` feature, in SQLite. Semantic memory embeds past messages or
+//! documents into a vector store and recalls the most relevant ones for a
+//! new request.
 
+pub mod error;
+pub mod jsonl_store;
+pub mod memory;
+pub mod semantic;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_store;
+pub mod store;
+pub mod summary;
+#[cfg(test)]
+mod test_support;
+pub mod token;
+mod util;
+pub mod vector;
+
+// Re-export main types for convenience
+pub use error::*;
+pub use jsonl_store::*;
+pub use memory::*;
+pub use semantic::*;
+#[cfg(feature = "sqlite")]
+pub use sqlite_store::*;
+pub use store::*;
+pub use summary::*;
+pub use token::*;
+pub use vector::*;
@@ -0,0 +1,257 @@
+//! The [`Memory`] trait and simple buffering strategies.
+//!
+//! Every strategy keeps system messages for the whole conversation and sends
+//! them first, so instructions are never forgotten. Windowed strategies drop
+//! the oldest turns, and never start the window with a tool result whose
+//! tool call has already been dropped.
+
+use crate::error::MemoryError;
+use async_trait::async_trait;
+use ferrous_llm_core::{ChatRequest, ContentPart, Message, MessageContent, Role};
+use std::collections::VecDeque;
+
+/// Conversation memory that decides which messages are sent with each request.
+///
+/// Record every message of the conversation, including the model's replies,
+/// and build the next request from [`Memory::messages`].
+#[async_trait]
+pub trait Memory: Send + Sync {
+    /// Record a message in the conversation.
+    async fn add_message(&mut self, message: Message) -> Result<(), MemoryError>;
+
+    /// Record several messages, in order.
+    async fn add_messages(&mut self, messages: Vec<Message>) -> Result<(), MemoryError> {
+        for message in messages {
+            self.add_message(message).await?;
+        }
+        Ok(())
+    }
+
+    /// Get the messages to send with the next request.
+    fn messages(&self) -> Vec<Message>;
+
+    /// Forget the whole conversation.
+    fn clear(&mut self);
+
+    /// Build a chat request from the remembered messages.
+    fn chat_request(&self) -> ChatRequest {
+        ChatRequest::builder().messages(self.messages()).build()
+    }
+}
+
+/// Memory that keeps the full conversation.
+#[derive(Debug, Clone, Default)]
+pub struct BufferMemory {
+    messages: Vec<Message>,
+}
+
+impl BufferMemory {
+    /// Create an empty buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of remembered messages.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Check whether the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+#[async_trait]
+impl Memory for BufferMemory {
+    async fn add_message(&mut self, message: Message) -> Result<(), MemoryError> {
+        self.messages.push(message);
+        Ok(())
+    }
+
+    fn messages(&self) -> Vec<Message> {
+        self.messages.clone()
+    }
+
+    fn clear(&mut self) {
+        self.messages.clear();
+    }
+}
+
+/// Memory that keeps the most recent messages.
+///
+/// System messages don't count towards the window.
+#[derive(Debug, Clone)]
+pub struct WindowMemory {
+    max_messages: usize,
+    system: Vec<Message>,
+    history: VecDeque<Message>,
+}
+
+impl WindowMemory {
+    /// Create a window of at most `max_messages` non-system messages.
+    pub fn new(max_messages: usize) -> Self {
+        Self {
+            max_messages,
+            system: Vec::new(),
+            history: VecDeque::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Memory for WindowMemory {
+    async fn add_message(&mut self, message: Message) -> Result<(), MemoryError> {
+        if message.role == Role::System {
+            self.system.push(message);
+            return Ok(());
+        }
+
+        self.history.push_back(message);
+        while self.history.len() > self.max_messages {
+            self.history.pop_front();
+        }
+        drop_orphaned_tool_results(&mut self.history);
+        Ok(())
+    }
+
+    fn messages(&self) -> Vec<Message> {
+        self.system.iter().chain(&self.history).cloned().collect()
+    }
+
+    fn clear(&mut self) {
+        self.system.clear();
+        self.history.clear();
+    }
+}
+
+/// Drop tool results at the start of a window whose tool call was dropped.
+///
+/// Providers reject tool results that don't follow the assistant message
+/// that made the call.
+pub(crate) fn drop_orphaned_tool_results(history: &mut VecDeque<Message>) {
+    while history
+        .front()
+        .is_some_and(|message| message.role == Role::Tool)
+    {
+        history.pop_front();
+    }
+}
+
+/// Render a message as plain text.
+///
+/// Images and audio are replaced by placeholders and tool calls are written
+/// out with their arguments, so the text can be used for summaries and token
+/// estimates.
+pub fn message_text(message: &Message) -> String {
+    match &message.content {
+        MessageContent::Text(text) => text.clone(),
+        MessageContent::Multimodal(parts) => parts
+            .iter()
+            .map(|part| match part {
+                ContentPart::Text { text } => text.as_str(),
+                ContentPart::Image { .. } => "[image]",
+                ContentPart::Audio { .. } => "[audio]",
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        MessageContent::Tool(content) => {
+            let mut lines: Vec<String> = content.text.iter().cloned().collect();
+            for tool_call in content.tool_calls.iter().flatten() {
+                lines.push(format!(
+                    "[called {}({})]",
+                    tool_call.function.name, tool_call.function.arguments
+                ));
+            }
+            lines.join("\n")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferrous_llm_core::{FunctionCall, ToolCall};
+
+    fn tool_call(id: &str) -> ToolCall {
+        ToolCall {
+            id: id.to_string(),
+            call_type: "function".to_string(),
+            function: FunctionCall {
+                name: "get_weather".to_string(),
+                arguments: r#"{"city":"Paris"}"#.to_string(),
+            },
+        }
+    }
+
+    fn texts(messages: &[Message]) -> Vec<String> {
+        messages.iter().map(message_text).collect()
+    }
+
+    #[tokio::test]
+    async fn test_buffer_memory() {
+        let mut memory = BufferMemory::new();
+        memory
+            .add_messages(vec![Message::user("Hi"), Message::assistant("Hello!")])
+            .await
+            .unwrap();
+
+        assert_eq!(memory.len(), 2);
+        assert_eq!(texts(&memory.chat_request().messages), ["Hi", "Hello!"]);
+
+        memory.clear();
+        assert!(memory.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_window_memory_keeps_system_messages() {
+        let mut memory = WindowMemory::new(2);
+        memory
+            .add_messages(vec![
+                Message::system("Be brief."),
+                Message::user("One"),
+                Message::assistant("Two"),
+                Message::user("Three"),
+            ])
+            .await
+            .unwrap();
+
+        let messages = memory.messages();
+        assert_eq!(texts(&messages), ["Be brief.", "Two", "Three"]);
+        assert_eq!(messages[0].role, Role::System);
+    }
+
+    #[tokio::test]
+    async fn test_window_memory_drops_orphaned_tool_results() {
+        let mut memory = WindowMemory::new(3);
+        memory
+            .add_messages(vec![
+                Message::user("Weather?"),
+                Message::assistant_with_tools("", vec![tool_call("call_1")]),
+                Message::tool_response("Sunny", "call_1"),
+                Message::tool_response("Warm", "call_1"),
+                Message::assistant("Sunny and warm."),
+            ])
+            .await
+            .unwrap();
+
+        // The window would start at the first tool result, whose call is gone
+        let messages = memory.messages();
+        assert_eq!(texts(&messages), ["Sunny and warm."]);
+    }
+
+    #[test]
+    fn test_message_text() {
+        let message = Message::user_multimodal(vec![
+            ContentPart::text("What is this?"),
+            ContentPart::image_url("https://example.com/cat.png"),
+        ]);
+        assert_eq!(message_text(&message), "What is this?\n[image]");
+
+        let message = Message::assistant_with_tools("Checking", vec![tool_call("call_1")]);
+        assert_eq!(
+            message_text(&message),
+            "Checking\n[called get_weather({\"city\":\"Paris\"})]"
+        );
+    }
+}
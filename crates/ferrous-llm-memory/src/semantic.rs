@@ -0,0 +1,342 @@
+//! Retrieval-augmented memory built on an embedding provider.
+//!
+//! [`SemanticMemory`] embeds messages or documents with any
+//! [`EmbeddingProvider`], keeps them in a [`VectorStore`] and recalls the
+//! snippets most relevant to a new query. [`inject_context`] adds recalled
+//! snippets to a [`ChatRequest`] so the model can draw on them.
+
+use crate::error::MemoryError;
+use crate::memory::message_text;
+use crate::vector::{ScoredRecord, VectorRecord, VectorStore};
+use ferrous_llm_core::{ChatRequest, EmbeddingProvider, Message, Role};
+use serde_json::json;
+use std::collections::HashMap;
+
+/// Where recalled context is placed in a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContextPlacement {
+    /// A system message after the request's own system messages
+    #[default]
+    System,
+    /// A user message just before the latest user message
+    User,
+}
+
+/// Memory that recalls stored snippets by semantic similarity.
+#[derive(Debug, Clone)]
+pub struct SemanticMemory<P, S> {
+    provider: P,
+    store: S,
+    top_k: usize,
+    min_score: Option<f32>,
+}
+
+impl<P: EmbeddingProvider, S: VectorStore> SemanticMemory<P, S> {
+    /// Create semantic memory that embeds with `provider` into `store`.
+    pub fn new(provider: P, store: S) -> Self {
+        Self {
+            provider,
+            store,
+            top_k: 4,
+            min_score: None,
+        }
+    }
+
+    /// Set the number of snippets to recall.
+    pub fn top_k(mut self, top_k: usize) -> Self {
+        self.top_k = top_k;
+        self
+    }
+
+    /// Ignore snippets scoring below `min_score`.
+    pub fn min_score(mut self, min_score: f32) -> Self {
+        self.min_score = Some(min_score);
+        self
+    }
+
+    /// Get the underlying vector store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Embed and store a text snippet, such as a document chunk.
+    pub async fn add_text(
+        &self,
+        id: impl Into<String>,
+        text: impl Into<String>,
+    ) -> Result<(), MemoryError> {
+        self.add_texts(vec![(id.into(), text.into())]).await
+    }
+
+    /// Embed and store several `(id, text)` snippets in one request.
+    pub async fn add_texts(&self, texts: Vec<(String, String)>) -> Result<(), MemoryError> {
+        let records = texts
+            .into_iter()
+            .map(|(id, text)| VectorRecord {
+                id,
+                text,
+                embedding: Vec::new(),
+                metadata: HashMap::new(),
+            })
+            .collect();
+        self.add_records(records).await
+    }
+
+    /// Embed and store a message, recording its role in the metadata.
+    pub async fn add_message(
+        &self,
+        id: impl Into<String>,
+        message: &Message,
+    ) -> Result<(), MemoryError> {
+        let record = VectorRecord {
+            id: id.into(),
+            text: message_text(message),
+            embedding: Vec::new(),
+            metadata: HashMap::from([("role".to_string(), json!(message.role.to_string()))]),
+        };
+        self.add_records(vec![record]).await
+    }
+
+    /// Embed the text of records and store them, keeping their metadata.
+    pub async fn add_records(&self, mut records: Vec<VectorRecord>) -> Result<(), MemoryError> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        let texts: Vec<String> = records.iter().map(|record| record.text.clone()).collect();
+        let embeddings = self.embed(&texts).await?;
+        for (record, embedding) in records.iter_mut().zip(embeddings) {
+            record.embedding = embedding;
+        }
+        self.store.upsert(records).await
+    }
+
+    /// Recall the snippets most relevant to `query`, best first.
+    pub async fn recall(&self, query: &str) -> Result<Vec<ScoredRecord>, MemoryError> {
+        let embedding = self
+            .embed(&[query.to_string()])
+            .await?
+            .pop()
+            .unwrap_or_default();
+        let mut results = self.store.search(&embedding, self.top_k).await?;
+        if let Some(min_score) = self.min_score {
+            results.retain(|result| result.score >= min_score);
+        }
+        Ok(results)
+    }
+
+    /// Recall snippets relevant to the latest user message and add them to
+    /// the request.
+    ///
+    /// Returns the recalled snippets; the request is unchanged if there are none.
+    pub async fn augment(
+        &self,
+        request: &mut ChatRequest,
+        placement: ContextPlacement,
+    ) -> Result<Vec<ScoredRecord>, MemoryError> {
+        let Some(query) = request
+            .messages
+            .iter()
+            .rev()
+            .find(|message| message.role == Role::User)
+            .map(message_text)
+        else {
+            return Ok(Vec::new());
+        };
+
+        let results = self.recall(&query).await?;
+        inject_context(request, &results, placement);
+        Ok(results)
+    }
+
+    /// Embed texts, in order.
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, MemoryError> {
+        let mut embeddings =
+            self.provider
+                .embed(texts)
+                .await
+                .map_err(|e| MemoryError::Embedding {
+                    message: e.to_string(),
+                })?;
+        if embeddings.len() != texts.len() {
+            return Err(MemoryError::Embedding {
+                message: format!(
+                    "expected {} embeddings, got {}",
+                    texts.len(),
+                    embeddings.len()
+                ),
+            });
+        }
+        embeddings.sort_by_key(|embedding| embedding.index);
+        Ok(embeddings
+            .into_iter()
+            .map(|embedding| embedding.embedding)
+            .collect())
+    }
+}
+
+/// Add recalled snippets to a request as a single context message.
+///
+/// Does nothing if there are no snippets.
+pub fn inject_context(
+    request: &mut ChatRequest,
+    snippets: &[ScoredRecord],
+    placement: ContextPlacement,
+) {
+    if snippets.is_empty() {
+        return;
+    }
+
+    let mut context = String::from("Use the following context if it is relevant:\n");
+    for (number, snippet) in snippets.iter().enumerate() {
+        context.push_str(&format!("\n[{}] {}", number + 1, snippet.record.text));
+    }
+
+    let messages = &mut request.messages;
+    match placement {
+        ContextPlacement::System => {
+            let position = messages
+                .iter()
+                .position(|message| message.role != Role::System)
+                .unwrap_or(messages.len());
+            messages.insert(position, Message::system(context));
+        }
+        ContextPlacement::User => {
+            let position = messages
+                .iter()
+                .rposition(|message| message.role == Role::User)
+                .unwrap_or(messages.len());
+            messages.insert(position, Message::user(context));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{MockConfig, MockError};
+    use crate::vector::{InMemoryVectorStore, Similarity};
+    use async_trait::async_trait;
+    use ferrous_llm_core::Embedding;
+
+    /// Embeds text as counts of a few keywords, returned in reverse order.
+    struct KeywordEmbedder;
+
+    const KEYWORDS: [&str; 3] = ["rust", "pasta", "weather"];
+
+    #[async_trait]
+    impl EmbeddingProvider for KeywordEmbedder {
+        type Config = MockConfig;
+        type Error = MockError;
+
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Embedding>, MockError> {
+            Ok(texts
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(index, text)| Embedding {
+                    embedding: KEYWORDS
+                        .iter()
+                        .map(|keyword| text.to_lowercase().matches(keyword).count() as f32)
+                        .collect(),
+                    index,
+                })
+                .collect())
+        }
+    }
+
+    async fn memory() -> SemanticMemory<KeywordEmbedder, InMemoryVectorStore> {
+        let memory = SemanticMemory::new(
+            KeywordEmbedder,
+            InMemoryVectorStore::new(Similarity::Cosine),
+        )
+        .top_k(2)
+        .min_score(0.1);
+        memory
+            .add_texts(vec![
+                (
+                    "rust".to_string(),
+                    "Rust has no garbage collector.".to_string(),
+                ),
+                (
+                    "pasta".to_string(),
+                    "Cook pasta in salted water.".to_string(),
+                ),
+            ])
+            .await
+            .unwrap();
+        memory
+            .add_message(
+                "weather",
+                &Message::assistant("The weather in Paris is sunny."),
+            )
+            .await
+            .unwrap();
+        memory
+    }
+
+    #[tokio::test]
+    async fn test_recall() {
+        let memory = memory().await;
+        assert_eq!(memory.store().len().await.unwrap(), 3);
+
+        let results = memory.recall("How do I cook pasta?").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].record.id, "pasta");
+
+        let results = memory.recall("What's the weather?").await.unwrap();
+        assert_eq!(results[0].record.metadata["role"], "assistant");
+
+        assert!(memory.recall("Unrelated").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_augment_as_system_message() {
+        let memory = memory().await;
+        let mut request = ChatRequest::builder()
+            .system_message("Be helpful.")
+            .user_message("Hi")
+            .assistant_message("Hello!")
+            .user_message("Does Rust have a garbage collector?")
+            .build();
+
+        let results = memory
+            .augment(&mut request, ContextPlacement::System)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+
+        let messages = &request.messages;
+        assert_eq!(messages.len(), 5);
+        assert_eq!(messages[1].role, Role::System);
+        assert_eq!(
+            message_text(&messages[1]),
+            "Use the following context if it is relevant:\n\n[1] Rust has no garbage collector."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_augment_as_user_message() {
+        let memory = memory().await;
+        let mut request = ChatRequest::builder()
+            .user_message("Any pasta tips?")
+            .build();
+
+        memory
+            .augment(&mut request, ContextPlacement::User)
+            .await
+            .unwrap();
+
+        let messages = &request.messages;
+        assert_eq!(messages.len(), 2);
+        assert!(message_text(&messages[0]).contains("salted water"));
+        assert_eq!(message_text(&messages[1]), "Any pasta tips?");
+
+        // Nothing relevant leaves the request untouched
+        let mut request = ChatRequest::builder().user_message("Unrelated").build();
+        memory
+            .augment(&mut request, ContextPlacement::System)
+            .await
+            .unwrap();
+        assert_eq!(request.messages.len(), 1);
+    }
+}
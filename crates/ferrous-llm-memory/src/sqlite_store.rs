@@ -0,0 +1,242 @@
+//! Conversation store backed by SQLite.
+
+use crate::error::MemoryError;
+use crate::store::{ConversationKey, ConversationStore, StoredMessage};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use ferrous_llm_core::Usage;
+use rusqlite::{Connection, params};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS conversation_messages (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    conversation_id TEXT NOT NULL,
+    user_id TEXT,
+    role TEXT NOT NULL,
+    message TEXT NOT NULL,
+    prompt_tokens INTEGER,
+    completion_tokens INTEGER,
+    total_tokens INTEGER,
+    created_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS conversation_messages_key
+    ON conversation_messages (conversation_id, user_id, id);
+";
+
+/// Conversation store backed by a SQLite database.
+///
+/// Each message is a row holding the message as JSON alongside its role and
+/// token usage, so usage can also be queried with SQL. Queries run on the
+/// blocking thread pool.
+#[derive(Debug, Clone)]
+pub struct SqliteStore {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteStore {
+    /// Open or create the database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, MemoryError> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// Open a private in-memory database.
+    pub fn open_in_memory() -> Result<Self, MemoryError> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    /// Use an existing connection, creating the schema if needed.
+    pub fn from_connection(connection: Connection) -> Result<Self, MemoryError> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    /// Run a query on the blocking thread pool.
+    async fn with_connection<T, F>(&self, query: F) -> Result<T, MemoryError>
+    where
+        F: FnOnce(&mut Connection) -> Result<T, MemoryError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let connection = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = connection.lock().map_err(|_| MemoryError::Storage {
+                message: "SQLite connection poisoned".to_string(),
+            })?;
+            query(&mut connection)
+        })
+        .await
+        .map_err(|e| MemoryError::Storage {
+            message: e.to_string(),
+        })?
+    }
+}
+
+#[async_trait]
+impl ConversationStore for SqliteStore {
+    async fn append(
+        &self,
+        key: &ConversationKey,
+        messages: &[StoredMessage],
+    ) -> Result<(), MemoryError> {
+        let key = key.clone();
+        let messages = messages.to_vec();
+        self.with_connection(move |connection| {
+            let transaction = connection.transaction()?;
+            {
+                let mut insert = transaction.prepare(
+                    "INSERT INTO conversation_messages (conversation_id, user_id, role, message, \
+                     prompt_tokens, completion_tokens, total_tokens, created_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                )?;
+                for stored in &messages {
+                    let usage = stored.usage.as_ref();
+                    insert.execute(params![
+                        key.conversation_id,
+                        key.user_id,
+                        stored.message.role.to_string(),
+                        serde_json::to_string(&stored.message)?,
+                        usage.map(|usage| usage.prompt_tokens),
+                        usage.map(|usage| usage.completion_tokens),
+                        usage.map(|usage| usage.total_tokens),
+                        stored.created_at.to_rfc3339(),
+                    ])?;
+                }
+            }
+            transaction.commit()?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn load(&self, key: &ConversationKey) -> Result<Vec<StoredMessage>, MemoryError> {
+        let key = key.clone();
+        self.with_connection(move |connection| {
+            let mut select = connection.prepare(
+                "SELECT message, prompt_tokens, completion_tokens, total_tokens, created_at \
+                 FROM conversation_messages \
+                 WHERE conversation_id = ?1 AND user_id IS ?2 ORDER BY id",
+            )?;
+            let rows = select.query_map(params![key.conversation_id, key.user_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<u32>>(1)?,
+                    row.get::<_, Option<u32>>(2)?,
+                    row.get::<_, Option<u32>>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            })?;
+
+            let mut messages = Vec::new();
+            for row in rows {
+                let (message, prompt_tokens, completion_tokens, total_tokens, created_at) = row?;
+                let usage = match (prompt_tokens, completion_tokens, total_tokens) {
+                    (Some(prompt_tokens), Some(completion_tokens), Some(total_tokens)) => {
+                        Some(Usage {
+                            prompt_tokens,
+                            completion_tokens,
+                            total_tokens,
+                        })
+                    }
+                    _ => None,
+                };
+                let created_at = DateTime::parse_from_rfc3339(&created_at)
+                    .map(|time| time.with_timezone(&Utc))
+                    .map_err(|e| MemoryError::Storage {
+                        message: format!("Invalid timestamp {created_at}: {e}"),
+                    })?;
+                messages.push(StoredMessage {
+                    message: serde_json::from_str(&message)?,
+                    usage,
+                    created_at,
+                });
+            }
+            Ok(messages)
+        })
+        .await
+    }
+
+    async fn list(&self, user_id: Option<&str>) -> Result<Vec<String>, MemoryError> {
+        let user_id = user_id.map(str::to_string);
+        self.with_connection(move |connection| {
+            let mut select = connection.prepare(
+                "SELECT DISTINCT conversation_id FROM conversation_messages \
+                 WHERE user_id IS ?1 ORDER BY conversation_id",
+            )?;
+            let ids = select
+                .query_map(params![user_id], |row| row.get(0))?
+                .collect::<Result<Vec<String>, _>>()?;
+            Ok(ids)
+        })
+        .await
+    }
+
+    async fn delete(&self, key: &ConversationKey) -> Result<(), MemoryError> {
+        let key = key.clone();
+        self.with_connection(move |connection| {
+            connection.execute(
+                "DELETE FROM conversation_messages WHERE conversation_id = ?1 AND user_id IS ?2",
+                params![key.conversation_id, key.user_id],
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Total the usage with a single query.
+    async fn usage(&self, key: &ConversationKey) -> Result<Usage, MemoryError> {
+        let key = key.clone();
+        self.with_connection(move |connection| {
+            // Aggregates always return a row, so an unknown conversation sums to zero
+            let usage = connection.query_row(
+                "SELECT COALESCE(SUM(prompt_tokens), 0), COALESCE(SUM(completion_tokens), 0), \
+                 COALESCE(SUM(total_tokens), 0) FROM conversation_messages \
+                 WHERE conversation_id = ?1 AND user_id IS ?2",
+                params![key.conversation_id, key.user_id],
+                |row| {
+                    Ok(Usage {
+                        prompt_tokens: row.get(0)?,
+                        completion_tokens: row.get(1)?,
+                        total_tokens: row.get(2)?,
+                    })
+                },
+            )?;
+            Ok(usage)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::tests::{check_store, conversation};
+
+    #[tokio::test]
+    async fn test_sqlite_store() {
+        check_store(&SqliteStore::open_in_memory().unwrap()).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_store_survives_reopen() {
+        let path =
+            std::env::temp_dir().join(format!("ferrous-llm-memory-{}.sqlite", std::process::id()));
+        let key = ConversationKey::for_user("trip", "ada");
+        let conversation = conversation();
+        SqliteStore::open(&path)
+            .unwrap()
+            .append(&key, &conversation)
+            .await
+            .unwrap();
+
+        let reopened = SqliteStore::open(&path).unwrap();
+        let loaded = reopened.load(&key).await.unwrap();
+        assert_eq!(loaded.len(), 4);
+        assert_eq!(loaded[3].created_at, conversation[3].created_at);
+        assert_eq!(reopened.usage(&key).await.unwrap().total_tokens, 258);
+        drop(reopened);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
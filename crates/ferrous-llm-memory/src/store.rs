@@ -0,0 +1,272 @@
+//! Persistent conversation storage.
+//!
+//! A [`ConversationStore`] keeps the full history of conversations so they
+//! survive restarts. Conversations are keyed by id and, optionally, by the
+//! user they belong to, so the same id can be reused by different users.
+//! Load a stored conversation into a [`Memory`](crate::Memory) strategy to
+//! decide which of its messages are sent with the next request.
+
+use crate::error::MemoryError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use ferrous_llm_core::{Message, Metadata, Usage};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Identifies a stored conversation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ConversationKey {
+    /// Conversation identifier
+    pub conversation_id: String,
+    /// User the conversation belongs to, if any
+    pub user_id: Option<String>,
+}
+
+impl ConversationKey {
+    /// Create a key for a conversation without a user.
+    pub fn new(conversation_id: impl Into<String>) -> Self {
+        Self {
+            conversation_id: conversation_id.into(),
+            user_id: None,
+        }
+    }
+
+    /// Create a key for a user's conversation.
+    pub fn for_user(conversation_id: impl Into<String>, user_id: impl Into<String>) -> Self {
+        Self {
+            conversation_id: conversation_id.into(),
+            user_id: Some(user_id.into()),
+        }
+    }
+
+    /// Create a key for a conversation, taking the user from request metadata.
+    pub fn from_metadata(conversation_id: impl Into<String>, metadata: &Metadata) -> Self {
+        Self {
+            conversation_id: conversation_id.into(),
+            user_id: metadata.user_id.clone(),
+        }
+    }
+}
+
+/// A message as stored in a conversation, with the usage of the turn that
+/// produced it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredMessage {
+    /// The message, including any tool calls and multimodal parts
+    pub message: Message,
+    /// Token usage of the request that produced the message
+    pub usage: Option<Usage>,
+    /// When the message was stored
+    pub created_at: DateTime<Utc>,
+}
+
+impl StoredMessage {
+    /// Wrap a message for storage.
+    pub fn new(message: Message) -> Self {
+        Self {
+            message,
+            usage: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Attach the usage of the request that produced the message.
+    pub fn with_usage(mut self, usage: Option<Usage>) -> Self {
+        self.usage = usage;
+        self
+    }
+}
+
+impl From<Message> for StoredMessage {
+    fn from(message: Message) -> Self {
+        Self::new(message)
+    }
+}
+
+/// Storage for conversation histories.
+#[async_trait]
+pub trait ConversationStore: Send + Sync {
+    /// Append messages to a conversation, creating it if needed.
+    async fn append(
+        &self,
+        key: &ConversationKey,
+        messages: &[StoredMessage],
+    ) -> Result<(), MemoryError>;
+
+    /// Load a conversation in order. Unknown conversations are empty.
+    async fn load(&self, key: &ConversationKey) -> Result<Vec<StoredMessage>, MemoryError>;
+
+    /// List the ids of a user's conversations, or of those without a user.
+    async fn list(&self, user_id: Option<&str>) -> Result<Vec<String>, MemoryError>;
+
+    /// Delete a conversation.
+    async fn delete(&self, key: &ConversationKey) -> Result<(), MemoryError>;
+
+    /// Load just the messages of a conversation.
+    async fn messages(&self, key: &ConversationKey) -> Result<Vec<Message>, MemoryError> {
+        let stored = self.load(key).await?;
+        Ok(stored.into_iter().map(|stored| stored.message).collect())
+    }
+
+    /// Total the usage recorded for a conversation.
+    async fn usage(&self, key: &ConversationKey) -> Result<Usage, MemoryError> {
+        let stored = self.load(key).await?;
+        Ok(total_usage(stored.iter().filter_map(|s| s.usage.as_ref())))
+    }
+}
+
+/// Sum token usage.
+fn total_usage<'a>(usages: impl Iterator<Item = &'a Usage>) -> Usage {
+    usages.fold(
+        Usage {
+            prompt_tokens: 0,
+            completion_tokens: 0,
+            total_tokens: 0,
+        },
+        |total, usage| Usage {
+            prompt_tokens: total.prompt_tokens + usage.prompt_tokens,
+            completion_tokens: total.completion_tokens + usage.completion_tokens,
+            total_tokens: total.total_tokens + usage.total_tokens,
+        },
+    )
+}
+
+/// Conversation store that keeps conversations in process memory.
+///
+/// Useful for tests and short-lived processes; nothing survives a restart.
+#[derive(Debug, Default)]
+pub struct InMemoryStore {
+    conversations: Mutex<HashMap<ConversationKey, Vec<StoredMessage>>>,
+}
+
+impl InMemoryStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ConversationStore for InMemoryStore {
+    async fn append(
+        &self,
+        key: &ConversationKey,
+        messages: &[StoredMessage],
+    ) -> Result<(), MemoryError> {
+        let mut conversations = self.conversations.lock().unwrap();
+        conversations
+            .entry(key.clone())
+            .or_default()
+            .extend_from_slice(messages);
+        Ok(())
+    }
+
+    async fn load(&self, key: &ConversationKey) -> Result<Vec<StoredMessage>, MemoryError> {
+        let conversations = self.conversations.lock().unwrap();
+        Ok(conversations.get(key).cloned().unwrap_or_default())
+    }
+
+    async fn list(&self, user_id: Option<&str>) -> Result<Vec<String>, MemoryError> {
+        let conversations = self.conversations.lock().unwrap();
+        let mut ids: Vec<String> = conversations
+            .keys()
+            .filter(|key| key.user_id.as_deref() == user_id)
+            .map(|key| key.conversation_id.clone())
+            .collect();
+        ids.sort();
+        Ok(ids)
+    }
+
+    async fn delete(&self, key: &ConversationKey) -> Result<(), MemoryError> {
+        self.conversations.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use ferrous_llm_core::{ContentPart, FunctionCall, MessageContent, Role, ToolCall};
+
+    fn usage(prompt_tokens: u32, completion_tokens: u32) -> Usage {
+        Usage {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+        }
+    }
+
+    /// A conversation with tool calls, multimodal parts and per-turn usage.
+    pub(crate) fn conversation() -> Vec<StoredMessage> {
+        let tool_call = ToolCall {
+            id: "call_1".to_string(),
+            call_type: "function".to_string(),
+            function: FunctionCall {
+                name: "get_weather".to_string(),
+                arguments: r#"{"city":"Paris"}"#.to_string(),
+            },
+        };
+        vec![
+            Message::user_multimodal(vec![
+                ContentPart::text("Weather where this was taken?"),
+                ContentPart::image_url("https://example.com/paris.jpg"),
+            ])
+            .into(),
+            StoredMessage::new(Message::assistant_with_tools("", vec![tool_call]))
+                .with_usage(Some(usage(100, 20))),
+            Message::tool_response("Sunny", "call_1").into(),
+            StoredMessage::new(Message::assistant("It's sunny in Paris."))
+                .with_usage(Some(usage(130, 8))),
+        ]
+    }
+
+    /// Exercise a store through the [`ConversationStore`] contract.
+    pub(crate) async fn check_store(store: &dyn ConversationStore) {
+        let ada = ConversationKey::for_user("trip", "ada");
+        let bob = ConversationKey::for_user("trip", "bob");
+        let anonymous = ConversationKey::new("scratch");
+
+        store.append(&ada, &conversation()[..2]).await.unwrap();
+        store.append(&ada, &conversation()[2..]).await.unwrap();
+        store
+            .append(&bob, &[Message::user("Hi").into()])
+            .await
+            .unwrap();
+        store
+            .append(&anonymous, &[Message::user("Scratch").into()])
+            .await
+            .unwrap();
+
+        let loaded = store.load(&ada).await.unwrap();
+        assert_eq!(loaded.len(), 4);
+        assert!(matches!(
+            &loaded[0].message.content,
+            MessageContent::Multimodal(parts) if matches!(parts[1], ContentPart::Image { .. })
+        ));
+        let MessageContent::Tool(content) = &loaded[1].message.content else {
+            panic!("expected tool calls");
+        };
+        assert_eq!(content.tool_calls.as_ref().unwrap()[0].id, "call_1");
+        assert_eq!(loaded[2].message.role, Role::Tool);
+        assert_eq!(loaded[3].usage.as_ref().unwrap().completion_tokens, 8);
+
+        let usage = store.usage(&ada).await.unwrap();
+        assert_eq!(usage.prompt_tokens, 230);
+        assert_eq!(usage.total_tokens, 258);
+
+        assert_eq!(store.messages(&bob).await.unwrap().len(), 1);
+        assert_eq!(store.list(Some("ada")).await.unwrap(), ["trip"]);
+        assert_eq!(store.list(None).await.unwrap(), ["scratch"]);
+
+        store.delete(&ada).await.unwrap();
+        assert!(store.load(&ada).await.unwrap().is_empty());
+        assert!(store.list(Some("ada")).await.unwrap().is_empty());
+        assert_eq!(store.messages(&bob).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store() {
+        check_store(&InMemoryStore::new()).await;
+    }
+}
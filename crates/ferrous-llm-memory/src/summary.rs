@@ -0,0 +1,246 @@
+//! Summary memory that compresses older turns with a chat model.
+
+use crate::error::MemoryError;
+use crate::memory::{Memory, message_text};
+use async_trait::async_trait;
+use ferrous_llm_core::{ChatProvider, ChatRequest, ChatResponse, Message, Role};
+use std::collections::VecDeque;
+
+/// Default instructions for summarizing older messages.
+pub const DEFAULT_SUMMARY_PROMPT: &str = "Summarize the conversation you are given so it can \
+replace the original messages. Extend the previous summary if there is one. Keep names, facts, \
+decisions and open questions. Reply with the summary only.";
+
+/// Memory that asks a model to summarize older messages.
+///
+/// Once the conversation grows past `max_messages`, everything but the
+/// `keep_recent` newest messages is folded into a running summary, which is
+/// sent as a system message ahead of the recent messages.
+#[derive(Debug, Clone)]
+pub struct SummaryMemory<P> {
+    provider: P,
+    max_messages: usize,
+    keep_recent: usize,
+    prompt: String,
+    summary: Option<String>,
+    system: Vec<Message>,
+    history: VecDeque<Message>,
+}
+
+impl<P: ChatProvider> SummaryMemory<P> {
+    /// Create summary memory that uses `provider` to write summaries.
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            max_messages: 20,
+            keep_recent: 6,
+            prompt: DEFAULT_SUMMARY_PROMPT.to_string(),
+            summary: None,
+            system: Vec::new(),
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Set the number of non-system messages that triggers a summary.
+    pub fn max_messages(mut self, max_messages: usize) -> Self {
+        self.max_messages = max_messages;
+        self
+    }
+
+    /// Set the number of recent messages kept verbatim after summarizing.
+    pub fn keep_recent(mut self, keep_recent: usize) -> Self {
+        self.keep_recent = keep_recent;
+        self
+    }
+
+    /// Set the instructions used to summarize.
+    pub fn prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Get the current summary of older messages.
+    pub fn summary(&self) -> Option<&str> {
+        self.summary.as_deref()
+    }
+
+    /// Summarize all but the most recent messages now.
+    ///
+    /// Messages are only dropped once the summary has been written, so they
+    /// are kept if the provider fails.
+    pub async fn summarize(&mut self) -> Result<(), MemoryError> {
+        let mut split = self.history.len().saturating_sub(self.keep_recent);
+        // Keep tool results together with the call that produced them
+        while split > 0
+            && self
+                .history
+                .get(split)
+                .is_some_and(|m| m.role == Role::Tool)
+        {
+            split -= 1;
+        }
+        if split == 0 {
+            return Ok(());
+        }
+
+        let request = ChatRequest::builder()
+            .system_message(self.prompt.clone())
+            .user_message(self.transcript(split))
+            .build();
+        let response =
+            self.provider
+                .chat(request)
+                .await
+                .map_err(|e| MemoryError::Summarization {
+                    message: e.to_string(),
+                })?;
+
+        self.summary = Some(response.content().trim().to_string());
+        self.history.drain(..split);
+        Ok(())
+    }
+
+    /// Render the previous summary and the first `count` messages for the model.
+    fn transcript(&self, count: usize) -> String {
+        let mut transcript = String::new();
+        if let Some(summary) = &self.summary {
+            transcript.push_str(&format!("Previous summary:\n{summary}\n\n"));
+        }
+        transcript.push_str("Conversation:\n");
+        for message in self.history.iter().take(count) {
+            transcript.push_str(&format!("{}: {}\n", message.role, message_text(message)));
+        }
+        transcript
+    }
+}
+
+#[async_trait]
+impl<P: ChatProvider> Memory for SummaryMemory<P> {
+    async fn add_message(&mut self, message: Message) -> Result<(), MemoryError> {
+        if message.role == Role::System {
+            self.system.push(message);
+            return Ok(());
+        }
+
+        self.history.push_back(message);
+        if self.history.len() > self.max_messages {
+            self.summarize().await?;
+        }
+        Ok(())
+    }
+
+    fn messages(&self) -> Vec<Message> {
+        let summary = self.summary.as_ref().map(|summary| {
+            Message::system(format!("Summary of the earlier conversation:\n{summary}"))
+        });
+        self.system
+            .iter()
+            .cloned()
+            .chain(summary)
+            .chain(self.history.iter().cloned())
+            .collect()
+    }
+
+    fn clear(&mut self) {
+        self.summary = None;
+        self.system.clear();
+        self.history.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{MockConfig, MockError, MockResponse};
+    use std::sync::{Arc, Mutex};
+
+    /// Replies with a numbered summary and records the transcripts it was sent.
+    #[derive(Clone, Default)]
+    struct SummaryProvider {
+        transcripts: Arc<Mutex<Vec<String>>>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl ChatProvider for SummaryProvider {
+        type Config = MockConfig;
+        type Response = MockResponse;
+        type Error = MockError;
+
+        async fn chat(&self, request: ChatRequest) -> Result<MockResponse, MockError> {
+            if self.fail {
+                return Err(MockError);
+            }
+            let mut transcripts = self.transcripts.lock().unwrap();
+            transcripts.push(message_text(&request.messages[1]));
+            Ok(MockResponse(format!(" Summary {} ", transcripts.len())))
+        }
+    }
+
+    fn texts(messages: &[Message]) -> Vec<String> {
+        messages.iter().map(message_text).collect()
+    }
+
+    #[tokio::test]
+    async fn test_summarizes_older_messages() {
+        let provider = SummaryProvider::default();
+        let mut memory = SummaryMemory::new(provider.clone())
+            .max_messages(4)
+            .keep_recent(2);
+        memory
+            .add_messages(vec![
+                Message::system("Be brief."),
+                Message::user("My name is Ada."),
+                Message::assistant("Hi Ada!"),
+                Message::user("I like tea."),
+                Message::assistant("Noted."),
+            ])
+            .await
+            .unwrap();
+        assert!(memory.summary().is_none());
+
+        memory.add_message(Message::user("Bye")).await.unwrap();
+        assert_eq!(memory.summary(), Some("Summary 1"));
+        assert_eq!(
+            texts(&memory.messages()),
+            [
+                "Be brief.",
+                "Summary of the earlier conversation:\nSummary 1",
+                "Noted.",
+                "Bye"
+            ]
+        );
+        assert_eq!(
+            provider.transcripts.lock().unwrap()[0],
+            "Conversation:\nuser: My name is Ada.\nassistant: Hi Ada!\nuser: I like tea.\n"
+        );
+
+        // The next summary extends the previous one
+        memory
+            .add_messages(vec![Message::assistant("Goodbye!"), Message::user("Wait")])
+            .await
+            .unwrap();
+        memory
+            .add_message(Message::assistant("Yes?"))
+            .await
+            .unwrap();
+        assert_eq!(memory.summary(), Some("Summary 2"));
+        assert!(
+            provider.transcripts.lock().unwrap()[1].starts_with("Previous summary:\nSummary 1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_keeps_messages_when_summary_fails() {
+        let provider = SummaryProvider {
+            fail: true,
+            ..Default::default()
+        };
+        let mut memory = SummaryMemory::new(provider).max_messages(1).keep_recent(0);
+        memory.add_message(Message::user("One")).await.unwrap();
+
+        let result = memory.add_message(Message::user("Two")).await;
+        assert!(matches!(result, Err(MemoryError::Summarization { .. })));
+        assert_eq!(texts(&memory.messages()), ["One", "Two"]);
+    }
+}
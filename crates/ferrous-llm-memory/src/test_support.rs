@@ -0,0 +1,69 @@
+//! Mock provider types shared by the unit tests.
+
+use ferrous_llm_core::{
+    ChatResponse, ConfigError, FinishReason, Metadata, ProviderConfig, ProviderError, Usage,
+};
+use std::convert::Infallible;
+use std::time::Duration;
+
+#[derive(Debug, thiserror::Error)]
+#[error("mock error")]
+pub(crate) struct MockError;
+
+impl ProviderError for MockError {
+    fn error_code(&self) -> Option<&str> {
+        None
+    }
+
+    fn is_retryable(&self) -> bool {
+        false
+    }
+
+    fn is_rate_limited(&self) -> bool {
+        false
+    }
+
+    fn is_auth_error(&self) -> bool {
+        false
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Configuration of the mock providers, which are built directly instead.
+#[derive(Debug, Clone)]
+pub(crate) struct MockConfig;
+
+impl ProviderConfig for MockConfig {
+    type Provider = Infallible;
+
+    fn build(self) -> Result<Self::Provider, ConfigError> {
+        Err(ConfigError::validation_failed("not buildable"))
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        Ok(())
+    }
+}
+
+pub(crate) struct MockResponse(pub String);
+
+impl ChatResponse for MockResponse {
+    fn content(&self) -> String {
+        self.0.clone()
+    }
+
+    fn usage(&self) -> Option<Usage> {
+        None
+    }
+
+    fn finish_reason(&self) -> Option<FinishReason> {
+        None
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata::default()
+    }
+}
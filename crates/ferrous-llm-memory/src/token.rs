@@ -0,0 +1,213 @@
+//! Token-budget windowing.
+
+use crate::error::MemoryError;
+use crate::memory::{Memory, message_text};
+use async_trait::async_trait;
+use ferrous_llm_core::{Message, Role};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+
+/// Counts the tokens a message uses in a request.
+///
+/// Implemented for closures, so a model's real tokenizer can be plugged in.
+pub trait TokenCounter: Send + Sync {
+    /// Count the tokens in a message.
+    fn count_tokens(&self, message: &Message) -> usize;
+}
+
+impl<F> TokenCounter for F
+where
+    F: Fn(&Message) -> usize + Send + Sync,
+{
+    fn count_tokens(&self, message: &Message) -> usize {
+        self(message)
+    }
+}
+
+/// Rough token count that needs no tokenizer.
+///
+/// Assumes about four characters per token, plus a few tokens of overhead
+/// for each message's role and formatting.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EstimatedTokenCounter;
+
+impl TokenCounter for EstimatedTokenCounter {
+    fn count_tokens(&self, message: &Message) -> usize {
+        message_text(message).chars().count().div_ceil(4) + 4
+    }
+}
+
+/// Memory that keeps the most recent messages that fit in a token budget.
+///
+/// System messages are always kept and count towards the budget. The newest
+/// message is kept even if it doesn't fit on its own, together with the tool
+/// call it answers if it is a tool result.
+#[derive(Clone)]
+pub struct TokenWindowMemory {
+    max_tokens: usize,
+    counter: Arc<dyn TokenCounter>,
+    system: Vec<Message>,
+    history: VecDeque<Message>,
+    tokens: usize,
+}
+
+impl TokenWindowMemory {
+    /// Create a window of at most `max_tokens`, using [`EstimatedTokenCounter`].
+    pub fn new(max_tokens: usize) -> Self {
+        Self {
+            max_tokens,
+            counter: Arc::new(EstimatedTokenCounter),
+            system: Vec::new(),
+            history: VecDeque::new(),
+            tokens: 0,
+        }
+    }
+
+    /// Set the token counter.
+    ///
+    /// Call before adding messages; remembered messages are not recounted.
+    pub fn counter(mut self, counter: impl TokenCounter + 'static) -> Self {
+        self.counter = Arc::new(counter);
+        self
+    }
+
+    /// Get the number of tokens in the remembered messages.
+    pub fn tokens(&self) -> usize {
+        self.tokens
+    }
+
+    fn pop_front(&mut self) {
+        if let Some(message) = self.history.pop_front() {
+            self.tokens -= self.counter.count_tokens(&message);
+        }
+    }
+}
+
+#[async_trait]
+impl Memory for TokenWindowMemory {
+    async fn add_message(&mut self, message: Message) -> Result<(), MemoryError> {
+        self.tokens += self.counter.count_tokens(&message);
+        if message.role == Role::System {
+            self.system.push(message);
+        } else {
+            self.history.push_back(message);
+        }
+
+        // Never evict the newest message, or the tool call it answers
+        let mut newest = self.history.len().saturating_sub(1);
+        while newest > 0 && self.history[newest].role == Role::Tool {
+            newest -= 1;
+        }
+        while self.tokens > self.max_tokens && newest > 0 {
+            self.pop_front();
+            newest -= 1;
+        }
+        // Don't start the window with tool results whose call was dropped
+        while self.history.front().is_some_and(|m| m.role == Role::Tool) {
+            self.pop_front();
+        }
+        Ok(())
+    }
+
+    fn messages(&self) -> Vec<Message> {
+        self.system.iter().chain(&self.history).cloned().collect()
+    }
+
+    fn clear(&mut self) {
+        self.system.clear();
+        self.history.clear();
+        self.tokens = 0;
+    }
+}
+
+impl fmt::Debug for TokenWindowMemory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenWindowMemory")
+            .field("max_tokens", &self.max_tokens)
+            .field("system", &self.system)
+            .field("history", &self.history)
+            .field("tokens", &self.tokens)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferrous_llm_core::{FunctionCall, ToolCall};
+
+    /// One token per word.
+    fn words(message: &Message) -> usize {
+        message_text(message).split_whitespace().count()
+    }
+
+    #[tokio::test]
+    async fn test_token_window() {
+        let mut memory = TokenWindowMemory::new(6).counter(words);
+        memory
+            .add_messages(vec![
+                Message::system("Be brief"),
+                Message::user("one two"),
+                Message::assistant("three four"),
+                Message::user("five six"),
+            ])
+            .await
+            .unwrap();
+
+        let texts: Vec<String> = memory.messages().iter().map(message_text).collect();
+        assert_eq!(texts, ["Be brief", "three four", "five six"]);
+        assert_eq!(memory.tokens(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_token_window_keeps_newest_message() {
+        let mut memory = TokenWindowMemory::new(2).counter(words);
+        memory
+            .add_messages(vec![
+                Message::user("short"),
+                Message::user("a much longer message"),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(memory.messages().len(), 1);
+        assert_eq!(memory.tokens(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_token_window_keeps_oversized_tool_result() {
+        let tool_call = ToolCall {
+            id: "call_1".to_string(),
+            call_type: "function".to_string(),
+            function: FunctionCall {
+                name: "read_file".to_string(),
+                arguments: "{}".to_string(),
+            },
+        };
+        let mut memory = TokenWindowMemory::new(4).counter(words);
+        memory
+            .add_messages(vec![
+                Message::user("Read the file"),
+                Message::assistant_with_tools("", vec![tool_call]),
+                Message::tool_response("a b c d e f g h", "call_1"),
+            ])
+            .await
+            .unwrap();
+
+        let messages = memory.messages();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].role, Role::Assistant);
+        assert_eq!(messages[1].role, Role::Tool);
+        assert_eq!(memory.tokens(), 10);
+    }
+
+    #[test]
+    fn test_estimated_token_counter() {
+        assert_eq!(EstimatedTokenCounter.count_tokens(&Message::user("")), 4);
+        assert_eq!(
+            EstimatedTokenCounter.count_tokens(&Message::user("Hello, world")),
+            7
+        );
+    }
+}
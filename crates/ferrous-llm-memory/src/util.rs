@@ -0,0 +1,19 @@
+//! File helpers shared by the file-backed stores.
+
+use std::path::Path;
+use tokio::io::AsyncWriteExt;
+
+/// Replace the contents of `path` without leaving it truncated on a crash.
+///
+/// The contents are written to a sibling `.tmp` file and flushed to disk
+/// before it is renamed over `path`, so `path` holds either the old or the
+/// new contents.
+pub(crate) async fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let mut temp_path = path.to_path_buf().into_os_string();
+    temp_path.push(".tmp");
+    let mut file = tokio::fs::File::create(&temp_path).await?;
+    file.write_all(contents.as_ref()).await?;
+    file.sync_all().await?;
+    drop(file);
+    tokio::fs::rename(&temp_path, path).await
+}
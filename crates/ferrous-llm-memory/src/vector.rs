@@ -0,0 +1,336 @@
+//! Vector stores for semantic memory.
+//!
+//! A [`VectorStore`] holds text snippets with their embeddings and finds the
+//! ones most similar to a query embedding. [`InMemoryVectorStore`] is an exact
+//! in-process index that can optionally persist itself to a JSON file.
+
+use crate::error::MemoryError;
+use crate::util::write_atomic;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+
+/// How the similarity between two embeddings is scored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Similarity {
+    /// Cosine similarity, independent of vector length
+    #[default]
+    Cosine,
+    /// Dot product, for embeddings that are already normalized
+    DotProduct,
+}
+
+impl Similarity {
+    /// Score two embeddings of the same length; higher is more similar.
+    pub fn score(&self, a: &[f32], b: &[f32]) -> f32 {
+        let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+        match self {
+            Similarity::DotProduct => dot,
+            Similarity::Cosine => {
+                let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+                let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+                if norm_a == 0.0 || norm_b == 0.0 {
+                    0.0
+                } else {
+                    dot / (norm_a * norm_b)
+                }
+            }
+        }
+    }
+}
+
+/// A text snippet stored with its embedding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VectorRecord {
+    /// Unique identifier; adding a record with the same id replaces it
+    pub id: String,
+    /// The text that was embedded
+    pub text: String,
+    /// The embedding of `text`
+    pub embedding: Vec<f32>,
+    /// Application data, e.g. the source document or message role
+    #[serde(default)]
+    pub metadata: HashMap<String, Value>,
+}
+
+/// A record returned by a search, with its similarity to the query.
+#[derive(Debug, Clone)]
+pub struct ScoredRecord {
+    /// The matching record
+    pub record: VectorRecord,
+    /// Similarity to the query; higher is more similar
+    pub score: f32,
+}
+
+/// Storage and nearest-neighbour search for embedded snippets.
+#[async_trait]
+pub trait VectorStore: Send + Sync {
+    /// Add records, replacing any with the same id.
+    async fn upsert(&self, records: Vec<VectorRecord>) -> Result<(), MemoryError>;
+
+    /// Find the `top_k` records most similar to `embedding`, best first.
+    async fn search(
+        &self,
+        embedding: &[f32],
+        top_k: usize,
+    ) -> Result<Vec<ScoredRecord>, MemoryError>;
+
+    /// Remove a record.
+    async fn delete(&self, id: &str) -> Result<(), MemoryError>;
+
+    /// Get the number of stored records.
+    async fn len(&self) -> Result<usize, MemoryError>;
+
+    /// Check whether the store is empty.
+    async fn is_empty(&self) -> Result<bool, MemoryError> {
+        Ok(self.len().await? == 0)
+    }
+}
+
+/// Exact in-process vector index.
+///
+/// Search compares the query with every record, which is fast enough for
+/// tens of thousands of snippets. When opened with a path, the index is
+/// loaded from that JSON file and rewritten after every change.
+#[derive(Debug, Default)]
+pub struct InMemoryVectorStore {
+    similarity: Similarity,
+    path: Option<PathBuf>,
+    records: Mutex<Vec<VectorRecord>>,
+}
+
+impl InMemoryVectorStore {
+    /// Create an empty index.
+    pub fn new(similarity: Similarity) -> Self {
+        Self {
+            similarity,
+            path: None,
+            records: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Open an index persisted at `path`, which is created on the first change.
+    pub async fn open(
+        path: impl Into<PathBuf>,
+        similarity: Similarity,
+    ) -> Result<Self, MemoryError> {
+        let path = path.into();
+        let records = match tokio::fs::read(&path).await {
+            Ok(contents) => serde_json::from_slice(&contents)?,
+            Err(error) if error.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(error) => return Err(error.into()),
+        };
+
+        Ok(Self {
+            similarity,
+            path: Some(path),
+            records: Mutex::new(records),
+        })
+    }
+
+    /// Get the path the index is persisted to, if any.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Write the index to its file, if it has one.
+    ///
+    /// Called with the records locked, so concurrent changes are written in order.
+    async fn persist(&self, records: &[VectorRecord]) -> Result<(), MemoryError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        write_atomic(path, serde_json::to_vec(records)?).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl VectorStore for InMemoryVectorStore {
+    async fn upsert(&self, new_records: Vec<VectorRecord>) -> Result<(), MemoryError> {
+        let mut records = self.records.lock().await;
+        let dimensions = records
+            .first()
+            .or(new_records.first())
+            .map(|record| record.embedding.len());
+        if let Some(expected) = dimensions
+            && let Some(record) = new_records
+                .iter()
+                .find(|record| record.embedding.len() != expected)
+        {
+            return Err(MemoryError::DimensionMismatch {
+                expected,
+                actual: record.embedding.len(),
+            });
+        }
+
+        for record in new_records {
+            match records.iter_mut().find(|existing| existing.id == record.id) {
+                Some(existing) => *existing = record,
+                None => records.push(record),
+            }
+        }
+        self.persist(&records).await
+    }
+
+    async fn search(
+        &self,
+        embedding: &[f32],
+        top_k: usize,
+    ) -> Result<Vec<ScoredRecord>, MemoryError> {
+        let records = self.records.lock().await;
+        if let Some(record) = records.first()
+            && record.embedding.len() != embedding.len()
+        {
+            return Err(MemoryError::DimensionMismatch {
+                expected: record.embedding.len(),
+                actual: embedding.len(),
+            });
+        }
+
+        let mut scored: Vec<(f32, &VectorRecord)> = records
+            .iter()
+            .map(|record| (self.similarity.score(embedding, &record.embedding), record))
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        Ok(scored
+            .into_iter()
+            .take(top_k)
+            .map(|(score, record)| ScoredRecord {
+                record: record.clone(),
+                score,
+            })
+            .collect())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), MemoryError> {
+        let mut records = self.records.lock().await;
+        let len = records.len();
+        records.retain(|record| record.id != id);
+        if records.len() == len {
+            return Ok(());
+        }
+        self.persist(&records).await
+    }
+
+    async fn len(&self) -> Result<usize, MemoryError> {
+        Ok(self.records.lock().await.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, embedding: Vec<f32>) -> VectorRecord {
+        VectorRecord {
+            id: id.to_string(),
+            text: format!("text {id}"),
+            embedding,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_similarity() {
+        assert!((Similarity::Cosine.score(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert_eq!(Similarity::Cosine.score(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+        assert_eq!(Similarity::Cosine.score(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+        assert_eq!(Similarity::DotProduct.score(&[1.0, 2.0], &[3.0, 4.0]), 11.0);
+    }
+
+    #[tokio::test]
+    async fn test_search_ranks_by_similarity() {
+        let store = InMemoryVectorStore::new(Similarity::Cosine);
+        store
+            .upsert(vec![
+                record("north", vec![0.0, 1.0]),
+                record("east", vec![1.0, 0.0]),
+                record("north-east", vec![1.0, 1.0]),
+            ])
+            .await
+            .unwrap();
+
+        let results = store.search(&[0.1, 1.0], 2).await.unwrap();
+        let ids: Vec<&str> = results.iter().map(|r| r.record.id.as_str()).collect();
+        assert_eq!(ids, ["north", "north-east"]);
+        assert!(results[0].score > results[1].score);
+
+        // Dot product favours longer vectors
+        let store = InMemoryVectorStore::new(Similarity::DotProduct);
+        store
+            .upsert(vec![
+                record("short", vec![0.0, 1.0]),
+                record("long", vec![0.0, 5.0]),
+            ])
+            .await
+            .unwrap();
+        let results = store.search(&[0.0, 1.0], 1).await.unwrap();
+        assert_eq!(results[0].record.id, "long");
+    }
+
+    #[tokio::test]
+    async fn test_upsert_replaces_and_checks_dimensions() {
+        let store = InMemoryVectorStore::new(Similarity::Cosine);
+        store
+            .upsert(vec![record("a", vec![1.0, 0.0])])
+            .await
+            .unwrap();
+        store
+            .upsert(vec![record("a", vec![0.0, 1.0])])
+            .await
+            .unwrap();
+        assert_eq!(store.len().await.unwrap(), 1);
+        assert_eq!(store.search(&[0.0, 1.0], 1).await.unwrap()[0].score, 1.0);
+
+        let result = store.upsert(vec![record("b", vec![1.0, 0.0, 0.0])]).await;
+        assert!(matches!(
+            result,
+            Err(MemoryError::DimensionMismatch {
+                expected: 2,
+                actual: 3
+            })
+        ));
+        assert!(store.search(&[1.0], 1).await.is_err());
+
+        store.delete("a").await.unwrap();
+        assert!(store.is_empty().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_persisted_index() {
+        let path = std::env::temp_dir().join(format!(
+            "ferrous-llm-memory-{}-index.json",
+            std::process::id()
+        ));
+        let store = InMemoryVectorStore::open(&path, Similarity::Cosine)
+            .await
+            .unwrap();
+        assert!(store.is_empty().await.unwrap());
+        store
+            .upsert(vec![
+                record("a", vec![1.0, 0.0]),
+                record("b", vec![0.0, 1.0]),
+            ])
+            .await
+            .unwrap();
+        store.delete("a").await.unwrap();
+
+        let reopened = InMemoryVectorStore::open(&path, Similarity::Cosine)
+            .await
+            .unwrap();
+        assert_eq!(reopened.len().await.unwrap(), 1);
+        assert_eq!(
+            reopened.search(&[0.0, 1.0], 5).await.unwrap()[0]
+                .record
+                .text,
+            "text b"
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+}
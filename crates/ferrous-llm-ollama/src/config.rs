@@ -7,6 +7,7 @@ use url::Url;
 
 /// Configuration for the Ollama provider.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct OllamaConfig {
     /// Model to use (e.g., "llama2", "codellama", "mistral")
     pub model: String,
@@ -20,6 +21,16 @@ pub struct OllamaConfig {
     /// Embedding model to use (e.g., "nomic-embed-text")
     pub embedding_model: Option<String>,
 
+    /// Number of texts sent in each embedding request
+    pub embedding_batch_size: usize,
+
+    /// Maximum number of embedding requests in flight at once
+    pub embedding_concurrency: usize,
+
+    /// Whether inputs longer than the embedding model's context are truncated
+    /// instead of rejected (Ollama truncates by default)
+    pub truncate: Option<bool>,
+
     /// Keep alive duration for the model (in seconds)
     pub keep_alive: Option<u64>,
 
@@ -34,6 +45,9 @@ impl Default for OllamaConfig {
             base_url: None,
             http: HttpConfig::default(),
             embedding_model: None,
+            embedding_batch_size: 64,
+            embedding_concurrency: 4,
+            truncate: None,
             keep_alive: None,
             options: None,
         }
@@ -70,6 +84,10 @@ impl ProviderConfig for OllamaConfig {
         validation::validate_positive_duration(self.http.timeout, "http.timeout")?;
         validation::validate_range(self.http.max_retries, 0, 10, "http.max_retries")?;
 
+        // Validate embedding batching
+        validation::validate_range(self.embedding_batch_size, 1, 2048, "embedding_batch_size")?;
+        validation::validate_range(self.embedding_concurrency, 1, 64, "embedding_concurrency")?;
+
         // Validate keep_alive if provided
         if let Some(keep_alive) = self.keep_alive
             && keep_alive > 86400
@@ -119,7 +137,13 @@ impl OllamaConfig {
         format!("{base}/api/generate")
     }
 
-    /// Get the embeddings endpoint URL.
+    /// Get the batch embedding endpoint URL.
+    pub fn embed_url(&self) -> String {
+        let base = self.base_url().trim_end_matches('/');
+        format!("{base}/api/embed")
+    }
+
+    /// Get the legacy single-text embeddings endpoint URL.
     pub fn embeddings_url(&self) -> String {
         let base = self.base_url().trim_end_matches('/');
         format!("{base}/api/embeddings")
@@ -131,6 +155,42 @@ impl OllamaConfig {
         format!("{base}/api/tags")
     }
 
+    /// Get the model details endpoint URL.
+    pub fn show_url(&self) -> String {
+        let base = self.base_url().trim_end_matches('/');
+        format!("{base}/api/show")
+    }
+
+    /// Get the model pull endpoint URL.
+    pub fn pull_url(&self) -> String {
+        let base = self.base_url().trim_end_matches('/');
+        format!("{base}/api/pull")
+    }
+
+    /// Get the model delete endpoint URL.
+    pub fn delete_url(&self) -> String {
+        let base = self.base_url().trim_end_matches('/');
+        format!("{base}/api/delete")
+    }
+
+    /// Get the model copy endpoint URL.
+    pub fn copy_url(&self) -> String {
+        let base = self.base_url().trim_end_matches('/');
+        format!("{base}/api/copy")
+    }
+
+    /// Get the model create endpoint URL.
+    pub fn create_url(&self) -> String {
+        let base = self.base_url().trim_end_matches('/');
+        format!("{base}/api/create")
+    }
+
+    /// Get the running models endpoint URL.
+    pub fn running_models_url(&self) -> String {
+        let base = self.base_url().trim_end_matches('/');
+        format!("{base}/api/ps")
+    }
+
     /// Load configuration from environment variables.
     pub fn from_env() -> Result<Self, ConfigError> {
         use ferrous_llm_core::env;
@@ -159,6 +219,7 @@ impl OllamaConfig {
             embedding_model,
             keep_alive,
             options: None,
+            ..Default::default()
         })
     }
 }
@@ -195,6 +256,24 @@ impl OllamaConfigBuilder {
         self
     }
 
+    /// Set the number of texts sent in each embedding request.
+    pub fn embedding_batch_size(mut self, embedding_batch_size: usize) -> Self {
+        self.config.embedding_batch_size = embedding_batch_size;
+        self
+    }
+
+    /// Set the maximum number of embedding requests in flight at once.
+    pub fn embedding_concurrency(mut self, embedding_concurrency: usize) -> Self {
+        self.config.embedding_concurrency = embedding_concurrency;
+        self
+    }
+
+    /// Set whether over-long embedding inputs are truncated instead of rejected.
+    pub fn truncate(mut self, truncate: bool) -> Self {
+        self.config.truncate = Some(truncate);
+        self
+    }
+
     /// Set the keep alive duration.
     pub fn keep_alive(mut self, keep_alive: u64) -> Self {
         self.config.keep_alive = Some(keep_alive);
@@ -258,12 +337,18 @@ mod tests {
         let config = OllamaConfig::builder()
             .model("codellama")
             .embedding_model("nomic-embed-text")
+            .embedding_batch_size(16)
+            .embedding_concurrency(2)
+            .truncate(false)
             .keep_alive(300)
             .timeout(Duration::from_secs(60))
             .build();
 
         assert_eq!(config.model, "codellama");
         assert_eq!(config.embedding_model, Some("nomic-embed-text".to_string()));
+        assert_eq!(config.embedding_batch_size, 16);
+        assert_eq!(config.embedding_concurrency, 2);
+        assert_eq!(config.truncate, Some(false));
         assert_eq!(config.keep_alive, Some(300));
         assert_eq!(config.http.timeout, Duration::from_secs(60));
     }
@@ -273,6 +358,9 @@ mod tests {
         let config = OllamaConfig::new("llama2");
         assert_eq!(config.chat_url(), "http://localhost:11434/api/chat");
         assert_eq!(config.generate_url(), "http://localhost:11434/api/generate");
+        assert_eq!(config.embed_url(), "http://localhost:11434/api/embed");
+        assert_eq!(config.models_url(), "http://localhost:11434/api/tags");
+        assert_eq!(config.running_models_url(), "http://localhost:11434/api/ps");
         assert_eq!(
             config.embeddings_url(),
             "http://localhost:11434/api/embeddings"
@@ -292,4 +380,15 @@ mod tests {
         config.keep_alive = Some(100000); // Too large
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_embedding_batching_validation() {
+        let mut config = OllamaConfig::new("llama2");
+        config.embedding_batch_size = 0;
+        assert!(config.validate().is_err());
+
+        let mut config = OllamaConfig::new("llama2");
+        config.embedding_concurrency = 0;
+        assert!(config.validate().is_err());
+    }
 }
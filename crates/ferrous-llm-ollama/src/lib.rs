@@ -1,24 +1,31 @@
 //! Ollama provider for the LLM library.
 //!
 //! This crate provides an implementation of the LLM core traits for Ollama's API,
-//! including support for chat, completion, streaming, and embeddings.
+//! including support for chat, completion, streaming, tool calling, and embeddings,
+//! plus an [`OllamaManager`] for listing, pulling and deleting local models.
 
 pub mod config;
 pub mod error;
+pub mod manager;
 pub mod provider;
+mod stream;
 pub mod types;
 
 // Re-export main types for convenience
 pub use config::OllamaConfig;
 pub use error::OllamaError;
-pub use provider::OllamaProvider;
+pub use manager::{OllamaManager, OllamaProgressStream};
+pub use provider::{OllamaProvider, OllamaStream};
 pub use types::{
     OllamaChatRequest, OllamaChatResponse, OllamaChoice, OllamaCompletionRequest,
-    OllamaCompletionResponse, OllamaEmbeddingsRequest, OllamaEmbeddingsResponse, OllamaMessage,
-    OllamaStreamChunk, OllamaUsage,
+    OllamaCompletionResponse, OllamaCreateRequest, OllamaEmbedRequest, OllamaEmbedResponse,
+    OllamaEmbeddingsRequest, OllamaEmbeddingsResponse, OllamaFunctionCall, OllamaMessage,
+    OllamaModel, OllamaModelDetails, OllamaModelInfo, OllamaProgress, OllamaRunningModel,
+    OllamaStreamChunk, OllamaTool, OllamaToolCall, OllamaToolFunction, OllamaUsage,
 };
 
 // Re-export core traits
 pub use ferrous_llm_core::{
-    ChatProvider, CompletionProvider, EmbeddingProvider, StreamingProvider,
+    ChatProvider, CompletionProvider, EmbeddingProvider, ModelCatalog, ResponseSchema, StreamEvent,
+    StreamingProvider, StreamingToolProvider, StructuredProvider, ToolProvider,
 };
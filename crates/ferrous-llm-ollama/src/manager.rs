@@ -0,0 +1,333 @@
+//! Ollama model management.
+
+use crate::config::OllamaConfig;
+use crate::error::{OllamaError, OllamaErrorResponse};
+use crate::provider::{build_client, build_stream_client};
+use crate::types::*;
+use ferrous_llm_core::{RetryConfig, retry};
+use futures::Stream;
+use reqwest::{Client, Method, Response};
+use serde::Deserialize;
+use serde_json::json;
+use std::pin::Pin;
+use tokio_stream::{StreamExt, wrappers::ReceiverStream};
+
+/// Stream of progress events from a model pull or create.
+pub type OllamaProgressStream =
+    Pin<Box<dyn Stream<Item = Result<OllamaProgress, OllamaError>> + Send>>;
+
+/// Response of `/api/tags`.
+#[derive(Deserialize)]
+struct ModelList {
+    #[serde(default)]
+    models: Vec<OllamaModel>,
+}
+
+/// Response of `/api/ps`.
+#[derive(Deserialize)]
+struct RunningModelList {
+    #[serde(default)]
+    models: Vec<OllamaRunningModel>,
+}
+
+/// Client for listing, pulling, creating and deleting models on an Ollama server.
+///
+/// Uses the base URL, HTTP settings and retry policy of an [`OllamaConfig`].
+/// Pulls and creates stream [`OllamaProgress`] events until they finish; for
+/// these `http.timeout` limits the wait between events rather than the whole
+/// download.
+#[derive(Debug, Clone)]
+pub struct OllamaManager {
+    config: OllamaConfig,
+    client: Client,
+    retry: RetryConfig,
+}
+
+impl OllamaManager {
+    /// Create a new model manager with the given configuration.
+    pub fn new(config: OllamaConfig) -> Result<Self, OllamaError> {
+        let client = build_client(&config)?;
+        Ok(Self::from_parts(config, client))
+    }
+
+    /// Create a model manager that shares an existing HTTP client.
+    pub(crate) fn from_parts(config: OllamaConfig, client: Client) -> Self {
+        Self {
+            retry: RetryConfig::from(&config.http),
+            config,
+            client,
+        }
+    }
+
+    /// List the models available locally.
+    pub async fn list_models(&self) -> Result<Vec<OllamaModel>, OllamaError> {
+        let response = self
+            .send(&self.client, Method::GET, &self.config.models_url(), None)
+            .await?;
+        let list: ModelList = Self::decode(response).await?;
+        Ok(list.models)
+    }
+
+    /// List the models currently loaded into memory.
+    pub async fn running_models(&self) -> Result<Vec<OllamaRunningModel>, OllamaError> {
+        let response = self
+            .send(
+                &self.client,
+                Method::GET,
+                &self.config.running_models_url(),
+                None,
+            )
+            .await?;
+        let list: RunningModelList = Self::decode(response).await?;
+        Ok(list.models)
+    }
+
+    /// Get details about a model, including its family, size, quantization
+    /// and context length.
+    pub async fn show_model(&self, model: &str) -> Result<OllamaModelInfo, OllamaError> {
+        let body = json!({ "model": model });
+        let response = self
+            .send(
+                &self.client,
+                Method::POST,
+                &self.config.show_url(),
+                Some(&body),
+            )
+            .await?;
+        Self::decode(response).await
+    }
+
+    /// Download a model from the registry, streaming progress.
+    ///
+    /// The stream ends after an event whose status is "success"; errors
+    /// reported mid-download, and a connection that closes before success,
+    /// are yielded as `Err` items.
+    pub async fn pull_model(&self, model: &str) -> Result<OllamaProgressStream, OllamaError> {
+        let body = json!({ "model": model, "stream": true });
+        let client = build_stream_client(&self.config)?;
+        let response = self
+            .send(&client, Method::POST, &self.config.pull_url(), Some(&body))
+            .await?;
+        Ok(progress_stream(response))
+    }
+
+    /// Create a model, e.g. from an existing one with a new system prompt,
+    /// streaming progress.
+    pub async fn create_model(
+        &self,
+        mut request: OllamaCreateRequest,
+    ) -> Result<OllamaProgressStream, OllamaError> {
+        request.stream = Some(true);
+        let body = serde_json::to_value(&request).map_err(|e| OllamaError::Json { source: e })?;
+        let client = build_stream_client(&self.config)?;
+        let response = self
+            .send(
+                &client,
+                Method::POST,
+                &self.config.create_url(),
+                Some(&body),
+            )
+            .await?;
+        Ok(progress_stream(response))
+    }
+
+    /// Copy a model under a new name.
+    pub async fn copy_model(&self, source: &str, destination: &str) -> Result<(), OllamaError> {
+        let body = json!({ "source": source, "destination": destination });
+        self.send(
+            &self.client,
+            Method::POST,
+            &self.config.copy_url(),
+            Some(&body),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Delete a model and its data.
+    pub async fn delete_model(&self, model: &str) -> Result<(), OllamaError> {
+        let body = json!({ "model": model });
+        self.send(
+            &self.client,
+            Method::DELETE,
+            &self.config.delete_url(),
+            Some(&body),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Send a request with `client`, retrying transient failures, and return
+    /// the successful response.
+    async fn send(
+        &self,
+        client: &Client,
+        method: Method,
+        url: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<Response, OllamaError> {
+        retry(&self.retry, || {
+            let mut request = client.request(method.clone(), url);
+            if let Some(body) = body {
+                request = request.json(body);
+            }
+
+            async move {
+                let response = request
+                    .send()
+                    .await
+                    .map_err(|e| OllamaError::Network { source: e })?;
+
+                if response.status().is_success() {
+                    Ok(response)
+                } else {
+                    let status = response.status().as_u16();
+                    let body = response.text().await.unwrap_or_default();
+                    Err(OllamaError::from_response(status, &body))
+                }
+            }
+        })
+        .await
+    }
+
+    /// Decode a JSON response body.
+    async fn decode<T>(response: Response) -> Result<T, OllamaError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        response
+            .json()
+            .await
+            .map_err(|e| OllamaError::Network { source: e })
+    }
+}
+
+/// Parse a newline-delimited JSON progress response into a stream of events.
+fn progress_stream(response: Response) -> OllamaProgressStream {
+    let (tx, rx) = tokio::sync::mpsc::channel::<Result<OllamaProgress, OllamaError>>(100);
+
+    tokio::spawn(async move {
+        let mut byte_stream = response.bytes_stream();
+        let mut buffer = Vec::new();
+
+        while let Some(chunk_result) = byte_stream.next().await {
+            let chunk = match chunk_result {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    let _ = tx.send(Err(OllamaError::Network { source: e })).await;
+                    return;
+                }
+            };
+            buffer.extend_from_slice(chunk.as_ref());
+
+            // Process complete lines; keep any partial line for the next chunk
+            let mut start = 0;
+            while let Some(pos) = buffer[start..].iter().position(|&b| b == b'\n') {
+                let line_end = start + pos;
+                let line = String::from_utf8_lossy(&buffer[start..line_end]).into_owned();
+                start = line_end + 1;
+
+                let Some((event, done)) = parse_progress_line(&line) else {
+                    continue;
+                };
+                if tx.send(event).await.is_err() || done {
+                    return;
+                }
+            }
+            buffer.drain(0..start);
+        }
+
+        // The last line may not end with a newline
+        if let Some((event, done)) = parse_progress_line(&String::from_utf8_lossy(&buffer))
+            && (tx.send(event).await.is_err() || done)
+        {
+            return;
+        }
+
+        // The connection closed cleanly, e.g. on a server restart, mid-operation
+        let _ = tx
+            .send(Err(OllamaError::Other {
+                message: "pull ended before success".to_string(),
+            }))
+            .await;
+    });
+
+    Box::pin(ReceiverStream::new(rx))
+}
+
+/// Parse one progress line, returning the event and whether it is the last.
+fn parse_progress_line(line: &str) -> Option<(Result<OllamaProgress, OllamaError>, bool)> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+
+    if let Ok(error) = serde_json::from_str::<OllamaErrorResponse>(line) {
+        // Errors arrive after a successful status, so classify by message alone
+        return Some((Err(OllamaError::from_error_response(200, error)), true));
+    }
+
+    match serde_json::from_str::<OllamaProgress>(line) {
+        Ok(progress) => {
+            let done = progress.is_success();
+            Some((Ok(progress), done))
+        }
+        Err(e) => Some((Err(OllamaError::Json { source: e }), true)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_progress_line() {
+        let (event, done) = parse_progress_line(
+            r#"{"status":"pulling 6a0746a1ec1a","digest":"sha256:6a07","total":4000,"completed":1000}"#,
+        )
+        .unwrap();
+        let progress = event.unwrap();
+        assert_eq!(progress.digest.as_deref(), Some("sha256:6a07"));
+        assert_eq!(progress.fraction(), Some(0.25));
+        assert!(!done);
+
+        let (event, done) = parse_progress_line(r#"{"status":"success"}"#).unwrap();
+        assert!(event.unwrap().is_success());
+        assert!(done);
+
+        let (event, done) =
+            parse_progress_line(r#"{"error":"pull model manifest: file does not exist"}"#).unwrap();
+        assert!(event.is_err());
+        assert!(done);
+
+        assert!(parse_progress_line("  ").is_none());
+    }
+
+    #[test]
+    fn test_model_info_accessors() {
+        let info: OllamaModelInfo = serde_json::from_value(json!({
+            "details": {
+                "format": "gguf",
+                "family": "llama",
+                "families": ["llama"],
+                "parameter_size": "8.0B",
+                "quantization_level": "Q4_K_M"
+            },
+            "model_info": {
+                "general.architecture": "llama",
+                "llama.context_length": 131072,
+                "llama.embedding_length": 4096
+            },
+            "capabilities": ["completion", "tools"]
+        }))
+        .unwrap();
+
+        assert_eq!(info.family(), Some("llama"));
+        assert_eq!(info.parameter_size(), Some("8.0B"));
+        assert_eq!(info.quantization_level(), Some("Q4_K_M"));
+        assert_eq!(info.context_length(), Some(131072));
+        assert_eq!(info.embedding_length(), Some(4096));
+        assert!(info.has_capability("tools"));
+        assert!(!info.has_capability("vision"));
+    }
+}
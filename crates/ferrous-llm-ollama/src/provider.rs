@@ -1,94 +1,53 @@
 //! Ollama provider implementation.
 
-use crate::{config::OllamaConfig, error::OllamaError, types::*};
+use crate::{
+    config::OllamaConfig, error::OllamaError, manager::OllamaManager, stream::StreamParser,
+    types::*,
+};
 use async_trait::async_trait;
 use ferrous_llm_core::{
-    ChatProvider, ChatRequest, CompletionProvider, CompletionRequest, Embedding, EmbeddingProvider,
-    ProviderResult, StreamingProvider,
+    ChatProvider, ChatRequest, CompletionProvider, CompletionRequest, DEFAULT_MAX_IMAGE_BYTES,
+    Embedding, EmbeddingProvider, InlineImage, MediaError, MessageContent, ModelCatalog, ModelInfo,
+    Parameters, ProviderResult, ResponseSchema, RetryConfig, Role, StreamEvent, StreamingProvider,
+    StreamingToolProvider, StructuredProvider, Tool, ToolChoice, ToolProvider, is_remote_url,
+    retry,
 };
-use futures::Stream;
-use reqwest::{Client, RequestBuilder};
+use futures::{Stream, TryStreamExt};
+use reqwest::{Client, ClientBuilder, RequestBuilder};
 use serde_json::json;
+use std::collections::HashMap;
 use std::pin::Pin;
 use tokio_stream::{StreamExt, wrappers::ReceiverStream};
 
+/// Maximum number of `/api/show` requests sent at once when listing models.
+const MODEL_INFO_CONCURRENCY: usize = 4;
+
+/// Stream of events returned by [`OllamaProvider`] streaming requests.
+pub type OllamaStream = Pin<Box<dyn Stream<Item = Result<StreamEvent, OllamaError>> + Send>>;
+
 /// Ollama provider implementation.
 #[derive(Debug, Clone)]
 pub struct OllamaProvider {
     config: OllamaConfig,
     client: Client,
+    image_client: Client,
+    retry: RetryConfig,
 }
 
 impl OllamaProvider {
     /// Create a new Ollama provider with the given configuration.
     pub fn new(config: OllamaConfig) -> Result<Self, OllamaError> {
-        let mut headers = reqwest::header::HeaderMap::new();
-
-        // Add content type header
-        headers.insert(
-            reqwest::header::CONTENT_TYPE,
-            "application/json"
-                .parse()
-                .map_err(|_| OllamaError::Config {
-                    source: ferrous_llm_core::ConfigError::invalid_value(
-                        "headers",
-                        "Invalid content type",
-                    ),
-                })?,
-        );
-
-        // Add user agent
-        if let Some(ref user_agent) = config.http.user_agent {
-            headers.insert(
-                reqwest::header::USER_AGENT,
-                user_agent.parse().map_err(|_| OllamaError::Config {
-                    source: ferrous_llm_core::ConfigError::invalid_value(
-                        "user_agent",
-                        "Invalid user agent format",
-                    ),
-                })?,
-            );
-        }
-
-        // Add custom headers
-        for (key, value) in &config.http.headers {
-            let header_name: reqwest::header::HeaderName =
-                key.parse().map_err(|_| OllamaError::Config {
-                    source: ferrous_llm_core::ConfigError::invalid_value(
-                        "headers",
-                        "Invalid header name",
-                    ),
-                })?;
-            let header_value: reqwest::header::HeaderValue =
-                value.parse().map_err(|_| OllamaError::Config {
-                    source: ferrous_llm_core::ConfigError::invalid_value(
-                        "headers",
-                        "Invalid header value",
-                    ),
-                })?;
-            headers.insert(header_name, header_value);
-        }
-
-        let mut client_builder = Client::builder()
-            .timeout(config.http.timeout)
-            .default_headers(headers);
-
-        // Configure compression
-        if !config.http.compression {
-            client_builder = client_builder.no_gzip();
-        }
-
-        // Configure connection pool
-        client_builder = client_builder
-            .pool_max_idle_per_host(config.http.pool.max_idle_connections)
-            .pool_idle_timeout(config.http.pool.idle_timeout)
-            .connect_timeout(config.http.pool.connect_timeout);
-
-        let client = client_builder
-            .build()
-            .map_err(|e| OllamaError::Network { source: e })?;
+        Ok(Self {
+            client: build_client(&config)?,
+            image_client: build_image_client(&config)?,
+            retry: RetryConfig::from(&config.http),
+            config,
+        })
+    }
 
-        Ok(Self { config, client })
+    /// Get a client for managing models on the same Ollama server.
+    pub fn manager(&self) -> OllamaManager {
+        OllamaManager::from_parts(self.config.clone(), self.client.clone())
     }
 
     /// Create a request builder with common settings.
@@ -109,9 +68,162 @@ impl OllamaProvider {
                 .await
                 .map_err(|e| OllamaError::Network { source: e })
         } else {
-            let body = response.text().await.unwrap_or_default();
-            Err(OllamaError::from_response(status.as_u16(), &body))
+            Err(Self::error_from_response(response).await)
+        }
+    }
+
+    /// Convert an unsuccessful HTTP response into an error.
+    async fn error_from_response(response: reqwest::Response) -> OllamaError {
+        let status = response.status().as_u16();
+        let body = response.text().await.unwrap_or_default();
+        OllamaError::from_response(status, &body)
+    }
+
+    /// POST a JSON body and decode the response, retrying transient failures.
+    async fn post_json<B, T>(&self, url: &str, body: &B) -> Result<T, OllamaError>
+    where
+        B: serde::Serialize + ?Sized + Sync,
+        T: serde::de::DeserializeOwned,
+    {
+        retry(&self.retry, || async move {
+            let response = self
+                .request_builder(reqwest::Method::POST, url)
+                .json(body)
+                .send()
+                .await
+                .map_err(|e| OllamaError::Network { source: e })?;
+
+            self.handle_response(response).await
+        })
+        .await
+    }
+
+    /// Embed one batch of texts, numbering the embeddings from `offset`.
+    async fn embed_batch(
+        &self,
+        model: &str,
+        texts: &[String],
+        offset: usize,
+    ) -> Result<Vec<Embedding>, OllamaError> {
+        let request = OllamaEmbedRequest {
+            model: model.to_string(),
+            input: texts.to_vec(),
+            truncate: self.config.truncate,
+            options: self.config.options.clone(),
+            keep_alive: self.config.keep_alive.map(|ka| format!("{ka}s")),
+        };
+
+        let response: OllamaEmbedResponse =
+            self.post_json(&self.config.embed_url(), &request).await?;
+        if response.embeddings.len() != texts.len() {
+            return Err(OllamaError::Other {
+                message: format!(
+                    "Expected {} embeddings, got {}",
+                    texts.len(),
+                    response.embeddings.len()
+                ),
+            });
+        }
+
+        Ok(response
+            .embeddings
+            .into_iter()
+            .enumerate()
+            .map(|(i, embedding)| Embedding {
+                embedding,
+                index: offset + i,
+            })
+            .collect())
+    }
+
+    /// POST a JSON body for a streaming response, retrying transient failures
+    /// until the connection is established.
+    async fn post_stream<B>(&self, url: &str, body: &B) -> Result<reqwest::Response, OllamaError>
+    where
+        B: serde::Serialize + ?Sized + Sync,
+    {
+        retry(&self.retry, || async move {
+            let response = self
+                .request_builder(reqwest::Method::POST, url)
+                .json(body)
+                .send()
+                .await
+                .map_err(|e| OllamaError::Network { source: e })?;
+
+            if response.status().is_success() {
+                Ok(response)
+            } else {
+                Err(Self::error_from_response(response).await)
+            }
+        })
+        .await
+    }
+
+    /// Replace remote image URLs in a chat request with their base64 data,
+    /// since Ollama only accepts inline images.
+    async fn resolve_images(&self, request: &mut OllamaChatRequest) -> Result<(), OllamaError> {
+        for message in &mut request.messages {
+            for image in message.images.iter_mut().flatten() {
+                if is_remote_url(image) {
+                    *image = self.fetch_image(image).await?.data;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Download an image, enforcing the size limit while reading the body.
+    async fn fetch_image(&self, url: &str) -> Result<InlineImage, OllamaError> {
+        let mut response = self
+            .image_client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| OllamaError::Network { source: e })?;
+
+        if !response.status().is_success() {
+            return Err(OllamaError::InvalidRequest {
+                message: format!("Failed to fetch image {url}: HTTP {}", response.status()),
+            });
+        }
+
+        let image_error = |error: MediaError| OllamaError::InvalidRequest {
+            message: format!("Invalid image {url}: {error}"),
+        };
+        let too_large = || {
+            image_error(MediaError::TooLarge {
+                limit: DEFAULT_MAX_IMAGE_BYTES,
+            })
+        };
+
+        if response
+            .content_length()
+            .is_some_and(|length| length > DEFAULT_MAX_IMAGE_BYTES as u64)
+        {
+            return Err(too_large());
+        }
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| OllamaError::Network { source: e })?
+        {
+            if bytes.len() + chunk.len() > DEFAULT_MAX_IMAGE_BYTES {
+                return Err(too_large());
+            }
+            bytes.extend_from_slice(&chunk);
         }
+
+        InlineImage::from_bytes(&bytes, content_type.as_deref(), DEFAULT_MAX_IMAGE_BYTES)
+            .map_err(image_error)
     }
 
     /// Apply request parameters to options, handling both existing and new options.
@@ -181,8 +293,11 @@ impl OllamaProvider {
             format: None,
             options: self.config.options.clone(),
             keep_alive: self.config.keep_alive.map(|ka| format!("{ka}s")),
+            tools: None,
         };
 
+        Self::resolve_tool_names(&request.messages, &mut ollama_request.messages);
+
         // Apply parameters to options using helper function
         ollama_request.options =
             Self::apply_parameters_to_options(&request.parameters, ollama_request.options);
@@ -190,6 +305,116 @@ impl OllamaProvider {
         ollama_request
     }
 
+    /// Fill in `tool_name` on tool results.
+    ///
+    /// Ollama identifies a tool result by the name of the tool rather than the
+    /// call ID, so look the name up from the assistant message that made the call.
+    fn resolve_tool_names(
+        messages: &[ferrous_llm_core::Message],
+        ollama_messages: &mut [OllamaMessage],
+    ) {
+        let mut names_by_id = HashMap::new();
+        for (message, ollama_message) in messages.iter().zip(ollama_messages.iter_mut()) {
+            let MessageContent::Tool(tool_content) = &message.content else {
+                continue;
+            };
+
+            for call in tool_content.tool_calls.iter().flatten() {
+                names_by_id.insert(call.id.clone(), call.function.name.clone());
+            }
+
+            if matches!(message.role, Role::Tool) {
+                ollama_message.tool_name = tool_content
+                    .tool_call_id
+                    .as_ref()
+                    .and_then(|id| names_by_id.get(id))
+                    .cloned();
+            }
+        }
+    }
+
+    /// Add tool definitions to an Ollama request.
+    ///
+    /// Ollama can't force or forbid tool calls, so `ToolChoice::None` sends no
+    /// tools and `ToolChoice::Function` sends only the named one.
+    fn apply_tools(request: &mut OllamaChatRequest, tools: &[Tool], parameters: &Parameters) {
+        let tools: Vec<&Tool> = match &parameters.tool_choice {
+            Some(ToolChoice::None) => return,
+            Some(ToolChoice::Function { name }) => tools
+                .iter()
+                .filter(|tool| tool.function.name == *name)
+                .collect(),
+            _ => tools.iter().collect(),
+        };
+        if !tools.is_empty() {
+            request.tools = Some(tools.into_iter().map(|t| t.into()).collect());
+        }
+    }
+
+    /// Send a streaming chat request and parse the response into events.
+    async fn stream_chat(
+        &self,
+        ollama_request: OllamaChatRequest,
+    ) -> Result<OllamaStream, OllamaError> {
+        let response = self
+            .post_stream(&self.config.chat_url(), &ollama_request)
+            .await?;
+
+        // Create a tokio channel for streaming
+        let (tx, rx) = tokio::sync::mpsc::channel::<Result<StreamEvent, OllamaError>>(100);
+
+        // Spawn a task to process the streaming response
+        let tx_clone = tx.clone();
+        tokio::spawn(async move {
+            let mut byte_stream = response.bytes_stream();
+            let mut buffer = Vec::new();
+            let mut parser = StreamParser::new();
+
+            while let Some(chunk_result) = byte_stream.next().await {
+                match chunk_result {
+                    Ok(chunk) => {
+                        buffer.extend_from_slice(chunk.as_ref());
+
+                        // Process complete lines (Ollama sends one JSON object per line)
+                        let mut start = 0;
+                        while let Some(pos) = buffer[start..].iter().position(|&b| b == b'\n') {
+                            let line_end = start + pos;
+                            let line = String::from_utf8_lossy(&buffer[start..line_end]);
+                            start = line_end + 1;
+
+                            for event in parser.parse_line(&line) {
+                                if tx_clone.send(Ok(event)).await.is_err() {
+                                    // Receiver dropped
+                                    return;
+                                }
+                            }
+
+                            // Check if this is the final chunk
+                            if parser.is_done() {
+                                return;
+                            }
+                        }
+
+                        // Keep remaining bytes in buffer
+                        buffer.drain(0..start);
+                    }
+                    Err(e) => {
+                        let _ = tx_clone.send(Err(OllamaError::Network { source: e })).await;
+                        return;
+                    }
+                }
+            }
+
+            // Close the channel when done
+            drop(tx_clone);
+        });
+
+        // Convert the receiver to a stream
+        let content_stream = ReceiverStream::new(rx);
+
+        Ok(Box::pin(content_stream))
+    }
+
     /// Convert core CompletionRequest to Ollama format.
     fn convert_completion_request(&self, request: &CompletionRequest) -> OllamaCompletionRequest {
         let mut ollama_request = OllamaCompletionRequest {
@@ -210,6 +435,105 @@ impl OllamaProvider {
     }
 }
 
+/// Build an HTTP client with the headers, timeouts and pooling from `config`.
+pub(crate) fn build_client(config: &OllamaConfig) -> Result<Client, OllamaError> {
+    client_builder(config)?
+        .timeout(config.http.timeout)
+        .build()
+        .map_err(|e| OllamaError::Network { source: e })
+}
+
+/// Build an HTTP client for long-running streams such as model pulls.
+///
+/// `http.timeout` bounds each read instead of the whole request, so a
+/// download only fails if the server stops sending progress.
+pub(crate) fn build_stream_client(config: &OllamaConfig) -> Result<Client, OllamaError> {
+    client_builder(config)?
+        .read_timeout(config.http.timeout)
+        .build()
+        .map_err(|e| OllamaError::Network { source: e })
+}
+
+/// Build an HTTP client for downloading remote images.
+///
+/// Image URLs point at arbitrary hosts, so this client leaves out the custom
+/// headers meant for the Ollama server and only keeps the timeout and user
+/// agent.
+fn build_image_client(config: &OllamaConfig) -> Result<Client, OllamaError> {
+    let mut client_builder = Client::builder().timeout(config.http.timeout);
+    if let Some(ref user_agent) = config.http.user_agent {
+        client_builder = client_builder.user_agent(user_agent);
+    }
+    client_builder
+        .build()
+        .map_err(|e| OllamaError::Network { source: e })
+}
+
+/// Client builder with the headers, compression and pooling from `config`.
+fn client_builder(config: &OllamaConfig) -> Result<ClientBuilder, OllamaError> {
+    let mut headers = reqwest::header::HeaderMap::new();
+
+    // Add content type header
+    headers.insert(
+        reqwest::header::CONTENT_TYPE,
+        "application/json"
+            .parse()
+            .map_err(|_| OllamaError::Config {
+                source: ferrous_llm_core::ConfigError::invalid_value(
+                    "headers",
+                    "Invalid content type",
+                ),
+            })?,
+    );
+
+    // Add user agent
+    if let Some(ref user_agent) = config.http.user_agent {
+        headers.insert(
+            reqwest::header::USER_AGENT,
+            user_agent.parse().map_err(|_| OllamaError::Config {
+                source: ferrous_llm_core::ConfigError::invalid_value(
+                    "user_agent",
+                    "Invalid user agent format",
+                ),
+            })?,
+        );
+    }
+
+    // Add custom headers
+    for (key, value) in &config.http.headers {
+        let header_name: reqwest::header::HeaderName =
+            key.parse().map_err(|_| OllamaError::Config {
+                source: ferrous_llm_core::ConfigError::invalid_value(
+                    "headers",
+                    "Invalid header name",
+                ),
+            })?;
+        let header_value: reqwest::header::HeaderValue =
+            value.parse().map_err(|_| OllamaError::Config {
+                source: ferrous_llm_core::ConfigError::invalid_value(
+                    "headers",
+                    "Invalid header value",
+                ),
+            })?;
+        headers.insert(header_name, header_value);
+    }
+
+    let mut client_builder = Client::builder().default_headers(headers);
+
+    // Configure compression
+    if !config.http.compression {
+        client_builder = client_builder.no_gzip();
+    }
+
+    // Configure connection pool
+    client_builder = client_builder
+        .pool_max_idle_per_host(config.http.pool.max_idle_connections)
+        .pool_idle_timeout(config.http.pool.idle_timeout)
+        .connect_timeout(config.http.pool.connect_timeout);
+
+    Ok(client_builder)
+}
+
 #[async_trait]
 impl ChatProvider for OllamaProvider {
     type Config = OllamaConfig;
@@ -217,16 +541,11 @@ impl ChatProvider for OllamaProvider {
     type Error = OllamaError;
 
     async fn chat(&self, request: ChatRequest) -> ProviderResult<Self::Response, Self::Error> {
-        let ollama_request = self.convert_chat_request(&request);
+        let mut ollama_request = self.convert_chat_request(&request);
+        self.resolve_images(&mut ollama_request).await?;
 
-        let response = self
-            .request_builder(reqwest::Method::POST, &self.config.chat_url())
-            .json(&ollama_request)
-            .send()
+        self.post_json(&self.config.chat_url(), &ollama_request)
             .await
-            .map_err(|e| OllamaError::Network { source: e })?;
-
-        self.handle_response(response).await
     }
 }
 
@@ -242,14 +561,8 @@ impl CompletionProvider for OllamaProvider {
     ) -> ProviderResult<Self::Response, Self::Error> {
         let ollama_request = self.convert_completion_request(&request);
 
-        let response = self
-            .request_builder(reqwest::Method::POST, &self.config.generate_url())
-            .json(&ollama_request)
-            .send()
+        self.post_json(&self.config.generate_url(), &ollama_request)
             .await
-            .map_err(|e| OllamaError::Network { source: e })?;
-
-        self.handle_response(response).await
     }
 }
 
@@ -258,141 +571,136 @@ impl EmbeddingProvider for OllamaProvider {
     type Config = OllamaConfig;
     type Error = OllamaError;
 
+    /// Embed texts with `/api/embed`, sending `embedding_batch_size` texts per
+    /// request and up to `embedding_concurrency` requests at once.
     async fn embed(&self, texts: &[String]) -> ProviderResult<Vec<Embedding>, Self::Error> {
         let embedding_model = self
             .config
             .embedding_model
             .clone()
             .unwrap_or_else(|| "nomic-embed-text".to_string());
+        let batch_size = self.config.embedding_batch_size.max(1);
+
+        let batches = texts
+            .chunks(batch_size)
+            .enumerate()
+            .map(|(batch, chunk)| self.embed_batch(&embedding_model, chunk, batch * batch_size))
+            .collect::<Vec<_>>();
+
+        // `buffered` keeps the batches in order while running them concurrently
+        let batches: Vec<Vec<Embedding>> = futures::StreamExt::buffered(
+            futures::stream::iter(batches),
+            self.config.embedding_concurrency.max(1),
+        )
+        .try_collect()
+        .await?;
+
+        Ok(batches.into_iter().flatten().collect())
+    }
+}
 
-        let mut embeddings = Vec::new();
-
-        // Ollama embeddings API processes one text at a time
-        for (index, text) in texts.iter().enumerate() {
-            let request = OllamaEmbeddingsRequest {
-                model: embedding_model.clone(),
-                prompt: text.clone(),
-                options: self.config.options.clone(),
-                keep_alive: self.config.keep_alive.map(|ka| format!("{ka}s")),
-            };
-
-            let response = self
-                .request_builder(reqwest::Method::POST, &self.config.embeddings_url())
-                .json(&request)
-                .send()
-                .await
-                .map_err(|e| OllamaError::Network { source: e })?;
-
-            let embeddings_response: OllamaEmbeddingsResponse =
-                self.handle_response(response).await?;
+#[async_trait]
+impl ModelCatalog for OllamaProvider {
+    type Error = OllamaError;
 
-            embeddings.push(Embedding {
-                embedding: embeddings_response.embedding,
-                index,
-            });
-        }
+    /// List local models, asking `/api/show` for each model's capabilities.
+    ///
+    /// Models that can't be shown, e.g. because they were deleted after being
+    /// listed, are still returned with only the details from `/api/tags`.
+    async fn list_models(&self) -> ProviderResult<Vec<ModelInfo>, Self::Error> {
+        let manager = self.manager();
+        let models = manager.list_models().await?;
+        let infos = models
+            .iter()
+            .map(|model| async {
+                let mut info = match manager.show_model(&model.name).await {
+                    Ok(info) => info.to_model_info(&model.name),
+                    Err(_) => model.to_model_info(),
+                };
+                info.metadata.insert("size".to_string(), json!(model.size));
+                info
+            })
+            .collect::<Vec<_>>();
+
+        Ok(
+            futures::StreamExt::buffered(futures::stream::iter(infos), MODEL_INFO_CONCURRENCY)
+                .collect()
+                .await,
+        )
+    }
 
-        Ok(embeddings)
+    async fn model_info(&self, model: &str) -> ProviderResult<ModelInfo, Self::Error> {
+        let info = self.manager().show_model(model).await?;
+        Ok(info.to_model_info(model))
     }
 }
 
 #[async_trait]
 impl StreamingProvider for OllamaProvider {
-    type StreamItem = String;
-    type Stream = Pin<Box<dyn Stream<Item = Result<Self::StreamItem, Self::Error>> + Send>>;
+    type StreamItem = StreamEvent;
+    type Stream = OllamaStream;
 
     async fn chat_stream(&self, request: ChatRequest) -> ProviderResult<Self::Stream, Self::Error> {
         let mut ollama_request = self.convert_chat_request(&request);
         ollama_request.stream = Some(true);
+        self.resolve_images(&mut ollama_request).await?;
 
-        let response = self
-            .request_builder(reqwest::Method::POST, &self.config.chat_url())
-            .json(&ollama_request)
-            .send()
-            .await
-            .map_err(|e| OllamaError::Network { source: e })?;
-
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let body = response.text().await.unwrap_or_default();
-            return Err(OllamaError::from_response(status, &body));
-        }
-
-        // Create a tokio channel for streaming
-        let (tx, rx) = tokio::sync::mpsc::channel::<Result<String, OllamaError>>(100);
-
-        // Spawn a task to process the streaming response
-        let tx_clone = tx.clone();
-        tokio::spawn(async move {
-            let mut byte_stream = response.bytes_stream();
-            let mut buffer = Vec::new();
-
-            while let Some(chunk_result) = byte_stream.next().await {
-                match chunk_result {
-                    Ok(chunk) => {
-                        buffer.extend_from_slice(chunk.as_ref());
+        self.stream_chat(ollama_request).await
+    }
+}
 
-                        // Process complete lines (Ollama sends one JSON object per line)
-                        let mut start = 0;
-                        while let Some(pos) = buffer[start..].iter().position(|&b| b == b'\n') {
-                            let line_end = start + pos;
-                            let line = String::from_utf8_lossy(&buffer[start..line_end])
-                                .trim()
-                                .to_string();
-                            start = line_end + 1;
+#[async_trait]
+impl ToolProvider for OllamaProvider {
+    async fn chat_with_tools(
+        &self,
+        request: ChatRequest,
+        tools: &[Tool],
+    ) -> ProviderResult<Self::Response, Self::Error> {
+        let mut ollama_request = self.convert_chat_request(&request);
+        Self::apply_tools(&mut ollama_request, tools, &request.parameters);
+        self.resolve_images(&mut ollama_request).await?;
 
-                            if !line.is_empty() {
-                                // Try to parse the JSON chunk
-                                if let Ok(chunk) = serde_json::from_str::<OllamaStreamChunk>(&line)
-                                {
-                                    // Extract content from the chunk
-                                    let content = if let Some(ref message) = chunk.message {
-                                        message.content.as_str()
-                                    } else {
-                                        chunk.response.as_deref().unwrap_or_default()
-                                    };
-
-                                    if !content.is_empty()
-                                        && tx_clone.send(Ok(content.to_string())).await.is_err()
-                                    {
-                                        // Receiver dropped
-                                        return;
-                                    }
-
-                                    // Check if this is the final chunk
-                                    if chunk.done {
-                                        drop(tx_clone);
-                                        return;
-                                    }
-                                }
-                            }
-                        }
+        self.post_json(&self.config.chat_url(), &ollama_request)
+            .await
+    }
+}
 
-                        // Keep remaining bytes in buffer
-                        buffer.drain(0..start);
-                    }
-                    Err(e) => {
-                        let _ = tx_clone.send(Err(OllamaError::Network { source: e })).await;
-                        return;
-                    }
-                }
-            }
+#[async_trait]
+impl StreamingToolProvider for OllamaProvider {
+    async fn chat_stream_with_tools(
+        &self,
+        request: ChatRequest,
+        tools: &[Tool],
+    ) -> ProviderResult<Self::Stream, Self::Error> {
+        let mut ollama_request = self.convert_chat_request(&request);
+        ollama_request.stream = Some(true);
+        Self::apply_tools(&mut ollama_request, tools, &request.parameters);
+        self.resolve_images(&mut ollama_request).await?;
 
-            // Close the channel when done
-            drop(tx_clone);
-        });
+        self.stream_chat(ollama_request).await
+    }
+}
 
-        // Convert the receiver to a stream
-        let content_stream = ReceiverStream::new(rx);
+#[async_trait]
+impl StructuredProvider for OllamaProvider {
+    async fn chat_structured(
+        &self,
+        request: ChatRequest,
+        schema: &ResponseSchema,
+    ) -> ProviderResult<Self::Response, Self::Error> {
+        let mut ollama_request = self.convert_chat_request(&request);
+        ollama_request.format = Some(schema.schema.clone());
+        self.resolve_images(&mut ollama_request).await?;
 
-        Ok(Box::pin(content_stream))
+        self.post_json(&self.config.chat_url(), &ollama_request)
+            .await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ferrous_llm_core::{Message, Metadata, Parameters};
+    use ferrous_llm_core::{FunctionCall, Message, Metadata, Parameters, ToolCall};
 
     fn create_test_config() -> OllamaConfig {
         OllamaConfig::new("llama2")
@@ -426,6 +734,81 @@ mod tests {
         assert_eq!(ollama_request.stream, Some(false));
     }
 
+    #[test]
+    fn test_apply_tools_tool_choice() {
+        let provider = OllamaProvider::new(create_test_config()).unwrap();
+        let tools = [
+            Tool::function("search", "Search the web", json!({"type": "object"})),
+            Tool::function("extract", "Extract fields", json!({"type": "object"})),
+        ];
+
+        let request = ChatRequest::builder()
+            .user_message("Hi")
+            .tool_choice(ToolChoice::function("extract"))
+            .build();
+        let mut ollama_request = provider.convert_chat_request(&request);
+        OllamaProvider::apply_tools(&mut ollama_request, &tools, &request.parameters);
+        let tools_sent = ollama_request.tools.unwrap();
+        assert_eq!(tools_sent.len(), 1);
+        assert_eq!(tools_sent[0].function.name, "extract");
+
+        let request = ChatRequest::builder()
+            .user_message("Hi")
+            .tool_choice(ToolChoice::None)
+            .build();
+        let mut ollama_request = provider.convert_chat_request(&request);
+        OllamaProvider::apply_tools(&mut ollama_request, &tools, &request.parameters);
+        assert!(ollama_request.tools.is_none());
+    }
+
+    #[test]
+    fn test_convert_tool_round_trip() {
+        let config = create_test_config();
+        let provider = OllamaProvider::new(config).unwrap();
+
+        let tool_call = ToolCall {
+            id: "call_0".to_string(),
+            call_type: "function".to_string(),
+            function: FunctionCall {
+                name: "get_weather".to_string(),
+                arguments: r#"{"city": "Paris"}"#.to_string(),
+            },
+        };
+        let request = ChatRequest::builder()
+            .user_message("What's the weather in Paris?")
+            .assistant_with_tools("", vec![tool_call])
+            .tool_response("Sunny, 22C", "call_0")
+            .build();
+
+        let mut ollama_request = provider.convert_chat_request(&request);
+        OllamaProvider::apply_tools(
+            &mut ollama_request,
+            &[Tool::function(
+                "get_weather",
+                "Get the weather",
+                json!({"type": "object"}),
+            )],
+            &request.parameters,
+        );
+
+        let body = serde_json::to_value(&ollama_request).unwrap();
+        assert_eq!(body["tools"][0]["type"], "function");
+        assert_eq!(body["tools"][0]["function"]["name"], "get_weather");
+
+        let assistant = &body["messages"][1];
+        assert_eq!(assistant["role"], "assistant");
+        assert_eq!(assistant["content"], "");
+        assert_eq!(
+            assistant["tool_calls"][0]["function"]["arguments"],
+            json!({"city": "Paris"})
+        );
+
+        let result = &body["messages"][2];
+        assert_eq!(result["role"], "tool");
+        assert_eq!(result["content"], "Sunny, 22C");
+        assert_eq!(result["tool_name"], "get_weather");
+    }
+
     #[test]
     fn test_convert_completion_request() {
         let config = create_test_config();
@@ -562,6 +945,8 @@ mod tests {
             stop_sequences: vec!["STOP".to_string(), "END".to_string(), "FINISH".to_string()],
             frequency_penalty: Some(0.5), // This should be ignored as it's not supported by Ollama
             presence_penalty: Some(0.3),  // This should be ignored as it's not supported by Ollama
+            tool_choice: Some(ToolChoice::Required), // Tool settings are not model options
+            parallel_tool_calls: Some(false),
         };
 
         let result = OllamaProvider::apply_parameters_to_options(&params, None);
@@ -587,6 +972,7 @@ mod tests {
                 .unwrap()
                 .contains_key("presence_penalty")
         );
+        assert_eq!(options.as_object().unwrap().len(), 5);
     }
 
     #[test]
@@ -0,0 +1,195 @@
+//! Parsing of Ollama newline-delimited JSON streams into core stream events.
+
+use crate::error::OllamaErrorResponse;
+use crate::types::{OllamaStreamChunk, finish_reason};
+use ferrous_llm_core::{StreamEvent, Usage};
+
+/// Incremental parser for Ollama's streaming chat format.
+#[derive(Debug, Default)]
+pub(crate) struct StreamParser {
+    started: bool,
+    done: bool,
+    /// Number of tool calls seen so far; Ollama sends each call complete
+    tool_calls: usize,
+}
+
+impl StreamParser {
+    /// Create a new stream parser.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the final chunk or an error has been seen.
+    pub(crate) fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Parse a single JSON line and return the events it produces.
+    pub(crate) fn parse_line(&mut self, line: &str) -> Vec<StreamEvent> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Vec::new();
+        }
+
+        match serde_json::from_str::<OllamaStreamChunk>(line) {
+            Ok(chunk) => self.parse_chunk(chunk),
+            Err(_) => match serde_json::from_str::<OllamaErrorResponse>(line) {
+                Ok(error) => {
+                    self.done = true;
+                    vec![StreamEvent::Error {
+                        message: error.error,
+                    }]
+                }
+                Err(_) => Vec::new(),
+            },
+        }
+    }
+
+    fn parse_chunk(&mut self, chunk: OllamaStreamChunk) -> Vec<StreamEvent> {
+        let mut events = Vec::new();
+
+        if !self.started {
+            self.started = true;
+            events.push(StreamEvent::MessageStart {
+                id: None,
+                model: Some(chunk.model.clone()),
+            });
+        }
+
+        let content = match chunk.message {
+            Some(ref message) => message.content.as_str(),
+            None => chunk.response.as_deref().unwrap_or_default(),
+        };
+        if !content.is_empty() {
+            events.push(StreamEvent::text_delta(content));
+        }
+
+        if let Some(tool_calls) = chunk.message.as_ref().and_then(|m| m.tool_calls.as_ref()) {
+            for call in tool_calls {
+                let index = self.tool_calls;
+                self.tool_calls += 1;
+
+                let tool_call = call.to_core(index);
+                events.push(StreamEvent::ToolCallStart {
+                    index,
+                    id: tool_call.id.clone(),
+                    name: tool_call.function.name.clone(),
+                });
+                events.push(StreamEvent::ToolCallDelta {
+                    index,
+                    arguments: tool_call.function.arguments.clone(),
+                });
+                events.push(StreamEvent::ToolCallEnd { index, tool_call });
+            }
+        }
+
+        if chunk.done {
+            if chunk.prompt_eval_count.is_some() || chunk.eval_count.is_some() {
+                let prompt_tokens = chunk.prompt_eval_count.unwrap_or(0);
+                let completion_tokens = chunk.eval_count.unwrap_or(0);
+                events.push(StreamEvent::Usage {
+                    usage: Usage {
+                        prompt_tokens,
+                        completion_tokens,
+                        total_tokens: prompt_tokens + completion_tokens,
+                    },
+                });
+            }
+
+            let reason = finish_reason(chunk.done_reason.as_deref(), self.tool_calls > 0);
+            events.push(StreamEvent::Finish { reason });
+            events.push(StreamEvent::Done);
+            self.done = true;
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferrous_llm_core::FinishReason;
+
+    #[test]
+    fn test_chat_stream() {
+        let mut parser = StreamParser::new();
+
+        let events = parser.parse_line(
+            r#"{"model":"llama3.2","created_at":"2024-01-01T00:00:00Z","message":{"role":"assistant","content":"Hi"},"done":false}"#,
+        );
+        assert!(matches!(
+            events.as_slice(),
+            [StreamEvent::MessageStart { model: Some(model), .. }, StreamEvent::TextDelta { text }]
+                if model == "llama3.2" && text == "Hi"
+        ));
+
+        let events = parser.parse_line(
+            r#"{"model":"llama3.2","created_at":"2024-01-01T00:00:01Z","message":{"role":"assistant","content":""},"done":true,"done_reason":"length","prompt_eval_count":10,"eval_count":5}"#,
+        );
+        match events.as_slice() {
+            [
+                StreamEvent::Usage { usage },
+                StreamEvent::Finish {
+                    reason: FinishReason::Length,
+                },
+                StreamEvent::Done,
+            ] => assert_eq!(usage.total_tokens, 15),
+            other => panic!("unexpected events: {other:?}"),
+        }
+        assert!(parser.is_done());
+    }
+
+    #[test]
+    fn test_tool_call_stream() {
+        let mut parser = StreamParser::new();
+
+        let events = parser.parse_line(
+            r#"{"model":"llama3.2","created_at":"2024-01-01T00:00:00Z","message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"get_weather","arguments":{"city":"Paris"}}}]},"done":false}"#,
+        );
+        match events.as_slice() {
+            [
+                StreamEvent::MessageStart { .. },
+                StreamEvent::ToolCallStart { index: 0, id, name },
+                StreamEvent::ToolCallDelta {
+                    index: 0,
+                    arguments,
+                },
+                StreamEvent::ToolCallEnd {
+                    index: 0,
+                    tool_call,
+                },
+            ] => {
+                assert_eq!(id, "call_0");
+                assert_eq!(name, "get_weather");
+                assert_eq!(arguments, r#"{"city":"Paris"}"#);
+                assert_eq!(tool_call.function.arguments, *arguments);
+            }
+            other => panic!("unexpected events: {other:?}"),
+        }
+
+        let events = parser.parse_line(
+            r#"{"model":"llama3.2","created_at":"2024-01-01T00:00:01Z","message":{"role":"assistant","content":""},"done":true,"done_reason":"stop"}"#,
+        );
+        assert!(matches!(
+            events.as_slice(),
+            [
+                StreamEvent::Finish {
+                    reason: FinishReason::ToolCalls
+                },
+                StreamEvent::Done
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_in_band_error() {
+        let mut parser = StreamParser::new();
+        let events = parser.parse_line(r#"{"error":"model not found"}"#);
+        assert!(matches!(
+            events.as_slice(),
+            [StreamEvent::Error { message }] if message == "model not found"
+        ));
+        assert!(parser.is_done());
+    }
+}
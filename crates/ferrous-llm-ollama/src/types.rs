@@ -1,7 +1,10 @@
 //! Ollama-specific request and response types.
 
 use chrono::{DateTime, Utc};
-use ferrous_llm_core::{ChatResponse, CompletionResponse, FinishReason, Metadata, Usage};
+use ferrous_llm_core::{
+    ChatResponse, CompletionResponse, FinishReason, FunctionCall, Metadata, ModelInfo, Tool,
+    ToolCall, Usage, parse_data_url,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -12,21 +15,98 @@ pub struct OllamaChatRequest {
     pub messages: Vec<OllamaMessage>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
+    /// "json", or a JSON schema the response must match
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub format: Option<String>,
+    pub format: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub options: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub keep_alive: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<OllamaTool>>,
 }
 
 /// Ollama message format.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OllamaMessage {
     pub role: String,
+    #[serde(default)]
     pub content: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub images: Option<Vec<String>>, // Base64 encoded images
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<OllamaToolCall>>,
+    /// Name of the tool whose result this message carries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
+}
+
+/// Ollama tool definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaTool {
+    #[serde(rename = "type")]
+    pub tool_type: String,
+    pub function: OllamaToolFunction,
+}
+
+/// Ollama function definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaToolFunction {
+    pub name: String,
+    pub description: String,
+    pub parameters: serde_json::Value,
+}
+
+/// Ollama tool call made by the model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaToolCall {
+    /// Call ID, only sent by newer Ollama versions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub function: OllamaFunctionCall,
+}
+
+/// Ollama function call; unlike OpenAI, arguments are a JSON object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaFunctionCall {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: serde_json::Value,
+}
+
+impl OllamaMessage {
+    /// Convert the tool calls in this message to core tool calls.
+    ///
+    /// Ollama doesn't always assign call IDs, so missing IDs are derived from
+    /// the call's position in the message.
+    pub fn core_tool_calls(&self) -> Option<Vec<ToolCall>> {
+        let tool_calls = self.tool_calls.as_ref().filter(|calls| !calls.is_empty())?;
+        Some(
+            tool_calls
+                .iter()
+                .enumerate()
+                .map(|(index, call)| call.to_core(index))
+                .collect(),
+        )
+    }
+}
+
+impl OllamaToolCall {
+    /// Convert to a core tool call, using `index` to derive a missing ID.
+    pub fn to_core(&self, index: usize) -> ToolCall {
+        let arguments = match &self.function.arguments {
+            serde_json::Value::Null => "{}".to_string(),
+            arguments => arguments.to_string(),
+        };
+        ToolCall {
+            id: self.id.clone().unwrap_or_else(|| format!("call_{index}")),
+            call_type: "function".to_string(),
+            function: FunctionCall {
+                name: self.function.name.clone(),
+                arguments,
+            },
+        }
+    }
 }
 
 /// Ollama chat completion response.
@@ -37,6 +117,8 @@ pub struct OllamaChatResponse {
     pub message: OllamaMessage,
     pub done: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub done_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub total_duration: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub load_duration: Option<u64>,
@@ -57,8 +139,9 @@ pub struct OllamaCompletionRequest {
     pub prompt: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
+    /// "json", or a JSON schema the response must match
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub format: Option<String>,
+    pub format: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub options: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -75,6 +158,8 @@ pub struct OllamaCompletionResponse {
     pub response: String,
     pub done: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub done_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<Vec<u32>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub total_duration: Option<u64>,
@@ -90,7 +175,34 @@ pub struct OllamaCompletionResponse {
     pub eval_duration: Option<u64>,
 }
 
-/// Ollama embeddings request.
+/// Ollama batch embedding request for `/api/embed`.
+#[derive(Debug, Clone, Serialize)]
+pub struct OllamaEmbedRequest {
+    pub model: String,
+    pub input: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncate: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep_alive: Option<String>,
+}
+
+/// Ollama batch embedding response, with one embedding per input.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OllamaEmbedResponse {
+    #[serde(default)]
+    pub model: Option<String>,
+    pub embeddings: Vec<Vec<f32>>,
+    #[serde(default)]
+    pub total_duration: Option<u64>,
+    #[serde(default)]
+    pub load_duration: Option<u64>,
+    #[serde(default)]
+    pub prompt_eval_count: Option<u32>,
+}
+
+/// Ollama embeddings request for the legacy single-text `/api/embeddings`.
 #[derive(Debug, Clone, Serialize)]
 pub struct OllamaEmbeddingsRequest {
     pub model: String,
@@ -101,7 +213,7 @@ pub struct OllamaEmbeddingsRequest {
     pub keep_alive: Option<String>,
 }
 
-/// Ollama embeddings response.
+/// Ollama embeddings response from the legacy `/api/embeddings`.
 #[derive(Debug, Clone, Deserialize)]
 pub struct OllamaEmbeddingsResponse {
     pub embedding: Vec<f32>,
@@ -118,6 +230,8 @@ pub struct OllamaStreamChunk {
     pub response: Option<String>, // For completion streaming
     pub done: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub done_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub total_duration: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub load_duration: Option<u64>,
@@ -147,6 +261,282 @@ pub struct OllamaChoice {
     pub finish_reason: Option<String>,
 }
 
+/// Details Ollama reports about a model's weights.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OllamaModelDetails {
+    #[serde(default)]
+    pub parent_model: Option<String>,
+    #[serde(default)]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub family: Option<String>,
+    #[serde(default)]
+    pub families: Option<Vec<String>>,
+    /// Parameter count, e.g. "8.0B"
+    #[serde(default)]
+    pub parameter_size: Option<String>,
+    /// Quantization, e.g. "Q4_K_M"
+    #[serde(default)]
+    pub quantization_level: Option<String>,
+}
+
+/// A model available locally, as listed by `/api/tags`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaModel {
+    pub name: String,
+    pub model: String,
+    #[serde(default)]
+    pub modified_at: Option<DateTime<Utc>>,
+    /// Size on disk in bytes
+    pub size: u64,
+    pub digest: String,
+    #[serde(default)]
+    pub details: OllamaModelDetails,
+}
+
+impl OllamaModelDetails {
+    /// Add the family, parameter size and quantization to the metadata of `info`.
+    fn insert_metadata(&self, info: &mut ModelInfo) {
+        let details = [
+            ("family", &self.family),
+            ("parameter_size", &self.parameter_size),
+            ("quantization_level", &self.quantization_level),
+        ];
+        for (key, value) in details {
+            if let Some(value) = value {
+                info.metadata.insert(key.to_string(), value.as_str().into());
+            }
+        }
+    }
+}
+
+impl OllamaModel {
+    /// Convert to a catalog entry using only what `/api/tags` reports.
+    ///
+    /// Capabilities and the context window are unknown without `/api/show`.
+    pub fn to_model_info(&self) -> ModelInfo {
+        let mut info = ModelInfo::new("ollama", &self.name);
+        self.details.insert_metadata(&mut info);
+        info
+    }
+}
+
+/// A model loaded into memory, as listed by `/api/ps`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaRunningModel {
+    pub name: String,
+    pub model: String,
+    /// Memory used in bytes
+    pub size: u64,
+    pub digest: String,
+    #[serde(default)]
+    pub details: OllamaModelDetails,
+    /// When the model will be unloaded
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Video memory used in bytes
+    #[serde(default)]
+    pub size_vram: u64,
+    /// Context length the model was loaded with
+    #[serde(default)]
+    pub context_length: Option<u64>,
+}
+
+/// Information about a model from `/api/show`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OllamaModelInfo {
+    #[serde(default)]
+    pub modelfile: Option<String>,
+    #[serde(default)]
+    pub parameters: Option<String>,
+    #[serde(default)]
+    pub template: Option<String>,
+    #[serde(default)]
+    pub system: Option<String>,
+    #[serde(default)]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub details: OllamaModelDetails,
+    /// Architecture metadata keyed like "general.architecture" or
+    /// "llama.context_length"
+    #[serde(default)]
+    pub model_info: HashMap<String, serde_json::Value>,
+    /// Features such as "completion", "tools", "vision" or "embedding"
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    #[serde(default)]
+    pub modified_at: Option<DateTime<Utc>>,
+}
+
+impl OllamaModelInfo {
+    /// Get the model family, e.g. "llama".
+    pub fn family(&self) -> Option<&str> {
+        self.details.family.as_deref()
+    }
+
+    /// Get the parameter count as reported by Ollama, e.g. "8.0B".
+    pub fn parameter_size(&self) -> Option<&str> {
+        self.details.parameter_size.as_deref()
+    }
+
+    /// Get the quantization level, e.g. "Q4_K_M".
+    pub fn quantization_level(&self) -> Option<&str> {
+        self.details.quantization_level.as_deref()
+    }
+
+    /// Get the model architecture, e.g. "llama".
+    pub fn architecture(&self) -> Option<&str> {
+        self.model_info
+            .get("general.architecture")
+            .and_then(|value| value.as_str())
+    }
+
+    /// Get the maximum context length the model was trained with.
+    pub fn context_length(&self) -> Option<u64> {
+        self.architecture_value("context_length")
+    }
+
+    /// Get the length of the model's embedding vectors.
+    pub fn embedding_length(&self) -> Option<u64> {
+        self.architecture_value("embedding_length")
+    }
+
+    /// Check whether the model supports a capability, e.g. "tools".
+    pub fn has_capability(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|c| c == capability)
+    }
+
+    /// Describe the model as core [`ModelInfo`].
+    ///
+    /// Models that report no capabilities, as older Ollama versions do, are
+    /// assumed to support completion.
+    pub fn to_model_info(&self, model: &str) -> ModelInfo {
+        let completion = self.capabilities.is_empty() || self.has_capability("completion");
+        let mut info = ModelInfo::new("ollama", model);
+        info.context_window = self
+            .context_length()
+            .map(|length| length.try_into().unwrap_or(u32::MAX));
+        info.supports_tools = self.has_capability("tools");
+        info.supports_vision = self.has_capability("vision");
+        info.supports_streaming = completion;
+        info.supports_json_mode = completion;
+        self.details.insert_metadata(&mut info);
+        info
+    }
+
+    /// Read an architecture-specific number such as "llama.context_length".
+    fn architecture_value(&self, key: &str) -> Option<u64> {
+        let architecture = self.architecture()?;
+        self.model_info
+            .get(&format!("{architecture}.{key}"))
+            .and_then(|value| value.as_u64())
+    }
+}
+
+/// Progress of a model pull or create, streamed as newline-delimited JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaProgress {
+    /// Current step, e.g. "pulling manifest" or "success"
+    pub status: String,
+    /// Layer being downloaded, if any
+    #[serde(default)]
+    pub digest: Option<String>,
+    /// Total bytes of the layer
+    #[serde(default)]
+    pub total: Option<u64>,
+    /// Bytes of the layer downloaded so far
+    #[serde(default)]
+    pub completed: Option<u64>,
+}
+
+impl OllamaProgress {
+    /// Get the fraction of the current layer that has been downloaded.
+    pub fn fraction(&self) -> Option<f64> {
+        match (self.completed, self.total) {
+            (Some(completed), Some(total)) if total > 0 => Some(completed as f64 / total as f64),
+            _ => None,
+        }
+    }
+
+    /// Check whether this is the final event of a successful operation.
+    pub fn is_success(&self) -> bool {
+        self.status == "success"
+    }
+}
+
+/// Request to create a model with `/api/create`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OllamaCreateRequest {
+    /// Name of the model to create
+    pub model: String,
+    /// Existing model to build on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// Files to create the model from, mapping file names to blob digests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<HashMap<String, String>>,
+    /// LoRA adapters, mapping file names to blob digests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adapters: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    /// Default model parameters, e.g. `{"temperature": 0.2}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<serde_json::Value>,
+    /// Messages that start every conversation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<Vec<OllamaMessage>>,
+    /// Quantization to apply to a non-quantized model, e.g. "q4_K_M"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantize: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+}
+
+impl OllamaCreateRequest {
+    /// Create a request for a model named `model`.
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Build on an existing model.
+    pub fn from(mut self, from: impl Into<String>) -> Self {
+        self.from = Some(from.into());
+        self
+    }
+
+    /// Set the system prompt.
+    pub fn system(mut self, system: impl Into<String>) -> Self {
+        self.system = Some(system.into());
+        self
+    }
+
+    /// Set the prompt template.
+    pub fn template(mut self, template: impl Into<String>) -> Self {
+        self.template = Some(template.into());
+        self
+    }
+
+    /// Set default model parameters.
+    pub fn parameters(mut self, parameters: serde_json::Value) -> Self {
+        self.parameters = Some(parameters);
+        self
+    }
+
+    /// Quantize the model.
+    pub fn quantize(mut self, quantize: impl Into<String>) -> Self {
+        self.quantize = Some(quantize.into());
+        self
+    }
+}
+
 /// Wrapper for Ollama chat response that includes converted generic data.
 #[derive(Debug, Clone)]
 pub struct OllamaChatResponseWrapper {
@@ -286,20 +676,15 @@ impl ChatResponse for OllamaChatResponseWrapper {
     }
 
     fn finish_reason(&self) -> Option<FinishReason> {
-        if self.response.done {
-            Some(FinishReason::Stop)
-        } else {
-            None
-        }
+        self.response.finish_reason()
     }
 
     fn metadata(&self) -> Metadata {
         self.converted_metadata.clone()
     }
 
-    fn tool_calls(&self) -> Option<Vec<ferrous_llm_core::ToolCall>> {
-        // Ollama doesn't support tool calls in the same way as OpenAI
-        None
+    fn tool_calls(&self) -> Option<Vec<ToolCall>> {
+        self.response.message.core_tool_calls()
     }
 }
 
@@ -314,11 +699,7 @@ impl CompletionResponse for OllamaCompletionResponseWrapper {
     }
 
     fn finish_reason(&self) -> Option<FinishReason> {
-        if self.response.done {
-            Some(FinishReason::Stop)
-        } else {
-            None
-        }
+        self.response.finish_reason()
     }
 
     fn metadata(&self) -> Metadata {
@@ -342,11 +723,13 @@ impl ChatResponse for OllamaChatResponse {
     }
 
     fn finish_reason(&self) -> Option<FinishReason> {
-        if self.done {
-            Some(FinishReason::Stop)
-        } else {
-            None
-        }
+        let has_tool_calls = self
+            .message
+            .tool_calls
+            .as_ref()
+            .is_some_and(|c| !c.is_empty());
+        self.done
+            .then(|| finish_reason(self.done_reason.as_deref(), has_tool_calls))
     }
 
     fn metadata(&self) -> Metadata {
@@ -372,8 +755,8 @@ impl ChatResponse for OllamaChatResponse {
         }
     }
 
-    fn tool_calls(&self) -> Option<Vec<ferrous_llm_core::ToolCall>> {
-        None
+    fn tool_calls(&self) -> Option<Vec<ToolCall>> {
+        self.message.core_tool_calls()
     }
 }
 
@@ -393,11 +776,8 @@ impl CompletionResponse for OllamaCompletionResponse {
     }
 
     fn finish_reason(&self) -> Option<FinishReason> {
-        if self.done {
-            Some(FinishReason::Stop)
-        } else {
-            None
-        }
+        self.done
+            .then(|| finish_reason(self.done_reason.as_deref(), false))
     }
 
     fn metadata(&self) -> Metadata {
@@ -448,13 +828,30 @@ impl From<&ferrous_llm_core::Message> for OllamaMessage {
                 text_parts.join("\n")
             }
             ferrous_llm_core::MessageContent::Tool(tool_content) => {
-                // Handle tool content - use text if available, otherwise create a placeholder
-                tool_content
-                    .text
-                    .as_deref()
-                    .unwrap_or("[Tool response]")
-                    .to_string()
+                tool_content.text.clone().unwrap_or_default()
+            }
+        };
+
+        let tool_calls = match &message.content {
+            ferrous_llm_core::MessageContent::Tool(tool_content) => {
+                tool_content.tool_calls.as_ref().map(|calls| {
+                    calls
+                        .iter()
+                        .map(|call| OllamaToolCall {
+                            id: Some(call.id.clone()),
+                            function: OllamaFunctionCall {
+                                name: call.function.name.clone(),
+                                // Ollama expects an object; pass through anything unparsable
+                                arguments: serde_json::from_str(&call.function.arguments)
+                                    .unwrap_or_else(|_| {
+                                        serde_json::Value::String(call.function.arguments.clone())
+                                    }),
+                            },
+                        })
+                        .collect()
+                })
             }
+            _ => None,
         };
 
         // Extract images from multimodal content
@@ -464,8 +861,12 @@ impl From<&ferrous_llm_core::Message> for OllamaMessage {
                     .iter()
                     .filter_map(|part| match part {
                         ferrous_llm_core::ContentPart::Image { image_source, .. } => {
+                            // Ollama takes raw base64; remote URLs are fetched by the provider
                             let url: String = image_source.clone().into();
-                            Some(url)
+                            Some(match parse_data_url(&url) {
+                                Some(image) => image.data,
+                                None => url,
+                            })
                         }
                         _ => None,
                     })
@@ -483,6 +884,21 @@ impl From<&ferrous_llm_core::Message> for OllamaMessage {
             role,
             content,
             images,
+            tool_calls,
+            tool_name: None,
+        }
+    }
+}
+
+impl From<&Tool> for OllamaTool {
+    fn from(tool: &Tool) -> Self {
+        Self {
+            tool_type: tool.tool_type.clone(),
+            function: OllamaToolFunction {
+                name: tool.function.name.clone(),
+                description: tool.function.description.clone(),
+                parameters: tool.function.parameters.clone(),
+            },
         }
     }
 }
@@ -507,6 +923,15 @@ impl From<&OllamaUsage> for Usage {
     }
 }
 
+/// Map Ollama's `done_reason` to a finish reason.
+pub(crate) fn finish_reason(done_reason: Option<&str>, has_tool_calls: bool) -> FinishReason {
+    match done_reason {
+        Some("length") => FinishReason::Length,
+        _ if has_tool_calls => FinishReason::ToolCalls,
+        _ => FinishReason::Stop,
+    }
+}
+
 /// Parse Ollama timestamp format (RFC3339).
 fn parse_ollama_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
     DateTime::parse_from_rfc3339(timestamp)
@@ -558,8 +983,11 @@ mod tests {
                 role: "assistant".to_string(),
                 content: "Hello!".to_string(),
                 images: None,
+                tool_calls: None,
+                tool_name: None,
             },
             done: true,
+            done_reason: Some("stop".to_string()),
             total_duration: Some(1000000),
             load_duration: None,
             prompt_eval_count: Some(5),
@@ -575,5 +1003,27 @@ mod tests {
         assert_eq!(wrapper.usage().unwrap().prompt_tokens, 5);
         assert_eq!(wrapper.usage().unwrap().completion_tokens, 3);
         assert_eq!(wrapper.metadata().request_id, Some("test-123".to_string()));
+        assert_eq!(wrapper.finish_reason(), Some(FinishReason::Stop));
+    }
+
+    #[test]
+    fn test_truncated_responses_finish_with_length() {
+        let chat: OllamaChatResponse = serde_json::from_str(
+            r#"{"model":"llama3.2","created_at":"2024-01-01T00:00:01Z","message":{"role":"assistant","content":"Once upon"},"done":true,"done_reason":"length"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            ChatResponse::finish_reason(&chat),
+            Some(FinishReason::Length)
+        );
+
+        let completion: OllamaCompletionResponse = serde_json::from_str(
+            r#"{"model":"llama3.2","created_at":"2024-01-01T00:00:01Z","response":"Once upon","done":true,"done_reason":"length"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            CompletionResponse::finish_reason(&completion),
+            Some(FinishReason::Length)
+        );
     }
 }
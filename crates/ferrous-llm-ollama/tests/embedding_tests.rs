@@ -0,0 +1,103 @@
+//! Batch embedding tests for the Ollama provider against a local mock server.
+
+use ferrous_llm_core::EmbeddingProvider;
+use ferrous_llm_ollama::{OllamaConfig, OllamaProvider};
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// Embeds each input as `[length, first byte]`, so results can be traced back.
+struct EchoEmbeddings;
+
+impl Respond for EchoEmbeddings {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: Value = serde_json::from_slice(&request.body).unwrap();
+        let embeddings: Vec<Vec<f32>> = body["input"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|input| {
+                let text = input.as_str().unwrap();
+                vec![text.len() as f32, text.as_bytes()[0] as f32]
+            })
+            .collect();
+        ResponseTemplate::new(200).set_body_json(json!({
+            "model": body["model"],
+            "embeddings": embeddings
+        }))
+    }
+}
+
+fn provider(server: &MockServer, batch_size: usize) -> OllamaProvider {
+    let config = OllamaConfig::builder()
+        .base_url(server.uri())
+        .unwrap()
+        .embedding_model("nomic-embed-text")
+        .embedding_batch_size(batch_size)
+        .embedding_concurrency(2)
+        .truncate(false)
+        .build();
+    OllamaProvider::new(config).unwrap()
+}
+
+#[tokio::test]
+async fn test_embed_batches_inputs_in_order() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/embed"))
+        .and(|request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            body["model"] == "nomic-embed-text"
+                && body["truncate"] == false
+                && body["input"]
+                    .as_array()
+                    .is_some_and(|input| input.len() <= 2)
+        })
+        .respond_with(EchoEmbeddings)
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let texts: Vec<String> = ["a", "bb", "ccc", "dddd", "eeeee"]
+        .iter()
+        .map(|text| text.to_string())
+        .collect();
+    let embeddings = provider(&server, 2).embed(&texts).await.unwrap();
+
+    assert_eq!(embeddings.len(), 5);
+    for (i, embedding) in embeddings.iter().enumerate() {
+        assert_eq!(embedding.index, i);
+        assert_eq!(embedding.embedding[0], texts[i].len() as f32);
+        assert_eq!(embedding.embedding[1], texts[i].as_bytes()[0] as f32);
+    }
+}
+
+#[tokio::test]
+async fn test_embed_empty_input_sends_nothing() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(EchoEmbeddings)
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let embeddings = provider(&server, 2).embed(&[]).await.unwrap();
+    assert!(embeddings.is_empty());
+}
+
+#[tokio::test]
+async fn test_embed_rejects_missing_embeddings() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/embed"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "model": "nomic-embed-text",
+            "embeddings": [[0.1, 0.2]]
+        })))
+        .mount(&server)
+        .await;
+
+    let texts = vec!["one".to_string(), "two".to_string()];
+    let result = provider(&server, 8).embed(&texts).await;
+    assert!(result.is_err());
+}
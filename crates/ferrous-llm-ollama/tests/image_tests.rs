@@ -0,0 +1,123 @@
+//! Image input tests for the Ollama provider against a local mock server.
+
+use ferrous_llm_core::{ChatProvider, ChatRequest, ContentPart, Message, MessageContent, Role};
+use ferrous_llm_ollama::{OllamaConfig, OllamaError, OllamaProvider};
+use serde_json::json;
+use wiremock::matchers::{body_partial_json, header, header_exists, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const PNG: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0];
+
+fn provider(server: &MockServer) -> OllamaProvider {
+    let mut config = OllamaConfig::new("llava");
+    config.base_url = Some(server.uri().parse().unwrap());
+    config.http.max_retries = 0;
+    OllamaProvider::new(config).unwrap()
+}
+
+fn image_request(url: String) -> ChatRequest {
+    ChatRequest::builder()
+        .message(Message {
+            role: Role::User,
+            content: MessageContent::Multimodal(vec![
+                ContentPart::text("What is in this image?"),
+                ContentPart::image_url(url),
+            ]),
+        })
+        .build()
+}
+
+#[tokio::test]
+async fn test_remote_image_is_inlined() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/cat.png"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(PNG, "application/octet-stream"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/chat"))
+        .and(body_partial_json(json!({
+            "messages": [{"images": ["iVBORw0KGgoAAA=="]}]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "model": "llava",
+            "created_at": "2024-01-01T00:00:00Z",
+            "message": {"role": "assistant", "content": "A cat"},
+            "done": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let request = image_request(format!("{}/cat.png", server.uri()));
+    provider(&server).chat(request).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_custom_headers_are_not_sent_to_image_host() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/cat.png"))
+        .and(header_exists("x-ollama-token"))
+        .respond_with(ResponseTemplate::new(403))
+        .expect(0)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/cat.png"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(PNG, "application/octet-stream"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/chat"))
+        .and(header("x-ollama-token", "secret"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "model": "llava",
+            "created_at": "2024-01-01T00:00:00Z",
+            "message": {"role": "assistant", "content": "A cat"},
+            "done": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut config = OllamaConfig::new("llava");
+    config.base_url = Some(server.uri().parse().unwrap());
+    config.http.max_retries = 0;
+    config
+        .http
+        .headers
+        .insert("x-ollama-token".to_string(), "secret".to_string());
+    let provider = OllamaProvider::new(config).unwrap();
+
+    let request = image_request(format!("{}/cat.png", server.uri()));
+    provider.chat(request).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_remote_non_image_is_rejected() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/page"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw("<html></html>", "text/html"))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/chat"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let request = image_request(format!("{}/page", server.uri()));
+    let error = provider(&server).chat(request).await.unwrap_err();
+    assert!(matches!(error, OllamaError::InvalidRequest { .. }));
+    assert!(
+        error
+            .to_string()
+            .contains("Unsupported image type: text/html")
+    );
+}
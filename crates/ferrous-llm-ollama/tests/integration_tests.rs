@@ -131,10 +131,12 @@ async fn test_streaming() {
 
             while let Some(chunk_result) = stream.next().await {
                 match chunk_result {
-                    Ok(chunk) => {
-                        content.push_str(&chunk);
-                        chunk_count += 1;
-                        println!("Chunk {}: {}", chunk_count, chunk);
+                    Ok(event) => {
+                        if let Some(text) = event.text() {
+                            content.push_str(text);
+                            chunk_count += 1;
+                            println!("Chunk {}: {}", chunk_count, text);
+                        }
                     }
                     Err(e) => {
                         println!("Stream error: {:?}", e);
@@ -0,0 +1,445 @@
+//! Model management tests for the Ollama provider against a local mock server.
+
+use ferrous_llm_core::ModelCatalog;
+use ferrous_llm_ollama::{
+    OllamaConfig, OllamaCreateRequest, OllamaError, OllamaManager, OllamaProvider,
+};
+use futures::StreamExt;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+fn config(server: &MockServer) -> OllamaConfig {
+    OllamaConfig::builder()
+        .base_url(server.uri())
+        .unwrap()
+        .max_retries(0)
+        .build()
+}
+
+fn manager(server: &MockServer) -> OllamaManager {
+    OllamaManager::new(config(server)).unwrap()
+}
+
+fn details() -> Value {
+    json!({
+        "parent_model": "",
+        "format": "gguf",
+        "family": "llama",
+        "families": ["llama"],
+        "parameter_size": "8.0B",
+        "quantization_level": "Q4_K_M"
+    })
+}
+
+#[tokio::test]
+async fn test_list_models() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/tags"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "models": [{
+                "name": "llama3.1:latest",
+                "model": "llama3.1:latest",
+                "modified_at": "2024-07-25T14:38:31.83753-07:00",
+                "size": 4661224676u64,
+                "digest": "42182419e950",
+                "details": details()
+            }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let models = manager(&server).list_models().await.unwrap();
+    assert_eq!(models.len(), 1);
+    assert_eq!(models[0].name, "llama3.1:latest");
+    assert_eq!(models[0].size, 4661224676);
+    assert!(models[0].modified_at.is_some());
+    assert_eq!(models[0].details.parameter_size.as_deref(), Some("8.0B"));
+    assert_eq!(
+        models[0].details.quantization_level.as_deref(),
+        Some("Q4_K_M")
+    );
+}
+
+#[tokio::test]
+async fn test_running_models() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/ps"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "models": [{
+                "name": "llama3.1:latest",
+                "model": "llama3.1:latest",
+                "size": 6654289920u64,
+                "digest": "42182419e950",
+                "details": details(),
+                "expires_at": "2024-07-25T14:43:31.83753-07:00",
+                "size_vram": 6654289920u64,
+                "context_length": 4096
+            }]
+        })))
+        .mount(&server)
+        .await;
+
+    let models = manager(&server).running_models().await.unwrap();
+    assert_eq!(models[0].size_vram, 6654289920);
+    assert_eq!(models[0].context_length, Some(4096));
+    assert!(models[0].expires_at.is_some());
+}
+
+#[tokio::test]
+async fn test_show_model() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/show"))
+        .and(body_json(json!({"model": "llama3.1"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "modelfile": "FROM llama3.1",
+            "parameters": "stop \"<|eot_id|>\"",
+            "template": "{{ .Prompt }}",
+            "details": details(),
+            "model_info": {
+                "general.architecture": "llama",
+                "general.parameter_count": 8030261248u64,
+                "llama.context_length": 131072
+            },
+            "capabilities": ["completion", "tools"],
+            "modified_at": "2024-07-25T14:38:31.83753-07:00"
+        })))
+        .mount(&server)
+        .await;
+
+    let info = manager(&server).show_model("llama3.1").await.unwrap();
+    assert_eq!(info.family(), Some("llama"));
+    assert_eq!(info.parameter_size(), Some("8.0B"));
+    assert_eq!(info.quantization_level(), Some("Q4_K_M"));
+    assert_eq!(info.context_length(), Some(131072));
+    assert!(info.has_capability("tools"));
+}
+
+#[tokio::test]
+async fn test_show_unknown_model() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/show"))
+        .respond_with(
+            ResponseTemplate::new(404).set_body_json(json!({"error": "model 'nope' not found"})),
+        )
+        .mount(&server)
+        .await;
+
+    let result = manager(&server).show_model("nope").await;
+    assert!(matches!(result, Err(OllamaError::ModelNotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_pull_model_streams_progress() {
+    let server = MockServer::start().await;
+    let body = [
+        r#"{"status":"pulling manifest"}"#,
+        r#"{"status":"pulling 6a0746a1ec1a","digest":"sha256:6a07","total":100,"completed":50}"#,
+        r#"{"status":"pulling 6a0746a1ec1a","digest":"sha256:6a07","total":100,"completed":100}"#,
+        r#"{"status":"verifying sha256 digest"}"#,
+        r#"{"status":"success"}"#,
+    ]
+    .join("\n");
+    Mock::given(method("POST"))
+        .and(path("/api/pull"))
+        .and(body_json(json!({"model": "llama3.1", "stream": true})))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/x-ndjson"))
+        .mount(&server)
+        .await;
+
+    let events: Vec<_> = manager(&server)
+        .pull_model("llama3.1")
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    let events: Vec<_> = events.into_iter().map(Result::unwrap).collect();
+
+    assert_eq!(events.len(), 5);
+    assert_eq!(events[0].status, "pulling manifest");
+    assert_eq!(events[1].fraction(), Some(0.5));
+    assert!(events[4].is_success());
+}
+
+#[tokio::test]
+async fn test_pull_model_reports_stream_error() {
+    let server = MockServer::start().await;
+    let body = "{\"status\":\"pulling manifest\"}\n\
+                {\"error\":\"pull model manifest: file does not exist\"}\n";
+    Mock::given(method("POST"))
+        .and(path("/api/pull"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/x-ndjson"))
+        .mount(&server)
+        .await;
+
+    let events: Vec<_> = manager(&server)
+        .pull_model("missing")
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert_eq!(events.len(), 2);
+    assert!(events[0].is_ok());
+    let error = events[1].as_ref().unwrap_err();
+    assert!(error.to_string().contains("file does not exist"));
+}
+
+#[tokio::test]
+async fn test_pull_model_reports_early_end() {
+    let server = MockServer::start().await;
+    let body = "{\"status\":\"pulling manifest\"}\n\
+                {\"status\":\"pulling 6a0746a1ec1a\",\"total\":100,\"completed\":50}\n";
+    Mock::given(method("POST"))
+        .and(path("/api/pull"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/x-ndjson"))
+        .mount(&server)
+        .await;
+
+    let events: Vec<_> = manager(&server)
+        .pull_model("llama3.1")
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert_eq!(events.len(), 3);
+    assert!(events[1].is_ok());
+    let error = events[2].as_ref().unwrap_err();
+    assert!(matches!(error, OllamaError::Other { .. }));
+    assert!(error.to_string().contains("ended before success"));
+}
+
+#[tokio::test]
+async fn test_pull_model_outlasts_request_timeout() {
+    // Wiremock can only delay the whole response, so serve a pull whose
+    // progress events trickle in over longer than `http.timeout`.
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = [0u8; 1024];
+        let _ = socket.read(&mut request).await.unwrap();
+        socket
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-type: application/x-ndjson\r\nconnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        for completed in [25, 50, 75, 100] {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            let line = format!(
+                "{{\"status\":\"pulling 6a07\",\"total\":100,\"completed\":{completed}}}\n"
+            );
+            socket.write_all(line.as_bytes()).await.unwrap();
+        }
+        socket
+            .write_all(b"{\"status\":\"success\"}\n")
+            .await
+            .unwrap();
+    });
+
+    let config = OllamaConfig::builder()
+        .base_url(format!("http://{address}"))
+        .unwrap()
+        .max_retries(0)
+        .timeout(Duration::from_millis(500))
+        .build();
+    let events: Vec<_> = OllamaManager::new(config)
+        .unwrap()
+        .pull_model("llama3.1")
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    let events: Vec<_> = events.into_iter().map(Result::unwrap).collect();
+
+    assert_eq!(events.len(), 5);
+    assert_eq!(events[3].fraction(), Some(1.0));
+    assert!(events[4].is_success());
+}
+
+#[tokio::test]
+async fn test_create_model() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/create"))
+        .and(body_json(json!({
+            "model": "mario",
+            "from": "llama3.1",
+            "system": "You are Mario from Super Mario Bros.",
+            "parameters": {"temperature": 0.2},
+            "stream": true
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            "{\"status\":\"using existing layer sha256:6a07\"}\n{\"status\":\"success\"}\n",
+            "application/x-ndjson",
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let request = OllamaCreateRequest::new("mario")
+        .from("llama3.1")
+        .system("You are Mario from Super Mario Bros.")
+        .parameters(json!({"temperature": 0.2}));
+    let events: Vec<_> = manager(&server)
+        .create_model(request)
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert_eq!(events.len(), 2);
+    assert!(events[1].as_ref().unwrap().is_success());
+}
+
+#[tokio::test]
+async fn test_copy_and_delete_model() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/copy"))
+        .and(body_json(
+            json!({"source": "llama3.1", "destination": "llama3.1-backup"}),
+        ))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/delete"))
+        .and(|request: &Request| {
+            serde_json::from_slice::<Value>(&request.body).unwrap()["model"] == "llama3.1-backup"
+        })
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // A manager from a provider talks to the same server
+    let manager = OllamaProvider::new(config(&server)).unwrap().manager();
+    manager
+        .copy_model("llama3.1", "llama3.1-backup")
+        .await
+        .unwrap();
+    manager.delete_model("llama3.1-backup").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_delete_unknown_model() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/delete"))
+        .respond_with(
+            ResponseTemplate::new(404).set_body_json(json!({"error": "model 'nope' not found"})),
+        )
+        .mount(&server)
+        .await;
+
+    let result = manager(&server).delete_model("nope").await;
+    assert!(matches!(result, Err(OllamaError::ModelNotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_model_catalog() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/tags"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "models": [
+                {"name": "llava:latest", "model": "llava:latest", "size": 4733363377u64, "digest": "8dd30f6b0cb1", "details": details()},
+                {"name": "nomic-embed-text:latest", "model": "nomic-embed-text:latest", "size": 274302450, "digest": "0a109f422b47", "details": {"family": "nomic-bert"}}
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/show"))
+        .and(body_json(json!({"model": "llava:latest"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "details": details(),
+            "model_info": {"general.architecture": "llama", "llama.context_length": 32768},
+            "capabilities": ["completion", "vision"]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/show"))
+        .and(body_json(json!({"model": "nomic-embed-text:latest"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "details": {"family": "nomic-bert"},
+            "model_info": {"general.architecture": "nomic-bert", "nomic-bert.context_length": 2048},
+            "capabilities": ["embedding"]
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = OllamaProvider::new(config(&server)).unwrap();
+    let models = ModelCatalog::list_models(&provider).await.unwrap();
+    assert_eq!(models.len(), 2);
+
+    let llava = &models[0];
+    assert_eq!(llava.id, "llava:latest");
+    assert_eq!(llava.provider, "ollama");
+    assert_eq!(llava.context_window, Some(32768));
+    assert!(llava.supports_vision && llava.supports_streaming && !llava.supports_tools);
+    assert_eq!(llava.metadata["quantization_level"], "Q4_K_M");
+    assert_eq!(llava.metadata["size"], 4733363377u64);
+
+    let embedder = &models[1];
+    assert_eq!(embedder.context_window, Some(2048));
+    assert!(!embedder.supports_streaming && !embedder.supports_json_mode);
+    assert_eq!(embedder.pricing, None);
+}
+
+#[tokio::test]
+async fn test_model_catalog_keeps_models_that_cant_be_shown() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/tags"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "models": [
+                {"name": "llava:latest", "model": "llava:latest", "size": 4733363377u64, "digest": "8dd30f6b0cb1", "details": details()},
+                {"name": "deleted:latest", "model": "deleted:latest", "size": 1024, "digest": "0a109f422b47", "details": details()}
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/show"))
+        .and(body_json(json!({"model": "llava:latest"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "details": details(),
+            "model_info": {"general.architecture": "llama", "llama.context_length": 32768},
+            "capabilities": ["completion", "vision"]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/show"))
+        .and(body_json(json!({"model": "deleted:latest"})))
+        .respond_with(
+            ResponseTemplate::new(404)
+                .set_body_json(json!({"error": "model 'deleted:latest' not found"})),
+        )
+        .mount(&server)
+        .await;
+
+    let provider = OllamaProvider::new(config(&server)).unwrap();
+    let models = ModelCatalog::list_models(&provider).await.unwrap();
+    assert_eq!(models.len(), 2);
+    assert_eq!(models[0].context_window, Some(32768));
+
+    let deleted = &models[1];
+    assert_eq!(deleted.id, "deleted:latest");
+    assert_eq!(deleted.context_window, None);
+    assert_eq!(deleted.metadata["family"], "llama");
+    assert_eq!(deleted.metadata["quantization_level"], "Q4_K_M");
+    assert_eq!(deleted.metadata["size"], 1024);
+}
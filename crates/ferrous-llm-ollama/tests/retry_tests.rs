@@ -0,0 +1,58 @@
+//! Retry behaviour tests for the Ollama provider against a local mock server.
+
+use ferrous_llm_core::{ChatProvider, ChatRequest, ChatResponse, EmbeddingProvider};
+use ferrous_llm_ollama::{OllamaConfig, OllamaProvider};
+use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn provider(server: &MockServer, max_retries: u32) -> OllamaProvider {
+    let mut config = OllamaConfig::new("llama3.2");
+    config.base_url = Some(server.uri().parse().unwrap());
+    config.http.max_retries = max_retries;
+    config.http.retry_delay = Duration::from_millis(1);
+    config.http.max_retry_delay = Duration::from_millis(50);
+    OllamaProvider::new(config).unwrap()
+}
+
+#[tokio::test]
+async fn test_chat_retries_service_unavailable() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/chat"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/chat"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "model": "llama3.2",
+            "created_at": "2024-01-01T00:00:00Z",
+            "message": {"role": "assistant", "content": "Hi there"},
+            "done": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let request = ChatRequest::builder().user_message("Hello").build();
+    let response = provider(&server, 2).chat(request).await.unwrap();
+    assert_eq!(response.content(), "Hi there");
+}
+
+#[tokio::test]
+async fn test_embed_does_not_retry_invalid_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/embed"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({"error": "bad input"})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = provider(&server, 3).embed(&["hello".to_string()]).await;
+    assert!(result.is_err());
+}
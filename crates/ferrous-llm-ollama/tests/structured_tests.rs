@@ -0,0 +1,60 @@
+//! Structured output tests for the Ollama provider against a local mock server.
+
+use ferrous_llm_core::{ChatRequest, ResponseSchema, StructuredOutput};
+use ferrous_llm_ollama::{OllamaConfig, OllamaProvider};
+use serde::Deserialize;
+use serde_json::json;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[derive(Debug, Deserialize)]
+struct Weather {
+    city: String,
+    celsius: f32,
+}
+
+#[tokio::test]
+async fn test_structured_output_sets_format_schema() {
+    let server = MockServer::start().await;
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "city": {"type": "string"},
+            "celsius": {"type": "number"}
+        },
+        "required": ["city", "celsius"]
+    });
+
+    Mock::given(method("POST"))
+        .and(path("/api/chat"))
+        .and(body_partial_json(
+            json!({"format": schema, "stream": false}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "model": "llama3.2",
+            "created_at": "2024-01-01T00:00:00Z",
+            "message": {
+                "role": "assistant",
+                "content": "{\"city\": \"Oslo\", \"celsius\": -3}"
+            },
+            "done": true,
+            "done_reason": "stop"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut config = OllamaConfig::new("llama3.2");
+    config.base_url = Some(server.uri().parse().unwrap());
+    let provider = OllamaProvider::new(config).unwrap();
+
+    let request = ChatRequest::builder()
+        .user_message("What's the weather in Oslo?")
+        .build();
+    let result = StructuredOutput::new(&provider, ResponseSchema::new("weather", schema))
+        .generate::<Weather>(request)
+        .await
+        .unwrap();
+    assert_eq!(result.value.city, "Oslo");
+    assert_eq!(result.value.celsius, -3.0);
+}
@@ -0,0 +1,143 @@
+//! Tool calling tests for the Ollama provider against a local mock server.
+
+use ferrous_llm_core::{
+    ChatRequest, ChatResponse, FinishReason, Tool, ToolError, ToolProvider, ToolRegistry,
+    ToolRunner,
+};
+use ferrous_llm_ollama::{OllamaConfig, OllamaProvider};
+use serde_json::{Value, json};
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn provider(server: &MockServer) -> OllamaProvider {
+    let mut config = OllamaConfig::new("llama3.2");
+    config.base_url = Some(server.uri().parse().unwrap());
+    OllamaProvider::new(config).unwrap()
+}
+
+fn weather_tool() -> Tool {
+    Tool::function(
+        "get_weather",
+        "Get the current weather for a city",
+        json!({
+            "type": "object",
+            "properties": {"city": {"type": "string"}},
+            "required": ["city"]
+        }),
+    )
+}
+
+#[tokio::test]
+async fn test_tool_runner_round_trip() {
+    let server = MockServer::start().await;
+
+    // Second turn: the tool result is sent back with the tool's name
+    Mock::given(method("POST"))
+        .and(path("/api/chat"))
+        .and(body_partial_json(json!({
+            "messages": [
+                {"role": "user"},
+                {
+                    "role": "assistant",
+                    "tool_calls": [{"function": {"name": "get_weather", "arguments": {"city": "Paris"}}}]
+                },
+                {"role": "tool", "content": "Sunny in Paris", "tool_name": "get_weather"}
+            ]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "model": "llama3.2",
+            "created_at": "2024-01-01T00:00:01Z",
+            "message": {"role": "assistant", "content": "It's sunny in Paris."},
+            "done": true,
+            "done_reason": "stop"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // First turn: the model calls the tool
+    Mock::given(method("POST"))
+        .and(path("/api/chat"))
+        .and(body_partial_json(json!({
+            "tools": [{"type": "function", "function": {"name": "get_weather"}}]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "model": "llama3.2",
+            "created_at": "2024-01-01T00:00:00Z",
+            "message": {
+                "role": "assistant",
+                "content": "",
+                "tool_calls": [{"function": {"name": "get_weather", "arguments": {"city": "Paris"}}}]
+            },
+            "done": true,
+            "done_reason": "stop"
+        })))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let registry = ToolRegistry::new().with_tool(weather_tool(), |arguments: Value| async move {
+        let city = arguments["city"]
+            .as_str()
+            .ok_or(ToolError::new("missing city"))?;
+        Ok(json!(format!("Sunny in {city}")))
+    });
+    let provider = provider(&server);
+
+    let request = ChatRequest::builder()
+        .user_message("What's the weather in Paris?")
+        .build();
+    let run = ToolRunner::new(&provider, &registry)
+        .run(request)
+        .await
+        .unwrap();
+
+    assert_eq!(run.response.content(), "It's sunny in Paris.");
+    assert_eq!(run.steps.len(), 1);
+
+    let tool_call = &run.steps[0].executions[0].tool_call;
+    assert_eq!(tool_call.id, "call_0");
+    assert_eq!(tool_call.function.arguments, r#"{"city":"Paris"}"#);
+    assert_eq!(run.steps[0].executions[0].result_text(), "Sunny in Paris");
+}
+
+#[tokio::test]
+async fn test_tool_call_response() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/chat"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "model": "llama3.2",
+            "created_at": "2024-01-01T00:00:00Z",
+            "message": {
+                "role": "assistant",
+                "content": "",
+                "tool_calls": [
+                    {"id": "call_abc", "function": {"name": "get_weather", "arguments": {"city": "Paris"}}},
+                    {"function": {"name": "get_weather", "arguments": {"city": "Rome"}}}
+                ]
+            },
+            "done": true
+        })))
+        .mount(&server)
+        .await;
+
+    let request = ChatRequest::builder()
+        .user_message("Weather in Paris and Rome?")
+        .build();
+    let response = provider(&server)
+        .chat_with_tools(request, &[weather_tool()])
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        response.finish_reason(),
+        Some(FinishReason::ToolCalls)
+    ));
+    let tool_calls = response.tool_calls().unwrap();
+    assert_eq!(tool_calls.len(), 2);
+    assert_eq!(tool_calls[0].id, "call_abc");
+    assert_eq!(tool_calls[1].id, "call_1");
+    assert_eq!(tool_calls[1].function.arguments, r#"{"city":"Rome"}"#);
+}
@@ -7,6 +7,7 @@ use url::Url;
 
 /// Configuration for the OpenAI provider.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct OpenAIConfig {
     /// OpenAI API key
     pub api_key: SecretString,
@@ -28,6 +29,15 @@ pub struct OpenAIConfig {
 
     /// Embedding model to use (e.g., "text-embedding-ada-002")
     pub embedding_model: Option<String>,
+
+    /// Image model to use (e.g., "dall-e-3", "gpt-image-1"); the API default if unset
+    pub image_model: Option<String>,
+
+    /// Transcription model to use (defaults to "whisper-1")
+    pub transcription_model: Option<String>,
+
+    /// Speech synthesis model to use (defaults to "tts-1")
+    pub speech_model: Option<String>,
 }
 
 impl Default for OpenAIConfig {
@@ -40,6 +50,9 @@ impl Default for OpenAIConfig {
             project: None,
             http: HttpConfig::default(),
             embedding_model: None,
+            image_model: None,
+            transcription_model: None,
+            speech_model: None,
         }
     }
 }
@@ -118,6 +131,16 @@ impl OpenAIConfig {
         format!("{}/images/generations", self.base_url())
     }
 
+    /// Get the image edits endpoint URL.
+    pub fn image_edits_url(&self) -> String {
+        format!("{}/images/edits", self.base_url())
+    }
+
+    /// Get the image variations endpoint URL.
+    pub fn image_variations_url(&self) -> String {
+        format!("{}/images/variations", self.base_url())
+    }
+
     /// Get the audio transcriptions endpoint URL.
     pub fn transcriptions_url(&self) -> String {
         format!("{}/audio/transcriptions", self.base_url())
@@ -128,6 +151,11 @@ impl OpenAIConfig {
         format!("{}/audio/speech", self.base_url())
     }
 
+    /// Get the models endpoint URL.
+    pub fn models_url(&self) -> String {
+        format!("{}/models", self.base_url())
+    }
+
     /// Load configuration from environment variables.
     pub fn from_env() -> Result<Self, ConfigError> {
         use ferrous_llm_core::env;
@@ -151,6 +179,9 @@ impl OpenAIConfig {
             project,
             http: HttpConfig::default(),
             embedding_model: None,
+            image_model: None,
+            transcription_model: None,
+            speech_model: None,
         })
     }
 }
@@ -180,6 +211,24 @@ impl OpenAIConfigBuilder {
         self
     }
 
+    /// Set the image model.
+    pub fn image_model(mut self, image_model: impl Into<String>) -> Self {
+        self.config.image_model = Some(image_model.into());
+        self
+    }
+
+    /// Set the transcription model.
+    pub fn transcription_model(mut self, transcription_model: impl Into<String>) -> Self {
+        self.config.transcription_model = Some(transcription_model.into());
+        self
+    }
+
+    /// Set the speech synthesis model.
+    pub fn speech_model(mut self, speech_model: impl Into<String>) -> Self {
+        self.config.speech_model = Some(speech_model.into());
+        self
+    }
+
     /// Set the base URL.
     pub fn base_url(mut self, base_url: impl Into<String>) -> Result<Self, ConfigError> {
         let url = validation::validate_url(&base_url.into(), "base_url")?;
@@ -270,6 +319,7 @@ mod tests {
             config.embeddings_url(),
             "https://api.openai.com/v1/embeddings"
         );
+        assert_eq!(config.models_url(), "https://api.openai.com/v1/models");
     }
 
     #[test]
@@ -125,6 +125,19 @@ impl ProviderError for OpenAIError {
 }
 
 impl OpenAIError {
+    /// Attach a server-provided retry delay, such as a `Retry-After` header,
+    /// to a rate limit error. Other errors are returned unchanged.
+    pub fn with_retry_after(self, retry_after: Option<Duration>) -> Self {
+        match self {
+            Self::RateLimit {
+                retry_after: existing,
+            } => Self::RateLimit {
+                retry_after: retry_after.or(existing),
+            },
+            other => other,
+        }
+    }
+
     /// Create an error from an HTTP status code and response body.
     pub fn from_response(status: u16, body: &str) -> Self {
         // Try to parse the error response
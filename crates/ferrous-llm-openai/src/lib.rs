@@ -1,24 +1,33 @@
 //! OpenAI provider for the LLM library.
 //!
 //! This crate provides an implementation of the LLM core traits for OpenAI's API,
-//! including support for chat, completion, streaming, embeddings, and tool calling.
+//! including support for chat, completion, streaming, embeddings, tool calling, and
+//! image generation.
 
 pub mod config;
 pub mod error;
 pub mod provider;
+mod stream;
 pub mod types;
 
 // Re-export main types for convenience
 pub use config::OpenAIConfig;
 pub use error::OpenAIError;
-pub use provider::OpenAIProvider;
+pub use provider::{OpenAIAudioStream, OpenAIProvider, OpenAIStream};
 pub use types::{
     OpenAIChatChoice, OpenAIChatRequest, OpenAIChatResponse, OpenAICompletionChoice,
     OpenAICompletionRequest, OpenAICompletionResponse, OpenAIEmbeddingsRequest,
-    OpenAIEmbeddingsResponse, OpenAIMessage, OpenAITool, OpenAIToolCall, OpenAIUsage,
+    OpenAIEmbeddingsResponse, OpenAIImageData, OpenAIImageEditRequest, OpenAIImageFile,
+    OpenAIImageRequest, OpenAIImageResponse, OpenAIImageVariationRequest, OpenAIMessage,
+    OpenAIModel, OpenAIModelList, OpenAISpeechRequest, OpenAISpeechResponse, OpenAITool,
+    OpenAIToolCall, OpenAITranscriptionResponse, OpenAITranscriptionSegment,
+    OpenAITranscriptionWord, OpenAIUsage,
 };
 
 // Re-export core traits
 pub use ferrous_llm_core::{
-    ChatProvider, CompletionProvider, EmbeddingProvider, StreamingProvider, ToolProvider,
+    ChatProvider, CompletionProvider, EmbeddingProvider, ImageProvider, ModelCatalog,
+    ResponseSchema, SpeechToTextProvider, StreamEvent, StreamingProvider,
+    StreamingTextToSpeechProvider, StreamingToolProvider, StructuredProvider, TextToSpeechProvider,
+    ToolProvider,
 };
@@ -1,119 +1,46 @@
 //! OpenAI provider implementation.
 
-use crate::{config::OpenAIConfig, error::OpenAIError, types::*};
+use crate::{config::OpenAIConfig, error::OpenAIError, stream::StreamParser, types::*};
 use async_trait::async_trait;
 use ferrous_llm_core::{
     ChatProvider, ChatRequest, CompletionProvider, CompletionRequest, Embedding, EmbeddingProvider,
-    ProviderResult, StreamingProvider, Tool, ToolProvider,
+    ImageProvider, ImageRequest, ModelCatalog, ModelInfo, Parameters, ProviderResult,
+    ResponseSchema, RetryConfig, SpeechStream, SpeechToTextProvider, SpeechToTextRequest,
+    StreamEvent, StreamingProvider, StreamingTextToSpeechProvider, StreamingToolProvider,
+    StructuredProvider, TextToSpeechProvider, TextToSpeechRequest, Tool, ToolChoice, ToolProvider,
+    parse_retry_after, retry, sniff_image_type,
 };
 use futures::Stream;
-use reqwest::{Client, RequestBuilder};
+use reqwest::multipart::{Form, Part};
+use reqwest::{Client, ClientBuilder, RequestBuilder};
 use serde_json::json;
 use std::pin::Pin;
 use tokio_stream::{StreamExt, wrappers::ReceiverStream};
 
+/// Stream of events returned by [`OpenAIProvider`] streaming requests.
+pub type OpenAIStream = Pin<Box<dyn Stream<Item = Result<StreamEvent, OpenAIError>> + Send>>;
+
+/// Stream of audio chunks returned by [`OpenAIProvider`] speech requests.
+pub type OpenAIAudioStream = Pin<Box<dyn Stream<Item = Result<Vec<u8>, OpenAIError>> + Send>>;
+
 /// OpenAI provider implementation.
 #[derive(Debug, Clone)]
 pub struct OpenAIProvider {
     config: OpenAIConfig,
     client: Client,
+    stream_client: Client,
+    retry: RetryConfig,
 }
 
 impl OpenAIProvider {
     /// Create a new OpenAI provider with the given configuration.
     pub fn new(config: OpenAIConfig) -> Result<Self, OpenAIError> {
-        let mut headers = reqwest::header::HeaderMap::new();
-
-        // Add authorization header
-        let auth_value = format!("Bearer {}", config.api_key.expose_secret());
-        headers.insert(
-            reqwest::header::AUTHORIZATION,
-            auth_value.parse().map_err(|_| OpenAIError::Config {
-                source: ferrous_llm_core::ConfigError::invalid_value(
-                    "api_key",
-                    "Invalid API key format",
-                ),
-            })?,
-        );
-
-        // Add organization header if provided
-        if let Some(ref org) = config.organization {
-            headers.insert(
-                "OpenAI-Organization",
-                org.parse().map_err(|_| OpenAIError::Config {
-                    source: ferrous_llm_core::ConfigError::invalid_value(
-                        "organization",
-                        "Invalid organization format",
-                    ),
-                })?,
-            );
-        }
-
-        // Add project header if provided
-        if let Some(ref project) = config.project {
-            headers.insert(
-                "OpenAI-Project",
-                project.parse().map_err(|_| OpenAIError::Config {
-                    source: ferrous_llm_core::ConfigError::invalid_value(
-                        "project",
-                        "Invalid project format",
-                    ),
-                })?,
-            );
-        }
-
-        // Add user agent
-        if let Some(ref user_agent) = config.http.user_agent {
-            headers.insert(
-                reqwest::header::USER_AGENT,
-                user_agent.parse().map_err(|_| OpenAIError::Config {
-                    source: ferrous_llm_core::ConfigError::invalid_value(
-                        "user_agent",
-                        "Invalid user agent format",
-                    ),
-                })?,
-            );
-        }
-
-        // Add custom headers
-        for (key, value) in &config.http.headers {
-            let header_name: reqwest::header::HeaderName =
-                key.parse().map_err(|_| OpenAIError::Config {
-                    source: ferrous_llm_core::ConfigError::invalid_value(
-                        "headers",
-                        "Invalid header name",
-                    ),
-                })?;
-            let header_value: reqwest::header::HeaderValue =
-                value.parse().map_err(|_| OpenAIError::Config {
-                    source: ferrous_llm_core::ConfigError::invalid_value(
-                        "headers",
-                        "Invalid header value",
-                    ),
-                })?;
-            headers.insert(header_name, header_value);
-        }
-
-        let mut client_builder = Client::builder()
-            .timeout(config.http.timeout)
-            .default_headers(headers);
-
-        // Configure compression
-        if !config.http.compression {
-            client_builder = client_builder.no_gzip();
-        }
-
-        // Configure connection pool
-        client_builder = client_builder
-            .pool_max_idle_per_host(config.http.pool.max_idle_connections)
-            .pool_idle_timeout(config.http.pool.idle_timeout)
-            .connect_timeout(config.http.pool.connect_timeout);
-
-        let client = client_builder
-            .build()
-            .map_err(|e| OpenAIError::Network { source: e })?;
-
-        Ok(Self { config, client })
+        Ok(Self {
+            client: build_client(&config)?,
+            stream_client: build_stream_client(&config)?,
+            retry: RetryConfig::from(&config.http),
+            config,
+        })
     }
 
     /// Create a request builder with common settings.
@@ -134,11 +61,133 @@ impl OpenAIProvider {
                 .await
                 .map_err(|e| OpenAIError::Network { source: e })
         } else {
-            let body = response.text().await.unwrap_or_default();
-            Err(OpenAIError::from_response(status.as_u16(), &body))
+            Err(Self::error_from_response(response).await)
         }
     }
 
+    /// Convert an unsuccessful HTTP response into an error.
+    async fn error_from_response(response: reqwest::Response) -> OpenAIError {
+        let status = response.status().as_u16();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_retry_after);
+        let body = response.text().await.unwrap_or_default();
+        OpenAIError::from_response(status, &body).with_retry_after(retry_after)
+    }
+
+    /// POST a JSON body and decode the response, retrying transient failures.
+    async fn post_json<B, T>(&self, url: &str, body: &B) -> Result<T, OpenAIError>
+    where
+        B: serde::Serialize + ?Sized + Sync,
+        T: serde::de::DeserializeOwned,
+    {
+        retry(&self.retry, || async move {
+            let response = self
+                .request_builder(reqwest::Method::POST, url)
+                .json(body)
+                .send()
+                .await
+                .map_err(|e| OpenAIError::Network { source: e })?;
+
+            self.handle_response(response).await
+        })
+        .await
+    }
+
+    /// GET a JSON response, retrying transient failures.
+    async fn get_json<T>(&self, url: &str) -> Result<T, OpenAIError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        retry(&self.retry, || async move {
+            let response = self
+                .request_builder(reqwest::Method::GET, url)
+                .send()
+                .await
+                .map_err(|e| OpenAIError::Network { source: e })?;
+
+            self.handle_response(response).await
+        })
+        .await
+    }
+
+    /// POST a JSON body for a streaming or binary response, retrying transient
+    /// failures until the connection is established.
+    async fn post_stream<B>(
+        &self,
+        client: &Client,
+        url: &str,
+        body: &B,
+    ) -> Result<reqwest::Response, OpenAIError>
+    where
+        B: serde::Serialize + ?Sized + Sync,
+    {
+        retry(&self.retry, || async move {
+            let response = client
+                .post(url)
+                .json(body)
+                .send()
+                .await
+                .map_err(|e| OpenAIError::Network { source: e })?;
+
+            if response.status().is_success() {
+                Ok(response)
+            } else {
+                Err(Self::error_from_response(response).await)
+            }
+        })
+        .await
+    }
+
+    /// POST a multipart form and decode the response, retrying transient failures.
+    ///
+    /// Forms can't be reused, so `form` builds a fresh one for each attempt.
+    async fn post_multipart<F, T>(&self, url: &str, form: F) -> Result<T, OpenAIError>
+    where
+        F: Fn() -> Result<Form, OpenAIError> + Sync,
+        T: serde::de::DeserializeOwned,
+    {
+        let form = &form;
+        retry(&self.retry, || async move {
+            let response = self.send_multipart(url, form).await?;
+            self.handle_response(response).await
+        })
+        .await
+    }
+
+    /// POST a multipart form and return the text body, retrying transient failures.
+    async fn post_multipart_text<F>(&self, url: &str, form: F) -> Result<String, OpenAIError>
+    where
+        F: Fn() -> Result<Form, OpenAIError> + Sync,
+    {
+        let form = &form;
+        retry(&self.retry, || async move {
+            let response = self.send_multipart(url, form).await?;
+            if !response.status().is_success() {
+                return Err(Self::error_from_response(response).await);
+            }
+            response
+                .text()
+                .await
+                .map_err(|e| OpenAIError::Network { source: e })
+        })
+        .await
+    }
+
+    /// Send a single multipart request.
+    async fn send_multipart<F>(&self, url: &str, form: &F) -> Result<reqwest::Response, OpenAIError>
+    where
+        F: Fn() -> Result<Form, OpenAIError> + Sync,
+    {
+        self.request_builder(reqwest::Method::POST, url)
+            .multipart(form()?)
+            .send()
+            .await
+            .map_err(|e| OpenAIError::Network { source: e })
+    }
+
     /// Convert core ChatRequest to OpenAI format.
     fn convert_chat_request(&self, request: &ChatRequest) -> OpenAIChatRequest {
         OpenAIChatRequest {
@@ -151,12 +200,99 @@ impl OpenAIProvider {
             presence_penalty: request.parameters.presence_penalty,
             stop: request.parameters.stop_sequences.clone(),
             stream: Some(false),
+            stream_options: None,
             tools: None, // Will be set by chat_with_tools
             tool_choice: None,
+            parallel_tool_calls: None,
+            response_format: None,
             user: request.metadata.user_id.clone(),
         }
     }
 
+    /// Attach tool definitions and the tool choice to a chat request.
+    fn apply_tools(request: &mut OpenAIChatRequest, tools: &[Tool], parameters: &Parameters) {
+        if !tools.is_empty() {
+            request.tools = Some(tools.iter().map(|t| t.into()).collect());
+            request.tool_choice = Some(match parameters.tool_choice.as_ref() {
+                None | Some(ToolChoice::Auto) => json!("auto"),
+                Some(ToolChoice::None) => json!("none"),
+                Some(ToolChoice::Required) => json!("required"),
+                Some(ToolChoice::Function { name }) => {
+                    json!({"type": "function", "function": {"name": name}})
+                }
+            });
+            request.parallel_tool_calls = parameters.parallel_tool_calls;
+        }
+    }
+
+    /// Send a streaming chat request and parse the SSE response into events.
+    async fn stream_chat(
+        &self,
+        mut openai_request: OpenAIChatRequest,
+    ) -> Result<OpenAIStream, OpenAIError> {
+        openai_request.stream = Some(true);
+        openai_request.stream_options = Some(OpenAIStreamOptions {
+            include_usage: true,
+        });
+
+        let response = self
+            .post_stream(&self.client, &self.config.chat_url(), &openai_request)
+            .await?;
+
+        // Create a tokio channel for streaming
+        let (tx, rx) = tokio::sync::mpsc::channel::<Result<StreamEvent, OpenAIError>>(100);
+
+        // Spawn a task to process the SSE stream
+        let tx_clone = tx.clone();
+        tokio::spawn(async move {
+            let mut byte_stream = response.bytes_stream();
+            let mut buffer = Vec::new();
+            let mut parser = StreamParser::new();
+
+            while let Some(chunk_result) = byte_stream.next().await {
+                match chunk_result {
+                    Ok(chunk) => {
+                        buffer.extend_from_slice(chunk.as_ref());
+
+                        // Process complete lines
+                        let mut start = 0;
+                        while let Some(pos) = buffer[start..].iter().position(|&b| b == b'\n') {
+                            let line_end = start + pos;
+                            let line = String::from_utf8_lossy(&buffer[start..line_end]);
+                            start = line_end + 1;
+
+                            for event in parser.parse_line(&line) {
+                                if tx_clone.send(Ok(event)).await.is_err() {
+                                    // Receiver dropped
+                                    return;
+                                }
+                            }
+
+                            if parser.is_done() {
+                                return;
+                            }
+                        }
+
+                        // Keep remaining bytes in buffer
+                        buffer.drain(0..start);
+                    }
+                    Err(e) => {
+                        let _ = tx_clone.send(Err(OpenAIError::Network { source: e })).await;
+                        return;
+                    }
+                }
+            }
+
+            // Close the channel when done
+            drop(tx_clone);
+        });
+
+        // Convert the receiver to a stream
+        let content_stream = ReceiverStream::new(rx);
+
+        Ok(Box::pin(content_stream))
+    }
+
     /// Convert core CompletionRequest to OpenAI format.
     fn convert_completion_request(&self, request: &CompletionRequest) -> OpenAICompletionRequest {
         OpenAICompletionRequest {
@@ -172,6 +308,271 @@ impl OpenAIProvider {
             user: request.metadata.user_id.clone(),
         }
     }
+
+    /// Convert core ImageRequest to OpenAI format.
+    fn convert_image_request(&self, request: &ImageRequest) -> OpenAIImageRequest {
+        // OpenAI has no negative prompt, so `negative_prompt` is not sent
+        OpenAIImageRequest {
+            model: self.config.image_model.clone(),
+            prompt: request.prompt.clone(),
+            n: request.n,
+            size: request.size.clone(),
+            quality: request.quality.clone(),
+            response_format: request.response_format.clone(),
+            user: request.metadata.user_id.clone(),
+        }
+    }
+
+    /// Build the multipart form for an image edit request.
+    fn image_edit_form(&self, request: &OpenAIImageEditRequest) -> Result<Form, OpenAIError> {
+        let mut form = Form::new()
+            .part("image", image_part(&request.image)?)
+            .text("prompt", request.prompt.clone());
+        if let Some(mask) = &request.mask {
+            form = form.part("mask", image_part(mask)?);
+        }
+
+        let fields = [
+            ("model", self.config.image_model.clone()),
+            ("n", request.n.map(|n| n.to_string())),
+            ("size", request.size.clone()),
+            ("quality", request.quality.clone()),
+            ("response_format", request.response_format.clone()),
+            ("user", request.user.clone()),
+        ];
+        Ok(add_text_fields(form, fields))
+    }
+
+    /// Build the multipart form for an image variation request.
+    fn image_variation_form(
+        &self,
+        request: &OpenAIImageVariationRequest,
+    ) -> Result<Form, OpenAIError> {
+        let form = Form::new().part("image", image_part(&request.image)?);
+
+        let fields = [
+            ("model", self.config.image_model.clone()),
+            ("n", request.n.map(|n| n.to_string())),
+            ("size", request.size.clone()),
+            ("response_format", request.response_format.clone()),
+            ("user", request.user.clone()),
+        ];
+        Ok(add_text_fields(form, fields))
+    }
+
+    /// Build the multipart form for a transcription request.
+    fn transcription_form(&self, request: &SpeechToTextRequest, audio: &[u8]) -> Form {
+        let file_name = request
+            .audio
+            .file_name()
+            .unwrap_or_else(|| format!("audio.{}", request.format));
+        let model = self
+            .config
+            .transcription_model
+            .clone()
+            .unwrap_or_else(|| "whisper-1".to_string());
+        let form = Form::new()
+            .part("file", Part::bytes(audio.to_vec()).file_name(file_name))
+            .text("model", model);
+
+        let fields = [
+            ("language", request.language.clone()),
+            ("response_format", transcription_format(request)),
+            ("user", request.metadata.user_id.clone()),
+        ];
+        request
+            .timestamp_granularities
+            .iter()
+            .flatten()
+            .fold(add_text_fields(form, fields), |form, granularity| {
+                form.text("timestamp_granularities[]", granularity.clone())
+            })
+    }
+
+    /// Convert core TextToSpeechRequest to OpenAI format.
+    fn convert_speech_request(&self, request: &TextToSpeechRequest) -> OpenAISpeechRequest {
+        OpenAISpeechRequest {
+            model: self
+                .config
+                .speech_model
+                .clone()
+                .unwrap_or_else(|| "tts-1".to_string()),
+            input: request.text.clone(),
+            voice: request.voice.clone(),
+            response_format: request.format.clone(),
+            speed: request.speed,
+        }
+    }
+
+    /// Edit an image from a prompt, optionally limited to the transparent
+    /// areas of a mask.
+    pub async fn edit_image(
+        &self,
+        request: OpenAIImageEditRequest,
+    ) -> Result<OpenAIImageResponse, OpenAIError> {
+        self.post_multipart(&self.config.image_edits_url(), || {
+            self.image_edit_form(&request)
+        })
+        .await
+    }
+
+    /// Create variations of an image.
+    pub async fn create_image_variation(
+        &self,
+        request: OpenAIImageVariationRequest,
+    ) -> Result<OpenAIImageResponse, OpenAIError> {
+        self.post_multipart(&self.config.image_variations_url(), || {
+            self.image_variation_form(&request)
+        })
+        .await
+    }
+}
+
+/// Build an HTTP client with the headers, timeouts and pooling from `config`.
+fn build_client(config: &OpenAIConfig) -> Result<Client, OpenAIError> {
+    client_builder(config)?
+        .timeout(config.http.timeout)
+        .build()
+        .map_err(|e| OpenAIError::Network { source: e })
+}
+
+/// Build an HTTP client for long-running streams such as synthesized speech.
+///
+/// `http.timeout` bounds each read instead of the whole request, so a stream
+/// only fails if the server stops sending data.
+fn build_stream_client(config: &OpenAIConfig) -> Result<Client, OpenAIError> {
+    client_builder(config)?
+        .read_timeout(config.http.timeout)
+        .build()
+        .map_err(|e| OpenAIError::Network { source: e })
+}
+
+/// Client builder with the headers, compression and pooling from `config`.
+fn client_builder(config: &OpenAIConfig) -> Result<ClientBuilder, OpenAIError> {
+    let mut headers = reqwest::header::HeaderMap::new();
+
+    // Add authorization header
+    let auth_value = format!("Bearer {}", config.api_key.expose_secret());
+    headers.insert(
+        reqwest::header::AUTHORIZATION,
+        auth_value.parse().map_err(|_| OpenAIError::Config {
+            source: ferrous_llm_core::ConfigError::invalid_value(
+                "api_key",
+                "Invalid API key format",
+            ),
+        })?,
+    );
+
+    // Add organization header if provided
+    if let Some(ref org) = config.organization {
+        headers.insert(
+            "OpenAI-Organization",
+            org.parse().map_err(|_| OpenAIError::Config {
+                source: ferrous_llm_core::ConfigError::invalid_value(
+                    "organization",
+                    "Invalid organization format",
+                ),
+            })?,
+        );
+    }
+
+    // Add project header if provided
+    if let Some(ref project) = config.project {
+        headers.insert(
+            "OpenAI-Project",
+            project.parse().map_err(|_| OpenAIError::Config {
+                source: ferrous_llm_core::ConfigError::invalid_value(
+                    "project",
+                    "Invalid project format",
+                ),
+            })?,
+        );
+    }
+
+    // Add user agent
+    if let Some(ref user_agent) = config.http.user_agent {
+        headers.insert(
+            reqwest::header::USER_AGENT,
+            user_agent.parse().map_err(|_| OpenAIError::Config {
+                source: ferrous_llm_core::ConfigError::invalid_value(
+                    "user_agent",
+                    "Invalid user agent format",
+                ),
+            })?,
+        );
+    }
+
+    // Add custom headers
+    for (key, value) in &config.http.headers {
+        let header_name: reqwest::header::HeaderName =
+            key.parse().map_err(|_| OpenAIError::Config {
+                source: ferrous_llm_core::ConfigError::invalid_value(
+                    "headers",
+                    "Invalid header name",
+                ),
+            })?;
+        let header_value: reqwest::header::HeaderValue =
+            value.parse().map_err(|_| OpenAIError::Config {
+                source: ferrous_llm_core::ConfigError::invalid_value(
+                    "headers",
+                    "Invalid header value",
+                ),
+            })?;
+        headers.insert(header_name, header_value);
+    }
+
+    let mut client_builder = Client::builder().default_headers(headers);
+
+    // Configure compression
+    if !config.http.compression {
+        client_builder = client_builder.no_gzip();
+    }
+
+    // Configure connection pool
+    client_builder = client_builder
+        .pool_max_idle_per_host(config.http.pool.max_idle_connections)
+        .pool_idle_timeout(config.http.pool.idle_timeout)
+        .connect_timeout(config.http.pool.connect_timeout);
+
+    Ok(client_builder)
+}
+
+/// Create a multipart file part for an uploaded image.
+fn image_part(file: &OpenAIImageFile) -> Result<Part, OpenAIError> {
+    let media_type = sniff_image_type(&file.data).unwrap_or("image/png");
+    Part::bytes(file.data.clone())
+        .file_name(file.file_name.clone())
+        .mime_str(media_type)
+        .map_err(|e| OpenAIError::Network { source: e })
+}
+
+/// Response format to request for a transcription.
+///
+/// Timestamps are only returned in `verbose_json`, so it is requested when
+/// granularities are set without an explicit format.
+fn transcription_format(request: &SpeechToTextRequest) -> Option<String> {
+    request.response_format.clone().or_else(|| {
+        request
+            .timestamp_granularities
+            .as_ref()
+            .map(|_| "verbose_json".to_string())
+    })
+}
+
+/// Audio format of a speech response; OpenAI defaults to mp3.
+fn speech_format(request: &TextToSpeechRequest) -> String {
+    request.format.clone().unwrap_or_else(|| "mp3".to_string())
+}
+
+/// Add the optional text fields that are set to a multipart form.
+fn add_text_fields<const N: usize>(
+    form: Form,
+    fields: [(&'static str, Option<String>); N],
+) -> Form {
+    fields
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|value| (name, value)))
+        .fold(form, |form, (name, value)| form.text(name, value))
 }
 
 #[async_trait]
@@ -183,14 +584,8 @@ impl ChatProvider for OpenAIProvider {
     async fn chat(&self, request: ChatRequest) -> ProviderResult<Self::Response, Self::Error> {
         let openai_request = self.convert_chat_request(&request);
 
-        let response = self
-            .request_builder(reqwest::Method::POST, &self.config.chat_url())
-            .json(&openai_request)
-            .send()
+        self.post_json(&self.config.chat_url(), &openai_request)
             .await
-            .map_err(|e| OpenAIError::Network { source: e })?;
-
-        self.handle_response(response).await
     }
 }
 
@@ -206,14 +601,8 @@ impl CompletionProvider for OpenAIProvider {
     ) -> ProviderResult<Self::Response, Self::Error> {
         let openai_request = self.convert_completion_request(&request);
 
-        let response = self
-            .request_builder(reqwest::Method::POST, &self.config.completions_url())
-            .json(&openai_request)
-            .send()
+        self.post_json(&self.config.completions_url(), &openai_request)
             .await
-            .map_err(|e| OpenAIError::Network { source: e })?;
-
-        self.handle_response(response).await
     }
 }
 
@@ -239,14 +628,9 @@ impl EmbeddingProvider for OpenAIProvider {
             user: None,
         };
 
-        let response = self
-            .request_builder(reqwest::Method::POST, &self.config.embeddings_url())
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| OpenAIError::Network { source: e })?;
-
-        let embeddings_response: OpenAIEmbeddingsResponse = self.handle_response(response).await?;
+        let embeddings_response: OpenAIEmbeddingsResponse = self
+            .post_json(&self.config.embeddings_url(), &request)
+            .await?;
 
         let embeddings = embeddings_response
             .data
@@ -262,89 +646,144 @@ impl EmbeddingProvider for OpenAIProvider {
 }
 
 #[async_trait]
-impl StreamingProvider for OpenAIProvider {
-    type StreamItem = String;
-    type Stream = Pin<Box<dyn Stream<Item = Result<Self::StreamItem, Self::Error>> + Send>>;
+impl ModelCatalog for OpenAIProvider {
+    type Error = OpenAIError;
 
-    async fn chat_stream(&self, request: ChatRequest) -> ProviderResult<Self::Stream, Self::Error> {
-        let mut openai_request = self.convert_chat_request(&request);
-        openai_request.stream = Some(true);
+    async fn list_models(&self) -> ProviderResult<Vec<ModelInfo>, Self::Error> {
+        let list: OpenAIModelList = self.get_json(&self.config.models_url()).await?;
+        Ok(list.data.into_iter().map(ModelInfo::from).collect())
+    }
 
-        let response = self
-            .request_builder(reqwest::Method::POST, &self.config.chat_url())
-            .json(&openai_request)
-            .send()
+    async fn model_info(&self, model: &str) -> ProviderResult<ModelInfo, Self::Error> {
+        let url = format!("{}/{model}", self.config.models_url());
+        let model: OpenAIModel = self.get_json(&url).await?;
+        Ok(model.into())
+    }
+}
+
+#[async_trait]
+impl ImageProvider for OpenAIProvider {
+    type Config = OpenAIConfig;
+    type Response = OpenAIImageResponse;
+    type Error = OpenAIError;
+
+    async fn generate_image(
+        &self,
+        request: ImageRequest,
+    ) -> ProviderResult<Self::Response, Self::Error> {
+        let openai_request = self.convert_image_request(&request);
+
+        self.post_json(&self.config.images_url(), &openai_request)
             .await
-            .map_err(|e| OpenAIError::Network { source: e })?;
+    }
+}
 
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let body = response.text().await.unwrap_or_default();
-            return Err(OpenAIError::from_response(status, &body));
-        }
+#[async_trait]
+impl SpeechToTextProvider for OpenAIProvider {
+    type Config = OpenAIConfig;
+    type Response = OpenAITranscriptionResponse;
+    type Error = OpenAIError;
 
-        // Create a tokio channel for streaming
-        let (tx, rx) = tokio::sync::mpsc::channel::<Result<String, OpenAIError>>(100);
+    async fn speech_to_text(
+        &self,
+        request: SpeechToTextRequest,
+    ) -> ProviderResult<Self::Response, Self::Error> {
+        let audio = request
+            .audio
+            .read()
+            .await
+            .map_err(|e| OpenAIError::InvalidRequest {
+                message: format!("Failed to read audio: {e}"),
+            })?;
 
-        // Spawn a task to process the SSE stream
-        let tx_clone = tx.clone();
-        tokio::spawn(async move {
-            let mut byte_stream = response.bytes_stream();
-            let mut buffer = Vec::new();
+        let body = self
+            .post_multipart_text(&self.config.transcriptions_url(), || {
+                Ok(self.transcription_form(&request, &audio))
+            })
+            .await?;
 
-            while let Some(chunk_result) = byte_stream.next().await {
-                match chunk_result {
-                    Ok(chunk) => {
-                        buffer.extend_from_slice(chunk.as_ref());
+        // text, srt and vtt responses are returned as-is rather than as JSON
+        match transcription_format(&request) {
+            Some(format) if !format.ends_with("json") => Ok(OpenAITranscriptionResponse {
+                text: body,
+                ..Default::default()
+            }),
+            _ => Ok(serde_json::from_str(&body)?),
+        }
+    }
+}
 
-                        // Process complete lines
-                        let mut start = 0;
-                        while let Some(pos) = buffer[start..].iter().position(|&b| b == b'\n') {
-                            let line_end = start + pos;
-                            let line = String::from_utf8_lossy(&buffer[start..line_end])
-                                .trim()
-                                .to_string();
-                            start = line_end + 1;
+#[async_trait]
+impl TextToSpeechProvider for OpenAIProvider {
+    type Config = OpenAIConfig;
+    type Response = OpenAISpeechResponse;
+    type Error = OpenAIError;
 
-                            // Process SSE format: "data: {json}" or "data: [DONE]"
-                            if let Some(data) = line.strip_prefix("data: ") {
-                                if data == "[DONE]" {
-                                    // End of stream
-                                    drop(tx_clone);
-                                    return;
-                                }
+    async fn text_to_speech(
+        &self,
+        request: TextToSpeechRequest,
+    ) -> ProviderResult<Self::Response, Self::Error> {
+        let openai_request = self.convert_speech_request(&request);
 
-                                // Try to parse the JSON chunk
-                                if let Ok(chunk) = serde_json::from_str::<OpenAIStreamChunk>(data)
-                                    && let Some(choice) = chunk.choices.first()
-                                    && let Some(content) = &choice.delta.content
-                                    && !content.is_empty()
-                                    && tx_clone.send(Ok(content.clone())).await.is_err()
-                                {
-                                    // Receiver dropped
-                                    return;
-                                }
-                            }
-                        }
+        let response = self
+            .post_stream(&self.client, &self.config.speech_url(), &openai_request)
+            .await?;
+        let audio = response
+            .bytes()
+            .await
+            .map_err(|e| OpenAIError::Network { source: e })?;
 
-                        // Keep remaining bytes in buffer
-                        buffer.drain(0..start);
-                    }
-                    Err(e) => {
-                        let _ = tx_clone.send(Err(OpenAIError::Network { source: e })).await;
-                        return;
-                    }
-                }
-            }
+        Ok(OpenAISpeechResponse {
+            audio: audio.to_vec(),
+            format: speech_format(&request),
+        })
+    }
+}
 
-            // Close the channel when done
-            drop(tx_clone);
+#[async_trait]
+impl StreamingTextToSpeechProvider for OpenAIProvider {
+    type AudioStream = OpenAIAudioStream;
+
+    async fn text_to_speech_stream(
+        &self,
+        request: TextToSpeechRequest,
+    ) -> ProviderResult<SpeechStream<Self::AudioStream>, Self::Error> {
+        let openai_request = self.convert_speech_request(&request);
+
+        let response = self
+            .post_stream(
+                &self.stream_client,
+                &self.config.speech_url(),
+                &openai_request,
+            )
+            .await?;
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let chunks = response.bytes_stream().map(|chunk| {
+            chunk
+                .map(|bytes| bytes.to_vec())
+                .map_err(|e| OpenAIError::Network { source: e })
         });
 
-        // Convert the receiver to a stream
-        let content_stream = ReceiverStream::new(rx);
+        Ok(SpeechStream {
+            format: speech_format(&request),
+            content_type,
+            chunks: Box::pin(chunks),
+        })
+    }
+}
 
-        Ok(Box::pin(content_stream))
+#[async_trait]
+impl StreamingProvider for OpenAIProvider {
+    type StreamItem = StreamEvent;
+    type Stream = OpenAIStream;
+
+    async fn chat_stream(&self, request: ChatRequest) -> ProviderResult<Self::Stream, Self::Error> {
+        let openai_request = self.convert_chat_request(&request);
+        self.stream_chat(openai_request).await
     }
 }
 
@@ -356,20 +795,51 @@ impl ToolProvider for OpenAIProvider {
         tools: &[Tool],
     ) -> ProviderResult<Self::Response, Self::Error> {
         let mut openai_request = self.convert_chat_request(&request);
+        Self::apply_tools(&mut openai_request, tools, &request.parameters);
 
-        if !tools.is_empty() {
-            openai_request.tools = Some(tools.iter().map(|t| t.into()).collect());
-            openai_request.tool_choice = Some(json!("auto"));
+        self.post_json(&self.config.chat_url(), &openai_request)
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamingToolProvider for OpenAIProvider {
+    async fn chat_stream_with_tools(
+        &self,
+        request: ChatRequest,
+        tools: &[Tool],
+    ) -> ProviderResult<Self::Stream, Self::Error> {
+        let mut openai_request = self.convert_chat_request(&request);
+        Self::apply_tools(&mut openai_request, tools, &request.parameters);
+
+        self.stream_chat(openai_request).await
+    }
+}
+
+#[async_trait]
+impl StructuredProvider for OpenAIProvider {
+    async fn chat_structured(
+        &self,
+        request: ChatRequest,
+        schema: &ResponseSchema,
+    ) -> ProviderResult<Self::Response, Self::Error> {
+        let mut json_schema = json!({
+            "name": schema.name,
+            "schema": schema.schema,
+            "strict": schema.strict,
+        });
+        if let Some(description) = &schema.description {
+            json_schema["description"] = json!(description);
         }
 
-        let response = self
-            .request_builder(reqwest::Method::POST, &self.config.chat_url())
-            .json(&openai_request)
-            .send()
-            .await
-            .map_err(|e| OpenAIError::Network { source: e })?;
+        let mut openai_request = self.convert_chat_request(&request);
+        openai_request.response_format = Some(json!({
+            "type": "json_schema",
+            "json_schema": json_schema,
+        }));
 
-        self.handle_response(response).await
+        self.post_json(&self.config.chat_url(), &openai_request)
+            .await
     }
 }
 
@@ -410,4 +880,42 @@ mod tests {
         assert_eq!(openai_request.max_tokens, Some(100));
         assert_eq!(openai_request.messages.len(), 1);
     }
+
+    #[test]
+    fn test_apply_tools_tool_choice() {
+        let provider = OpenAIProvider::new(create_test_config()).unwrap();
+        let tools = [Tool::function(
+            "extract",
+            "Extract fields",
+            json!({"type": "object"}),
+        )];
+
+        let request = ChatRequest::builder().user_message("Hi").build();
+        let mut openai_request = provider.convert_chat_request(&request);
+        OpenAIProvider::apply_tools(&mut openai_request, &tools, &request.parameters);
+        let body = serde_json::to_value(&openai_request).unwrap();
+        assert_eq!(body["tool_choice"], "auto");
+        assert!(body.get("parallel_tool_calls").is_none());
+
+        let request = ChatRequest::builder()
+            .user_message("Hi")
+            .tool_choice(ToolChoice::function("extract"))
+            .parallel_tool_calls(false)
+            .build();
+        let mut openai_request = provider.convert_chat_request(&request);
+        OpenAIProvider::apply_tools(&mut openai_request, &tools, &request.parameters);
+        let body = serde_json::to_value(&openai_request).unwrap();
+        assert_eq!(
+            body["tool_choice"],
+            json!({"type": "function", "function": {"name": "extract"}})
+        );
+        assert_eq!(body["parallel_tool_calls"], false);
+
+        // Without tools there is nothing to choose from
+        let mut openai_request = provider.convert_chat_request(&request);
+        OpenAIProvider::apply_tools(&mut openai_request, &[], &request.parameters);
+        let body = serde_json::to_value(&openai_request).unwrap();
+        assert!(body.get("tool_choice").is_none());
+        assert!(body.get("parallel_tool_calls").is_none());
+    }
 }
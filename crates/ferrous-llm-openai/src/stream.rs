@@ -0,0 +1,377 @@
+//! Parsing of OpenAI server-sent event streams into core stream events.
+
+use crate::error::OpenAIErrorResponse;
+use crate::types::{OpenAIStreamChunk, OpenAIStreamToolCall, map_finish_reason};
+use ferrous_llm_core::{FunctionCall, StreamEvent, ToolCall, Usage};
+use std::collections::BTreeMap;
+
+/// Incremental parser for the OpenAI chat completions SSE format.
+#[derive(Debug, Default)]
+pub(crate) struct StreamParser {
+    started: bool,
+    /// Tool calls in progress, keyed by their index in the response
+    tool_calls: BTreeMap<usize, PendingToolCall>,
+    done: bool,
+}
+
+/// A tool call whose fragments are still arriving.
+#[derive(Debug, Default)]
+struct PendingToolCall {
+    id: String,
+    name: String,
+    arguments: String,
+    started: bool,
+}
+
+impl StreamParser {
+    /// Create a new stream parser.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the terminating `[DONE]` marker has been seen.
+    pub(crate) fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Parse a single SSE line and return the events it produces.
+    pub(crate) fn parse_line(&mut self, line: &str) -> Vec<StreamEvent> {
+        let Some(data) = line.trim().strip_prefix("data:").map(str::trim) else {
+            return Vec::new();
+        };
+
+        if data == "[DONE]" {
+            self.done = true;
+            let mut events = self.finish_tool_calls();
+            events.push(StreamEvent::Done);
+            return events;
+        }
+
+        match serde_json::from_str::<OpenAIStreamChunk>(data) {
+            Ok(chunk) => self.parse_chunk(chunk),
+            Err(_) => match serde_json::from_str::<OpenAIErrorResponse>(data) {
+                Ok(error) => vec![StreamEvent::Error {
+                    message: error.error.message,
+                }],
+                Err(_) => Vec::new(),
+            },
+        }
+    }
+
+    fn parse_chunk(&mut self, chunk: OpenAIStreamChunk) -> Vec<StreamEvent> {
+        let mut events = Vec::new();
+
+        if !self.started {
+            self.started = true;
+            events.push(StreamEvent::MessageStart {
+                id: Some(chunk.id.clone()),
+                model: Some(chunk.model.clone()),
+            });
+        }
+
+        if let Some(choice) = chunk.choices.into_iter().next() {
+            if let Some(content) = choice.delta.content
+                && !content.is_empty()
+            {
+                events.push(StreamEvent::TextDelta { text: content });
+            }
+
+            for fragment in choice.delta.tool_calls.unwrap_or_default() {
+                self.push_tool_call_fragment(fragment, &mut events);
+            }
+
+            if let Some(reason) = choice.finish_reason.as_deref().and_then(map_finish_reason) {
+                events.extend(self.finish_tool_calls());
+                events.push(StreamEvent::Finish { reason });
+            }
+        }
+
+        if let Some(usage) = chunk.usage {
+            events.push(StreamEvent::Usage {
+                usage: Usage {
+                    prompt_tokens: usage.prompt_tokens,
+                    completion_tokens: usage.completion_tokens,
+                    total_tokens: usage.total_tokens,
+                },
+            });
+        }
+
+        events
+    }
+
+    /// Merge a tool call fragment into the pending call at the same index.
+    fn push_tool_call_fragment(
+        &mut self,
+        fragment: OpenAIStreamToolCall,
+        events: &mut Vec<StreamEvent>,
+    ) {
+        let index = fragment.index as usize;
+        let call = self.tool_calls.entry(index).or_default();
+
+        if let Some(id) = fragment.id {
+            call.id = id;
+        }
+        let arguments = match fragment.function {
+            Some(function) => {
+                if let Some(name) = function.name {
+                    call.name.push_str(&name);
+                }
+                function.arguments.unwrap_or_default()
+            }
+            None => String::new(),
+        };
+
+        call.arguments.push_str(&arguments);
+        if call.started {
+            if !arguments.is_empty() {
+                events.push(StreamEvent::ToolCallDelta { index, arguments });
+            }
+            return;
+        }
+
+        // Hold back arguments until the call's id and name are known
+        if !call.id.is_empty() && !call.name.is_empty() {
+            call.started = true;
+            events.push(StreamEvent::ToolCallStart {
+                index,
+                id: call.id.clone(),
+                name: call.name.clone(),
+            });
+            if !call.arguments.is_empty() {
+                events.push(StreamEvent::ToolCallDelta {
+                    index,
+                    arguments: call.arguments.clone(),
+                });
+            }
+        }
+    }
+
+    /// Complete all pending tool calls.
+    ///
+    /// Calls whose id or name never arrived are started here, so every end
+    /// follows a start.
+    fn finish_tool_calls(&mut self) -> Vec<StreamEvent> {
+        let mut events = Vec::new();
+        for (index, call) in std::mem::take(&mut self.tool_calls) {
+            if !call.started {
+                events.push(StreamEvent::ToolCallStart {
+                    index,
+                    id: call.id.clone(),
+                    name: call.name.clone(),
+                });
+            }
+            events.push(StreamEvent::ToolCallEnd {
+                index,
+                tool_call: ToolCall {
+                    id: call.id,
+                    call_type: "function".to_string(),
+                    function: FunctionCall {
+                        name: call.name,
+                        arguments: call.arguments,
+                    },
+                },
+            });
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ferrous_llm_core::FinishReason;
+
+    fn chunk(delta: &str, finish_reason: Option<&str>) -> String {
+        let finish_reason = finish_reason
+            .map(|r| format!("\"{r}\""))
+            .unwrap_or_else(|| "null".to_string());
+        format!(
+            r#"data: {{"id":"chatcmpl-1","object":"chat.completion.chunk","created":1,"model":"gpt-4o","choices":[{{"index":0,"delta":{delta},"finish_reason":{finish_reason}}}]}}"#
+        )
+    }
+
+    #[test]
+    fn test_text_stream() {
+        let mut parser = StreamParser::new();
+
+        let events = parser.parse_line(&chunk(r#"{"role":"assistant","content":""}"#, None));
+        assert!(matches!(
+            events.as_slice(),
+            [StreamEvent::MessageStart { id: Some(id), model: Some(model) }]
+                if id == "chatcmpl-1" && model == "gpt-4o"
+        ));
+
+        let events = parser.parse_line(&chunk(r#"{"content":"Hello"}"#, None));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].text(), Some("Hello"));
+
+        let events = parser.parse_line(&chunk("{}", Some("stop")));
+        assert!(matches!(
+            events.as_slice(),
+            [StreamEvent::Finish {
+                reason: FinishReason::Stop
+            }]
+        ));
+
+        let events = parser.parse_line("data: [DONE]");
+        assert!(events[0].is_done());
+        assert!(parser.is_done());
+    }
+
+    #[test]
+    fn test_tool_call_stream() {
+        let mut parser = StreamParser::new();
+        let lines = [
+            chunk(
+                r#"{"role":"assistant","content":null,"tool_calls":[{"index":0,"id":"call_a","type":"function","function":{"name":"get_weather","arguments":""}}]}"#,
+                None,
+            ),
+            chunk(
+                r#"{"tool_calls":[{"index":0,"function":{"arguments":"{\"city\":"}}]}"#,
+                None,
+            ),
+            chunk(
+                r#"{"tool_calls":[{"index":0,"function":{"arguments":"\"Paris\"}"}}]}"#,
+                None,
+            ),
+            chunk(
+                r#"{"tool_calls":[{"index":1,"id":"call_b","type":"function","function":{"name":"get_time","arguments":"{}"}}]}"#,
+                None,
+            ),
+            chunk("{}", Some("tool_calls")),
+        ];
+        let events: Vec<_> = lines
+            .iter()
+            .flat_map(|line| parser.parse_line(line))
+            .collect();
+
+        assert!(matches!(events[0], StreamEvent::MessageStart { .. }));
+        assert!(matches!(
+            &events[1],
+            StreamEvent::ToolCallStart { index: 0, id, name } if id == "call_a" && name == "get_weather"
+        ));
+        assert!(matches!(
+            &events[2],
+            StreamEvent::ToolCallDelta { index: 0, .. }
+        ));
+        assert!(matches!(
+            &events[3],
+            StreamEvent::ToolCallDelta { index: 0, .. }
+        ));
+        assert!(matches!(
+            &events[4],
+            StreamEvent::ToolCallStart { index: 1, .. }
+        ));
+        assert!(matches!(
+            &events[5],
+            StreamEvent::ToolCallDelta { index: 1, .. }
+        ));
+        match &events[6] {
+            StreamEvent::ToolCallEnd {
+                index: 0,
+                tool_call,
+            } => {
+                assert_eq!(tool_call.id, "call_a");
+                assert_eq!(tool_call.function.arguments, r#"{"city":"Paris"}"#);
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+        assert!(matches!(
+            &events[7],
+            StreamEvent::ToolCallEnd { index: 1, .. }
+        ));
+        assert!(matches!(
+            events[8],
+            StreamEvent::Finish {
+                reason: FinishReason::ToolCalls
+            }
+        ));
+        assert_eq!(events.len(), 9);
+    }
+
+    #[test]
+    fn test_tool_call_arguments_before_name() {
+        let mut parser = StreamParser::new();
+        let early = parser.parse_line(&chunk(
+            r#"{"tool_calls":[{"index":0,"function":{"arguments":"{\"ci"}}]}"#,
+            None,
+        ));
+        assert!(
+            !early
+                .iter()
+                .any(|event| matches!(event, StreamEvent::ToolCallDelta { .. }))
+        );
+
+        let started = parser.parse_line(&chunk(
+            r#"{"tool_calls":[{"index":0,"id":"call_a","function":{"name":"get_weather","arguments":"ty\":"}}]}"#,
+            None,
+        ));
+        assert_eq!(started.len(), 2);
+        assert!(matches!(
+            &started[0],
+            StreamEvent::ToolCallStart { index: 0, id, name } if id == "call_a" && name == "get_weather"
+        ));
+        assert!(matches!(
+            &started[1],
+            StreamEvent::ToolCallDelta { index: 0, arguments } if arguments == r#"{"city":"#
+        ));
+
+        let more = parser.parse_line(&chunk(
+            r#"{"tool_calls":[{"index":0,"function":{"arguments":"\"Paris\"}"}}]}"#,
+            None,
+        ));
+        assert!(matches!(
+            &more[..],
+            [StreamEvent::ToolCallDelta { index: 0, arguments }] if arguments == r#""Paris"}"#
+        ));
+
+        let finished = parser.parse_line(&chunk("{}", Some("tool_calls")));
+        match &finished[0] {
+            StreamEvent::ToolCallEnd {
+                index: 0,
+                tool_call,
+            } => {
+                assert_eq!(tool_call.id, "call_a");
+                assert_eq!(tool_call.function.arguments, r#"{"city":"Paris"}"#);
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_usage_chunk() {
+        let mut parser = StreamParser::new();
+        let events = parser.parse_line(
+            r#"data: {"id":"chatcmpl-1","object":"chat.completion.chunk","created":1,"model":"gpt-4o","choices":[],"usage":{"prompt_tokens":9,"completion_tokens":12,"total_tokens":21}}"#,
+        );
+        match events.as_slice() {
+            [
+                StreamEvent::MessageStart { .. },
+                StreamEvent::Usage { usage },
+            ] => {
+                assert_eq!(usage.prompt_tokens, 9);
+                assert_eq!(usage.total_tokens, 21);
+            }
+            other => panic!("unexpected events: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_ignores_non_data_lines() {
+        let mut parser = StreamParser::new();
+        assert!(parser.parse_line("").is_empty());
+        assert!(parser.parse_line(": keep-alive").is_empty());
+        assert!(parser.parse_line("event: ping").is_empty());
+    }
+
+    #[test]
+    fn test_in_band_error() {
+        let mut parser = StreamParser::new();
+        let events =
+            parser.parse_line(r#"data: {"error":{"message":"overloaded","type":"server_error"}}"#);
+        assert!(matches!(
+            events.as_slice(),
+            [StreamEvent::Error { message }] if message == "overloaded"
+        ));
+    }
+}
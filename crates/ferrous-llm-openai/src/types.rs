@@ -2,7 +2,9 @@
 
 use chrono::{DateTime, Utc};
 use ferrous_llm_core::{
-    ChatResponse, CompletionResponse, FinishReason, FunctionCall, Metadata, ToolCall, Usage,
+    ChatResponse, CompletionResponse, FinishReason, FunctionCall, GeneratedImage, ImageResponse,
+    Metadata, ModelInfo, SpeechToTextResponse, TextToSpeechResponse, ToolCall,
+    TranscriptionSegment, Usage,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -27,13 +29,26 @@ pub struct OpenAIChatRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<OpenAIStreamOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<Vec<OpenAITool>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_choice: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
 }
 
+/// OpenAI streaming options.
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenAIStreamOptions {
+    /// Request a final chunk carrying token usage for the whole request
+    pub include_usage: bool,
+}
+
 /// OpenAI message format.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpenAIMessage {
@@ -189,6 +204,229 @@ pub struct OpenAIEmbedding {
     pub embedding: Vec<f32>,
 }
 
+/// OpenAI image generation request.
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenAIImageRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    pub prompt: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+/// An image file uploaded to the edits and variations endpoints.
+#[derive(Debug, Clone)]
+pub struct OpenAIImageFile {
+    pub file_name: String,
+    pub data: Vec<u8>,
+}
+
+impl OpenAIImageFile {
+    /// Create an image file from its name and contents.
+    pub fn new(file_name: impl Into<String>, data: impl Into<Vec<u8>>) -> Self {
+        Self {
+            file_name: file_name.into(),
+            data: data.into(),
+        }
+    }
+
+    /// Read an image file from disk.
+    pub async fn from_path(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let data = tokio::fs::read(path).await?;
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "image.png".to_string());
+        Ok(Self { file_name, data })
+    }
+}
+
+/// OpenAI image edit request, sent as multipart form data.
+#[derive(Debug, Clone)]
+pub struct OpenAIImageEditRequest {
+    /// The image to edit
+    pub image: OpenAIImageFile,
+    /// Optional mask whose transparent areas mark where to edit
+    pub mask: Option<OpenAIImageFile>,
+    pub prompt: String,
+    pub n: Option<u32>,
+    pub size: Option<String>,
+    pub quality: Option<String>,
+    pub response_format: Option<String>,
+    pub user: Option<String>,
+}
+
+impl OpenAIImageEditRequest {
+    /// Create an edit request for an image and prompt.
+    pub fn new(image: OpenAIImageFile, prompt: impl Into<String>) -> Self {
+        Self {
+            image,
+            mask: None,
+            prompt: prompt.into(),
+            n: None,
+            size: None,
+            quality: None,
+            response_format: None,
+            user: None,
+        }
+    }
+}
+
+/// OpenAI image variation request, sent as multipart form data.
+#[derive(Debug, Clone)]
+pub struct OpenAIImageVariationRequest {
+    pub image: OpenAIImageFile,
+    pub n: Option<u32>,
+    pub size: Option<String>,
+    pub response_format: Option<String>,
+    pub user: Option<String>,
+}
+
+impl OpenAIImageVariationRequest {
+    /// Create a variation request for an image.
+    pub fn new(image: OpenAIImageFile) -> Self {
+        Self {
+            image,
+            n: None,
+            size: None,
+            response_format: None,
+            user: None,
+        }
+    }
+}
+
+/// OpenAI image response, shared by generations, edits and variations.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenAIImageResponse {
+    pub created: u64,
+    pub data: Vec<OpenAIImageData>,
+}
+
+/// A single image in an OpenAI image response.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenAIImageData {
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub b64_json: Option<String>,
+    #[serde(default)]
+    pub revised_prompt: Option<String>,
+}
+
+/// OpenAI transcription response.
+///
+/// `json` responses only carry `text`; `verbose_json` adds the language,
+/// duration and timestamps. Plain text formats (`text`, `srt`, `vtt`) are
+/// returned verbatim in `text`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OpenAITranscriptionResponse {
+    pub text: String,
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Duration of the audio in seconds
+    #[serde(default)]
+    pub duration: Option<f64>,
+    #[serde(default)]
+    pub segments: Option<Vec<OpenAITranscriptionSegment>>,
+    #[serde(default)]
+    pub words: Option<Vec<OpenAITranscriptionWord>>,
+}
+
+/// A timed segment of an OpenAI transcription.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenAITranscriptionSegment {
+    pub id: u32,
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+    #[serde(default)]
+    pub avg_logprob: Option<f64>,
+    #[serde(default)]
+    pub no_speech_prob: Option<f64>,
+}
+
+/// A timed word of an OpenAI transcription.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenAITranscriptionWord {
+    pub word: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+/// OpenAI speech synthesis request.
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenAISpeechRequest {
+    pub model: String,
+    pub input: String,
+    pub voice: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f32>,
+}
+
+/// Audio synthesized by the OpenAI speech endpoint.
+#[derive(Debug, Clone)]
+pub struct OpenAISpeechResponse {
+    pub audio: Vec<u8>,
+    /// Audio format, e.g. "mp3"
+    pub format: String,
+}
+
+/// A model listed by the OpenAI models endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAIModel {
+    pub id: String,
+    #[serde(default)]
+    pub object: Option<String>,
+    /// Unix timestamp of when the model was created
+    #[serde(default)]
+    pub created: Option<i64>,
+    #[serde(default)]
+    pub owned_by: Option<String>,
+}
+
+/// OpenAI model list response.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenAIModelList {
+    pub data: Vec<OpenAIModel>,
+}
+
+impl From<OpenAIModel> for ModelInfo {
+    /// Describe a model, filling in its capabilities from the built-in table.
+    fn from(model: OpenAIModel) -> Self {
+        let mut info = ModelInfo::lookup("openai", &model.id);
+        info.created = model
+            .created
+            .and_then(|created| DateTime::<Utc>::from_timestamp(created, 0));
+        if let Some(owned_by) = model.owned_by {
+            info.metadata
+                .insert("owned_by".to_string(), serde_json::Value::String(owned_by));
+        }
+        info
+    }
+}
+
+/// Map an OpenAI chat `finish_reason` string to the core [`FinishReason`].
+pub(crate) fn map_finish_reason(reason: &str) -> Option<FinishReason> {
+    match reason {
+        "stop" => Some(FinishReason::Stop),
+        "length" => Some(FinishReason::Length),
+        "tool_calls" | "function_call" => Some(FinishReason::ToolCalls),
+        "content_filter" => Some(FinishReason::ContentFilter),
+        _ => None,
+    }
+}
+
 /// OpenAI streaming response chunk.
 #[derive(Debug, Clone, Deserialize)]
 pub struct OpenAIStreamChunk {
@@ -197,6 +435,9 @@ pub struct OpenAIStreamChunk {
     pub created: u64,
     pub model: String,
     pub choices: Vec<OpenAIStreamChoice>,
+    /// Only present on the final chunk when `stream_options.include_usage` is set
+    #[serde(default)]
+    pub usage: Option<OpenAIUsage>,
 }
 
 /// OpenAI streaming choice.
@@ -337,14 +578,8 @@ impl ChatResponse for OpenAIChatResponseWrapper {
         self.response
             .choices
             .first()
-            .and_then(|choice| choice.finish_reason.as_ref())
-            .and_then(|reason| match reason.as_str() {
-                "stop" => Some(FinishReason::Stop),
-                "length" => Some(FinishReason::Length),
-                "tool_calls" => Some(FinishReason::ToolCalls),
-                "content_filter" => Some(FinishReason::ContentFilter),
-                _ => None,
-            })
+            .and_then(|choice| choice.finish_reason.as_deref())
+            .and_then(map_finish_reason)
     }
 
     fn metadata(&self) -> Metadata {
@@ -409,14 +644,8 @@ impl ChatResponse for OpenAIChatResponse {
     fn finish_reason(&self) -> Option<FinishReason> {
         self.choices
             .first()
-            .and_then(|choice| choice.finish_reason.as_ref())
-            .and_then(|reason| match reason.as_str() {
-                "stop" => Some(FinishReason::Stop),
-                "length" => Some(FinishReason::Length),
-                "tool_calls" => Some(FinishReason::ToolCalls),
-                "content_filter" => Some(FinishReason::ContentFilter),
-                _ => None,
-            })
+            .and_then(|choice| choice.finish_reason.as_deref())
+            .and_then(map_finish_reason)
     }
 
     fn metadata(&self) -> Metadata {
@@ -486,6 +715,70 @@ impl CompletionResponse for OpenAICompletionResponse {
     }
 }
 
+// Implement ImageResponse for OpenAIImageResponse
+impl ImageResponse for OpenAIImageResponse {
+    fn images(&self) -> Vec<GeneratedImage> {
+        self.data
+            .iter()
+            .map(|image| GeneratedImage {
+                url: image.url.clone(),
+                b64_json: image.b64_json.clone(),
+                revised_prompt: image.revised_prompt.clone(),
+            })
+            .collect()
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            extensions: HashMap::new(),
+            request_id: None,
+            user_id: None,
+            created_at: DateTime::from_timestamp(self.created as i64, 0).unwrap_or_else(Utc::now),
+        }
+    }
+}
+
+impl SpeechToTextResponse for OpenAITranscriptionResponse {
+    fn text(&self) -> String {
+        self.text.clone()
+    }
+
+    fn language(&self) -> Option<String> {
+        self.language.clone()
+    }
+
+    fn segments(&self) -> Option<Vec<TranscriptionSegment>> {
+        self.segments.as_ref().map(|segments| {
+            segments
+                .iter()
+                .map(|segment| TranscriptionSegment {
+                    start: segment.start,
+                    end: segment.end,
+                    text: segment.text.clone(),
+                })
+                .collect()
+        })
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata::default()
+    }
+}
+
+impl TextToSpeechResponse for OpenAISpeechResponse {
+    fn audio_data(&self) -> Vec<u8> {
+        self.audio.clone()
+    }
+
+    fn format(&self) -> String {
+        self.format.clone()
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata::default()
+    }
+}
+
 // Conversion utilities
 impl From<&ferrous_llm_core::Message> for OpenAIMessage {
     fn from(message: &ferrous_llm_core::Message) -> Self {
@@ -0,0 +1,254 @@
+//! Speech-to-text and text-to-speech tests for the OpenAI provider against a
+//! local mock server.
+
+use ferrous_llm_core::{
+    AudioInput, Metadata, SpeechToTextProvider, SpeechToTextRequest, SpeechToTextResponse,
+    StreamingTextToSpeechProvider, TextToSpeechProvider, TextToSpeechRequest, TextToSpeechResponse,
+};
+use ferrous_llm_openai::{OpenAIConfig, OpenAIProvider};
+use futures::StreamExt;
+use serde_json::json;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+const AUDIO: &[u8] = b"ID3\x04\x00fake-mp3";
+
+fn provider(server: &MockServer) -> OpenAIProvider {
+    let mut config = OpenAIConfig::new("sk-test123456789", "gpt-4o-mini");
+    config.base_url = Some(format!("{}/v1", server.uri()).parse().unwrap());
+    config.http.max_retries = 0;
+    OpenAIProvider::new(config).unwrap()
+}
+
+fn transcription_request(audio: AudioInput) -> SpeechToTextRequest {
+    SpeechToTextRequest {
+        audio,
+        format: "mp3".to_string(),
+        language: None,
+        response_format: None,
+        timestamp_granularities: None,
+        metadata: Metadata::default(),
+    }
+}
+
+/// Match multipart bodies containing all of the given fragments.
+fn body_contains(fragments: &'static [&'static str]) -> impl Fn(&Request) -> bool {
+    move |request: &Request| {
+        let body = String::from_utf8_lossy(&request.body);
+        fragments.iter().all(|fragment| body.contains(fragment))
+    }
+}
+
+#[tokio::test]
+async fn test_transcribe_with_segments() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/audio/transcriptions"))
+        .and(body_contains(&[
+            r#"name="file"; filename="meeting.mp3""#,
+            "fake-mp3",
+            "whisper-1",
+            r#"name="language""#,
+            "verbose_json",
+            r#"name="timestamp_granularities[]""#,
+        ]))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "task": "transcribe",
+            "language": "english",
+            "duration": 3.2,
+            "text": "Hello there. General Kenobi.",
+            "segments": [
+                {"id": 0, "start": 0.0, "end": 1.5, "text": "Hello there.", "avg_logprob": -0.2},
+                {"id": 1, "start": 1.5, "end": 3.2, "text": "General Kenobi."}
+            ]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut request = transcription_request(AudioInput::bytes(AUDIO).with_file_name("meeting.mp3"));
+    request.language = Some("en".to_string());
+    request.timestamp_granularities = Some(vec!["segment".to_string()]);
+
+    let response = provider(&server).speech_to_text(request).await.unwrap();
+
+    assert_eq!(response.text(), "Hello there. General Kenobi.");
+    assert_eq!(response.language().as_deref(), Some("english"));
+    assert_eq!(response.duration, Some(3.2));
+    let segments = response.segments().unwrap();
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[1].start, 1.5);
+    assert_eq!(segments[1].text, "General Kenobi.");
+}
+
+#[tokio::test]
+async fn test_transcribe_file_as_srt() {
+    let server = MockServer::start().await;
+    let srt = "1\n00:00:00,000 --> 00:00:01,500\nHello there.\n";
+    Mock::given(method("POST"))
+        .and(path("/v1/audio/transcriptions"))
+        .and(body_contains(&[r#"filename="clip.mp3""#, "srt"]))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(srt, "text/plain"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let file = std::env::temp_dir().join(format!("ferrous-llm-{}", std::process::id()));
+    std::fs::create_dir_all(&file).unwrap();
+    let file = file.join("clip.mp3");
+    std::fs::write(&file, AUDIO).unwrap();
+
+    let mut request = transcription_request(AudioInput::path(&file));
+    request.response_format = Some("srt".to_string());
+
+    let response = provider(&server).speech_to_text(request).await;
+    std::fs::remove_file(&file).unwrap();
+
+    let response = response.unwrap();
+    assert_eq!(response.text(), srt);
+    assert!(response.segments().is_none());
+}
+
+#[tokio::test]
+async fn test_transcribe_missing_file() {
+    let server = MockServer::start().await;
+    let request = transcription_request(AudioInput::path("/nonexistent/clip.mp3"));
+
+    let result = provider(&server).speech_to_text(request).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_text_to_speech() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/audio/speech"))
+        .and(body_json(json!({
+            "model": "tts-1",
+            "input": "Hello, world!",
+            "voice": "alloy",
+            "response_format": "opus",
+            "speed": 1.25
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(AUDIO, "audio/ogg"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let request = TextToSpeechRequest {
+        text: "Hello, world!".to_string(),
+        voice: "alloy".to_string(),
+        format: Some("opus".to_string()),
+        speed: Some(1.25),
+        metadata: Metadata::default(),
+    };
+    let response = provider(&server).text_to_speech(request).await.unwrap();
+
+    assert_eq!(response.audio_data(), AUDIO);
+    assert_eq!(response.format(), "opus");
+}
+
+#[tokio::test]
+async fn test_text_to_speech_stream() {
+    let server = MockServer::start().await;
+    let audio: Vec<u8> = (0..64 * 1024).map(|i| (i % 251) as u8).collect();
+    Mock::given(method("POST"))
+        .and(path("/v1/audio/speech"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(audio.clone(), "audio/mpeg"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let request = TextToSpeechRequest {
+        text: "Streaming speech".to_string(),
+        voice: "nova".to_string(),
+        format: None,
+        speed: None,
+        metadata: Metadata::default(),
+    };
+    let mut stream = provider(&server)
+        .text_to_speech_stream(request)
+        .await
+        .unwrap();
+
+    // Format is available before any audio is read
+    assert_eq!(stream.format, "mp3");
+    assert_eq!(stream.content_type.as_deref(), Some("audio/mpeg"));
+
+    let first = stream.next().await.unwrap().unwrap();
+    assert!(!first.is_empty());
+    let rest = stream.into_audio().await.unwrap();
+    assert_eq!([first, rest].concat(), audio);
+}
+
+#[tokio::test]
+async fn test_text_to_speech_stream_outlasts_request_timeout() {
+    // Wiremock can only delay the whole response, so serve audio whose
+    // chunks trickle in over longer than `http.timeout`.
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = [0u8; 4096];
+        let _ = socket.read(&mut request).await.unwrap();
+        socket
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-type: audio/mpeg\r\nconnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        for _ in 0..4 {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            socket.write_all(AUDIO).await.unwrap();
+        }
+    });
+
+    let config = OpenAIConfig::builder()
+        .api_key("sk-test123456789")
+        .model("gpt-4o-mini")
+        .base_url(format!("http://{address}/v1"))
+        .unwrap()
+        .max_retries(0)
+        .timeout(Duration::from_millis(500))
+        .build();
+    let request = TextToSpeechRequest {
+        text: "A long passage".to_string(),
+        voice: "nova".to_string(),
+        format: None,
+        speed: None,
+        metadata: Metadata::default(),
+    };
+    let audio = OpenAIProvider::new(config)
+        .unwrap()
+        .text_to_speech_stream(request)
+        .await
+        .unwrap()
+        .into_audio()
+        .await
+        .unwrap();
+
+    assert_eq!(audio, AUDIO.repeat(4));
+}
+
+#[tokio::test]
+async fn test_text_to_speech_stream_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/audio/speech"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "error": {"message": "Invalid voice", "type": "invalid_request_error"}
+        })))
+        .mount(&server)
+        .await;
+
+    let request = TextToSpeechRequest {
+        text: "Hello".to_string(),
+        voice: "unknown".to_string(),
+        format: None,
+        speed: None,
+        metadata: Metadata::default(),
+    };
+    let result = provider(&server).text_to_speech_stream(request).await;
+    assert!(result.is_err());
+}
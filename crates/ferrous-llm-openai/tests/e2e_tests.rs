@@ -94,8 +94,11 @@ mod e2e_tests {
 
                 while let Some(chunk_result) = stream.next().await {
                     match chunk_result {
-                        Ok(content) => {
-                            content_parts.push(content.clone());
+                        Ok(event) => {
+                            let Some(content) = event.text() else {
+                                continue;
+                            };
+                            content_parts.push(content.to_string());
                             chunk_count += 1;
                             println!("Streaming chunk {}: '{}'", chunk_count, content);
 
@@ -0,0 +1,135 @@
+//! Image generation tests for the OpenAI provider against a local mock server.
+
+use ferrous_llm_core::{ImageProvider, ImageRequest, ImageResponse, Metadata};
+use ferrous_llm_openai::{
+    OpenAIConfig, OpenAIImageEditRequest, OpenAIImageFile, OpenAIImageVariationRequest,
+    OpenAIProvider,
+};
+use serde_json::json;
+use wiremock::matchers::{body_json, header_regex, method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+const PNG: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0];
+
+fn provider(server: &MockServer) -> OpenAIProvider {
+    let mut config = OpenAIConfig::new("sk-test123456789", "gpt-4o-mini");
+    config.base_url = Some(format!("{}/v1", server.uri()).parse().unwrap());
+    config.image_model = Some("dall-e-2".to_string());
+    config.http.max_retries = 0;
+    OpenAIProvider::new(config).unwrap()
+}
+
+fn image_body() -> serde_json::Value {
+    json!({
+        "created": 1700000000,
+        "data": [{"b64_json": "iVBORw0KGgoAAA==", "revised_prompt": "A red fox"}]
+    })
+}
+
+/// Match multipart bodies containing all of the given fragments.
+fn body_contains(fragments: &'static [&'static str]) -> impl Fn(&Request) -> bool {
+    move |request: &Request| {
+        let body = String::from_utf8_lossy(&request.body);
+        fragments.iter().all(|fragment| body.contains(fragment))
+    }
+}
+
+#[tokio::test]
+async fn test_generate_image() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/images/generations"))
+        .and(body_json(json!({
+            "model": "dall-e-2",
+            "prompt": "A fox",
+            "n": 1,
+            "size": "256x256",
+            "response_format": "b64_json"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(image_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let request = ImageRequest {
+        prompt: "A fox".to_string(),
+        negative_prompt: None,
+        n: Some(1),
+        size: Some("256x256".to_string()),
+        quality: None,
+        response_format: Some("b64_json".to_string()),
+        metadata: Metadata::default(),
+    };
+    let response = provider(&server).generate_image(request).await.unwrap();
+
+    let images = response.images();
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].revised_prompt.as_deref(), Some("A red fox"));
+    assert_eq!(images[0].bytes().unwrap(), PNG);
+    assert_eq!(response.metadata().created_at.timestamp(), 1700000000);
+}
+
+#[tokio::test]
+async fn test_edit_image_with_mask() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/images/edits"))
+        .and(header_regex(
+            "content-type",
+            "^multipart/form-data; boundary=",
+        ))
+        .and(body_contains(&[
+            r#"name="image"; filename="fox.png""#,
+            r#"name="mask"; filename="mask.png""#,
+            "Content-Type: image/png",
+            r#"name="prompt""#,
+            "Add a hat",
+            r#"name="model""#,
+            r#"name="size""#,
+        ]))
+        .respond_with(ResponseTemplate::new(200).set_body_json(image_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut request =
+        OpenAIImageEditRequest::new(OpenAIImageFile::new("fox.png", PNG), "Add a hat");
+    request.mask = Some(OpenAIImageFile::new("mask.png", PNG));
+    request.size = Some("256x256".to_string());
+
+    let response = provider(&server).edit_image(request).await.unwrap();
+    assert_eq!(response.data.len(), 1);
+}
+
+#[tokio::test]
+async fn test_create_image_variation() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/images/variations"))
+        .and(body_contains(&[
+            r#"name="image"; filename="fox.png""#,
+            r#"name="n""#,
+        ]))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "created": 1700000000,
+            "data": [
+                {"url": "https://example.com/1.png"},
+                {"url": "https://example.com/2.png"}
+            ]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut request = OpenAIImageVariationRequest::new(OpenAIImageFile::new("fox.png", PNG));
+    request.n = Some(2);
+
+    let response = provider(&server)
+        .create_image_variation(request)
+        .await
+        .unwrap();
+    let images = response.images();
+    assert_eq!(images[1].url.as_deref(), Some("https://example.com/2.png"));
+    // Remote images have no inline data to decode
+    assert!(images[1].bytes().is_err());
+}
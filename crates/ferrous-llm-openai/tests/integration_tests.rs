@@ -363,8 +363,11 @@ fn test_openai_request_serialization() {
         presence_penalty: None,
         stop: vec![],
         stream: Some(false),
+        stream_options: None,
         tools: None,
         tool_choice: None,
+        parallel_tool_calls: None,
+        response_format: None,
         user: None,
     };
 
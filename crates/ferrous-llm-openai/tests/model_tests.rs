@@ -0,0 +1,80 @@
+//! Model catalog tests for the OpenAI provider against a local mock server.
+
+use ferrous_llm_core::ModelCatalog;
+use ferrous_llm_openai::{OpenAIConfig, OpenAIProvider};
+use serde_json::json;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn provider(server: &MockServer) -> OpenAIProvider {
+    let mut config = OpenAIConfig::new("sk-test123456789", "gpt-4o-mini");
+    config.base_url = Some(format!("{}/v1", server.uri()).parse().unwrap());
+    OpenAIProvider::new(config).unwrap()
+}
+
+#[tokio::test]
+async fn test_list_models() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models"))
+        .and(header("authorization", "Bearer sk-test123456789"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "object": "list",
+            "data": [
+                {"id": "gpt-4o-2024-08-06", "object": "model", "created": 1722814719, "owned_by": "system"},
+                {"id": "ft:gpt-4o-mini:acme::abc123", "object": "model", "created": 1730000000, "owned_by": "acme"}
+            ]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let models = provider(&server).list_models().await.unwrap();
+    assert_eq!(models.len(), 2);
+
+    let gpt4o = &models[0];
+    assert_eq!(gpt4o.provider, "openai");
+    assert_eq!(gpt4o.context_window, Some(128_000));
+    assert!(gpt4o.supports_tools && gpt4o.supports_vision && gpt4o.supports_json_mode);
+    assert_eq!(gpt4o.pricing.unwrap().output, 10.0);
+    assert_eq!(gpt4o.created.unwrap().timestamp(), 1722814719);
+    assert_eq!(gpt4o.metadata["owned_by"], "system");
+
+    // Fine-tunes aren't in the capability table
+    let fine_tune = &models[1];
+    assert_eq!(fine_tune.context_window, None);
+    assert_eq!(fine_tune.metadata["owned_by"], "acme");
+}
+
+#[tokio::test]
+async fn test_model_info() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models/text-embedding-3-small"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "text-embedding-3-small",
+            "object": "model",
+            "created": 1705948997,
+            "owned_by": "system"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/models/nope"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "error": {
+                "message": "The model 'nope' does not exist",
+                "type": "invalid_request_error",
+                "code": "model_not_found"
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = provider(&server);
+    let info = provider.model_info("text-embedding-3-small").await.unwrap();
+    assert_eq!(info.context_window, Some(8_191));
+    assert!(!info.supports_streaming);
+
+    assert!(provider.model_info("nope").await.is_err());
+}
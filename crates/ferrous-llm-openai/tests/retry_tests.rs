@@ -0,0 +1,183 @@
+//! Retry behaviour tests for the OpenAI provider against a local mock server.
+
+use ferrous_llm_core::{
+    ChatProvider, ChatRequest, ChatResponse, EmbeddingProvider, ProviderError, StreamingProvider,
+};
+use ferrous_llm_openai::{OpenAIConfig, OpenAIError, OpenAIProvider};
+use futures::StreamExt;
+use serde_json::json;
+use std::time::{Duration, Instant};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn provider(server: &MockServer, max_retries: u32) -> OpenAIProvider {
+    let mut config = OpenAIConfig::new("sk-test123456789", "gpt-4o-mini");
+    config.base_url = Some(format!("{}/v1", server.uri()).parse().unwrap());
+    config.http.max_retries = max_retries;
+    config.http.retry_delay = Duration::from_millis(1);
+    config.http.max_retry_delay = Duration::from_millis(50);
+    OpenAIProvider::new(config).unwrap()
+}
+
+fn request() -> ChatRequest {
+    ChatRequest::builder().user_message("Hello").build()
+}
+
+fn chat_body() -> serde_json::Value {
+    json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 1,
+        "model": "gpt-4o-mini",
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": "Hi there"},
+            "finish_reason": "stop"
+        }],
+        "usage": {"prompt_tokens": 5, "completion_tokens": 2, "total_tokens": 7}
+    })
+}
+
+#[tokio::test]
+async fn test_chat_retries_rate_limit_with_retry_after() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(chat_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let response = provider(&server, 3).chat(request()).await.unwrap();
+    assert_eq!(response.content(), "Hi there");
+}
+
+#[tokio::test]
+async fn test_retry_after_is_capped_by_max_retry_delay() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "120"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(chat_body()))
+        .mount(&server)
+        .await;
+
+    let start = Instant::now();
+    provider(&server, 1).chat(request()).await.unwrap();
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_chat_gives_up_after_max_retries() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let error = provider(&server, 2).chat(request()).await.unwrap_err();
+    assert!(error.is_service_unavailable());
+}
+
+#[tokio::test]
+async fn test_non_retryable_error_is_not_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+            "error": {"message": "bad key", "type": "invalid_api_key"}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let error = provider(&server, 3).chat(request()).await.unwrap_err();
+    assert!(error.is_auth_error());
+}
+
+#[tokio::test]
+async fn test_rate_limit_error_exposes_retry_after() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "7"))
+        .mount(&server)
+        .await;
+
+    let error = provider(&server, 0).chat(request()).await.unwrap_err();
+    assert!(matches!(error, OpenAIError::RateLimit { .. }));
+    assert_eq!(error.retry_after(), Some(Duration::from_secs(7)));
+}
+
+#[tokio::test]
+async fn test_embed_retries() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/embeddings"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/embeddings"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "object": "list",
+            "data": [{"object": "embedding", "index": 0, "embedding": [0.1, 0.2]}],
+            "model": "text-embedding-3-small",
+            "usage": {"prompt_tokens": 1, "total_tokens": 1}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let embeddings = provider(&server, 2)
+        .embed(&["hello".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(embeddings[0].embedding, vec![0.1, 0.2]);
+}
+
+#[tokio::test]
+async fn test_stream_connection_retries() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(502))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    let body = concat!(
+        "data: {\"id\":\"chatcmpl-1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"gpt-4o-mini\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hi\"},\"finish_reason\":null}]}\n\n",
+        "data: [DONE]\n\n",
+    );
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/event-stream"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut stream = provider(&server, 2).chat_stream(request()).await.unwrap();
+    let mut text = String::new();
+    while let Some(event) = stream.next().await {
+        if let Some(delta) = event.unwrap().text() {
+            text.push_str(delta);
+        }
+    }
+    assert_eq!(text, "Hi");
+}
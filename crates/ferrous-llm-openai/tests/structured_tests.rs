@@ -0,0 +1,82 @@
+//! Structured output tests for the OpenAI provider against a local mock server.
+
+use ferrous_llm_core::{ChatRequest, ResponseSchema, StructuredOutput};
+use ferrous_llm_openai::{OpenAIConfig, OpenAIProvider};
+use serde::Deserialize;
+use serde_json::json;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[derive(Debug, Deserialize)]
+struct Weather {
+    city: String,
+    celsius: f32,
+}
+
+fn completion(content: &str) -> serde_json::Value {
+    json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 1700000000,
+        "model": "gpt-4o-mini",
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": content},
+            "finish_reason": "stop"
+        }],
+        "usage": {"prompt_tokens": 20, "completion_tokens": 10, "total_tokens": 30}
+    })
+}
+
+#[tokio::test]
+async fn test_structured_output_uses_json_schema() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .and(body_partial_json(json!({
+            "response_format": {
+                "type": "json_schema",
+                "json_schema": {
+                    "name": "weather",
+                    "strict": true,
+                    "schema": {"type": "object", "required": ["city", "celsius"]}
+                }
+            }
+        })))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(completion(r#"{"city": "Oslo", "celsius": -3}"#)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut config = OpenAIConfig::new("sk-test123456789", "gpt-4o-mini");
+    config.base_url = Some(format!("{}/v1", server.uri()).parse().unwrap());
+    let provider = OpenAIProvider::new(config).unwrap();
+
+    let schema = ResponseSchema::new(
+        "weather",
+        json!({
+            "type": "object",
+            "properties": {
+                "city": {"type": "string"},
+                "celsius": {"type": "number"}
+            },
+            "required": ["city", "celsius"],
+            "additionalProperties": false
+        }),
+    )
+    .strict(true);
+    let request = ChatRequest::builder()
+        .user_message("What's the weather in Oslo?")
+        .build();
+
+    let result = StructuredOutput::new(&provider, schema)
+        .generate::<Weather>(request)
+        .await
+        .unwrap();
+    assert_eq!(result.value.city, "Oslo");
+    assert_eq!(result.value.celsius, -3.0);
+    assert_eq!(result.attempts, 1);
+}
@@ -66,14 +66,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Process the stream
     while let Some(chunk_result) = stream.next().await {
         match chunk_result {
-            Ok(chunk) => {
-                // Print the chunk immediately (streaming effect)
-                print!("{chunk}");
-                io::stdout().flush().unwrap(); // Ensure immediate output
-
-                // Accumulate the full response
-                full_response.push_str(&chunk);
-                token_count += 1;
+            Ok(event) => {
+                if let Some(text) = event.text() {
+                    // Print the text immediately (streaming effect)
+                    print!("{text}");
+                    io::stdout().flush().unwrap(); // Ensure immediate output
+
+                    // Accumulate the full response
+                    full_response.push_str(text);
+                    token_count += 1;
+                }
             }
             Err(e) => {
                 error!("\n❌ Error in stream: {e}");
@@ -0,0 +1,394 @@
+//! Build a provider from a single serialized configuration.
+//!
+//! [`AnyProviderConfig`] is tagged by a `provider` field, so a configuration
+//! file can pick the provider at runtime:
+//!
+//! ```toml
+//! provider = "anthropic"
+//! model = "claude-3-5-haiku-20241022"
+//! api_key = { env = "ANTHROPIC_API_KEY" }
+//! ```
+//!
+//! Only providers enabled through cargo features are available. Building the
+//! configuration yields an [`AnyProvider`], which implements the capability
+//! traits with boxed responses and [`DynError`] errors.
+
+use async_trait::async_trait;
+use ferrous_llm_core::{
+    BoxedChatResponse, ChatProvider, ChatRequest, ConfigError, DynChatProvider, DynChatStream,
+    DynError, DynStreamingProvider, DynStructuredProvider, Embedding, EmbeddingProvider,
+    ModelCatalog, ModelInfo, ProviderConfig, RequestError, ResponseSchema, StreamEvent,
+    StreamingProvider, StreamingToolProvider, StructuredProvider, Tool, ToolProvider,
+};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "anthropic")]
+use ferrous_llm_anthropic::{AnthropicConfig, AnthropicProvider};
+#[cfg(feature = "ollama")]
+use ferrous_llm_ollama::{OllamaConfig, OllamaProvider};
+#[cfg(feature = "openai")]
+use ferrous_llm_openai::{OpenAIConfig, OpenAIProvider};
+
+#[cfg(any(feature = "openai", feature = "ollama"))]
+use ferrous_llm_core::DynEmbeddingProvider;
+#[cfg(any(feature = "openai", feature = "anthropic", feature = "ollama"))]
+use ferrous_llm_core::DynToolProvider;
+#[cfg(any(feature = "openai", feature = "anthropic", feature = "ollama"))]
+use futures::StreamExt;
+
+/// Configuration for any of the enabled providers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "provider", rename_all = "lowercase")]
+pub enum AnyProviderConfig {
+    /// OpenAI configuration
+    #[cfg(feature = "openai")]
+    OpenAI(OpenAIConfig),
+
+    /// Anthropic configuration
+    #[cfg(feature = "anthropic")]
+    Anthropic(AnthropicConfig),
+
+    /// Ollama configuration
+    #[cfg(feature = "ollama")]
+    Ollama(OllamaConfig),
+}
+
+impl AnyProviderConfig {
+    /// Name of the selected provider, as used in the `provider` tag.
+    pub fn provider_name(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "openai")]
+            Self::OpenAI(_) => "openai",
+            #[cfg(feature = "anthropic")]
+            Self::Anthropic(_) => "anthropic",
+            #[cfg(feature = "ollama")]
+            Self::Ollama(_) => "ollama",
+        }
+    }
+
+    /// Model configured for chat requests.
+    pub fn model(&self) -> &str {
+        match self {
+            #[cfg(feature = "openai")]
+            Self::OpenAI(config) => &config.model,
+            #[cfg(feature = "anthropic")]
+            Self::Anthropic(config) => &config.model,
+            #[cfg(feature = "ollama")]
+            Self::Ollama(config) => &config.model,
+        }
+    }
+}
+
+impl ProviderConfig for AnyProviderConfig {
+    type Provider = AnyProvider;
+
+    fn build(self) -> Result<Self::Provider, ConfigError> {
+        match self {
+            #[cfg(feature = "openai")]
+            Self::OpenAI(config) => config.build().map(AnyProvider::OpenAI),
+            #[cfg(feature = "anthropic")]
+            Self::Anthropic(config) => config.build().map(AnyProvider::Anthropic),
+            #[cfg(feature = "ollama")]
+            Self::Ollama(config) => config.build().map(AnyProvider::Ollama),
+        }
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        match self {
+            #[cfg(feature = "openai")]
+            Self::OpenAI(config) => config.validate(),
+            #[cfg(feature = "anthropic")]
+            Self::Anthropic(config) => config.validate(),
+            #[cfg(feature = "ollama")]
+            Self::Ollama(config) => config.validate(),
+        }
+    }
+}
+
+#[cfg(feature = "openai")]
+impl From<OpenAIConfig> for AnyProviderConfig {
+    fn from(config: OpenAIConfig) -> Self {
+        Self::OpenAI(config)
+    }
+}
+
+#[cfg(feature = "anthropic")]
+impl From<AnthropicConfig> for AnyProviderConfig {
+    fn from(config: AnthropicConfig) -> Self {
+        Self::Anthropic(config)
+    }
+}
+
+#[cfg(feature = "ollama")]
+impl From<OllamaConfig> for AnyProviderConfig {
+    fn from(config: OllamaConfig) -> Self {
+        Self::Ollama(config)
+    }
+}
+
+/// A provider built from an [`AnyProviderConfig`].
+///
+/// Capabilities a provider lacks, such as embeddings on Anthropic, fail with
+/// [`RequestError::UnsupportedFeature`].
+#[derive(Debug, Clone)]
+pub enum AnyProvider {
+    /// OpenAI provider
+    #[cfg(feature = "openai")]
+    OpenAI(OpenAIProvider),
+
+    /// Anthropic provider
+    #[cfg(feature = "anthropic")]
+    Anthropic(AnthropicProvider),
+
+    /// Ollama provider
+    #[cfg(feature = "ollama")]
+    Ollama(OllamaProvider),
+}
+
+impl AnyProvider {
+    /// Build a provider from its configuration.
+    pub fn from_config(config: AnyProviderConfig) -> Result<Self, ConfigError> {
+        config.build()
+    }
+
+    /// Name of the underlying provider.
+    pub fn provider_name(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "openai")]
+            Self::OpenAI(_) => "openai",
+            #[cfg(feature = "anthropic")]
+            Self::Anthropic(_) => "anthropic",
+            #[cfg(feature = "ollama")]
+            Self::Ollama(_) => "ollama",
+        }
+    }
+
+    fn unsupported(&self, feature: &str) -> DynError {
+        DynError::Request(RequestError::unsupported_feature(format!(
+            "{feature} is not supported by {}",
+            self.provider_name()
+        )))
+    }
+}
+
+#[async_trait]
+impl ChatProvider for AnyProvider {
+    type Config = AnyProviderConfig;
+    type Response = BoxedChatResponse;
+    type Error = DynError;
+
+    async fn chat(&self, request: ChatRequest) -> Result<Self::Response, Self::Error> {
+        match self {
+            #[cfg(feature = "openai")]
+            Self::OpenAI(provider) => provider.chat_dyn(request).await,
+            #[cfg(feature = "anthropic")]
+            Self::Anthropic(provider) => provider.chat_dyn(request).await,
+            #[cfg(feature = "ollama")]
+            Self::Ollama(provider) => provider.chat_dyn(request).await,
+        }
+    }
+}
+
+#[async_trait]
+impl StreamingProvider for AnyProvider {
+    type StreamItem = StreamEvent;
+    type Stream = DynChatStream;
+
+    async fn chat_stream(&self, request: ChatRequest) -> Result<Self::Stream, Self::Error> {
+        match self {
+            #[cfg(feature = "openai")]
+            Self::OpenAI(provider) => provider.chat_stream_dyn(request).await,
+            #[cfg(feature = "anthropic")]
+            Self::Anthropic(provider) => provider.chat_stream_dyn(request).await,
+            #[cfg(feature = "ollama")]
+            Self::Ollama(provider) => provider.chat_stream_dyn(request).await,
+        }
+    }
+}
+
+#[async_trait]
+impl ToolProvider for AnyProvider {
+    async fn chat_with_tools(
+        &self,
+        request: ChatRequest,
+        tools: &[Tool],
+    ) -> Result<Self::Response, Self::Error> {
+        match self {
+            #[cfg(feature = "openai")]
+            Self::OpenAI(provider) => provider.chat_with_tools_dyn(request, tools).await,
+            #[cfg(feature = "anthropic")]
+            Self::Anthropic(provider) => provider.chat_with_tools_dyn(request, tools).await,
+            #[cfg(feature = "ollama")]
+            Self::Ollama(provider) => provider.chat_with_tools_dyn(request, tools).await,
+        }
+    }
+}
+
+#[async_trait]
+impl StreamingToolProvider for AnyProvider {
+    async fn chat_stream_with_tools(
+        &self,
+        request: ChatRequest,
+        tools: &[Tool],
+    ) -> Result<Self::Stream, Self::Error> {
+        match self {
+            #[cfg(feature = "openai")]
+            Self::OpenAI(provider) => provider
+                .chat_stream_with_tools(request, tools)
+                .await
+                .map(|stream| stream.map(|item| item.map_err(DynError::provider)).boxed())
+                .map_err(DynError::provider),
+            #[cfg(feature = "anthropic")]
+            Self::Anthropic(provider) => provider
+                .chat_stream_with_tools(request, tools)
+                .await
+                .map(|stream| stream.map(|item| item.map_err(DynError::provider)).boxed())
+                .map_err(DynError::provider),
+            #[cfg(feature = "ollama")]
+            Self::Ollama(provider) => provider
+                .chat_stream_with_tools(request, tools)
+                .await
+                .map(|stream| stream.map(|item| item.map_err(DynError::provider)).boxed())
+                .map_err(DynError::provider),
+        }
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for AnyProvider {
+    type Config = AnyProviderConfig;
+    type Error = DynError;
+
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Embedding>, Self::Error> {
+        match self {
+            #[cfg(feature = "openai")]
+            Self::OpenAI(provider) => provider.embed_dyn(texts).await,
+            #[cfg(feature = "ollama")]
+            Self::Ollama(provider) => provider.embed_dyn(texts).await,
+            #[allow(unreachable_patterns)]
+            _ => {
+                let _ = texts;
+                Err(self.unsupported("embeddings"))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StructuredProvider for AnyProvider {
+    async fn chat_structured(
+        &self,
+        request: ChatRequest,
+        schema: &ResponseSchema,
+    ) -> Result<Self::Response, Self::Error> {
+        match self {
+            #[cfg(feature = "openai")]
+            Self::OpenAI(provider) => provider.chat_structured_dyn(request, schema).await,
+            #[cfg(feature = "anthropic")]
+            Self::Anthropic(provider) => provider.chat_structured_dyn(request, schema).await,
+            #[cfg(feature = "ollama")]
+            Self::Ollama(provider) => provider.chat_structured_dyn(request, schema).await,
+        }
+    }
+}
+
+#[async_trait]
+impl ModelCatalog for AnyProvider {
+    type Error = DynError;
+
+    async fn list_models(&self) -> Result<Vec<ModelInfo>, Self::Error> {
+        match self {
+            #[cfg(feature = "openai")]
+            Self::OpenAI(provider) => provider.list_models().await.map_err(DynError::provider),
+            #[cfg(feature = "anthropic")]
+            Self::Anthropic(provider) => provider.list_models().await.map_err(DynError::provider),
+            #[cfg(feature = "ollama")]
+            Self::Ollama(provider) => provider.list_models().await.map_err(DynError::provider),
+        }
+    }
+
+    async fn model_info(&self, model: &str) -> Result<ModelInfo, Self::Error> {
+        match self {
+            #[cfg(feature = "openai")]
+            Self::OpenAI(provider) => provider.model_info(model).await.map_err(DynError::provider),
+            #[cfg(feature = "anthropic")]
+            Self::Anthropic(provider) => {
+                provider.model_info(model).await.map_err(DynError::provider)
+            }
+            #[cfg(feature = "ollama")]
+            Self::Ollama(provider) => provider.model_info(model).await.map_err(DynError::provider),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "anthropic")]
+    #[test]
+    #[allow(irrefutable_let_patterns)]
+    fn test_anthropic_from_toml() {
+        let config: AnyProviderConfig = toml::from_str(
+            r#"
+            provider = "anthropic"
+            model = "claude-3-5-haiku-20241022"
+            api_key = "sk-ant-test123456789"
+
+            [http]
+            max_retries = 5
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.provider_name(), "anthropic");
+        assert_eq!(config.model(), "claude-3-5-haiku-20241022");
+        let AnyProviderConfig::Anthropic(inner) = &config else {
+            panic!("expected an Anthropic config");
+        };
+        assert_eq!(inner.version, "2023-06-01");
+        assert_eq!(inner.http.max_retries, 5);
+
+        let provider = config.build().unwrap();
+        assert_eq!(provider.provider_name(), "anthropic");
+    }
+
+    #[cfg(feature = "openai")]
+    #[test]
+    fn test_openai_api_key_from_env() {
+        let error = serde_json::from_value::<AnyProviderConfig>(serde_json::json!({
+            "provider": "openai",
+            "model": "gpt-4o-mini",
+            "api_key": {"env": "FERROUS_LLM_FACTORY_UNSET_KEY"}
+        }))
+        .unwrap_err();
+        assert!(error.to_string().contains("FERROUS_LLM_FACTORY_UNSET_KEY"));
+    }
+
+    #[cfg(feature = "anthropic")]
+    #[tokio::test]
+    async fn test_anthropic_unsupported_embeddings() {
+        let config: AnyProviderConfig = serde_json::from_value(serde_json::json!({
+            "provider": "anthropic",
+            "model": "claude-3-5-haiku-20241022",
+            "api_key": "sk-ant-test123456789"
+        }))
+        .unwrap();
+        let provider = AnyProvider::from_config(config).unwrap();
+
+        let result = provider.embed(&["Hello".to_string()]).await;
+        assert!(matches!(
+            result,
+            Err(DynError::Request(RequestError::UnsupportedFeature { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_unknown_provider() {
+        let result = serde_json::from_value::<AnyProviderConfig>(serde_json::json!({
+            "provider": "nonexistent",
+            "model": "x"
+        }));
+        assert!(result.is_err());
+    }
+}
@@ -1,6 +1,11 @@
 #[allow(ambiguous_glob_reexports)]
 pub use ferrous_llm_core::*;
 
+#[cfg(any(feature = "openai", feature = "ollama", feature = "anthropic"))]
+pub mod factory;
+#[cfg(any(feature = "openai", feature = "ollama", feature = "anthropic"))]
+pub use factory::{AnyProvider, AnyProviderConfig};
+
 #[cfg(feature = "openai")]
 pub mod openai {
     pub use ferrous_llm_openai::*;
@@ -15,3 +20,11 @@ pub mod ollama {
 pub mod anthropic {
     pub use ferrous_llm_anthropic::*;
 }
+
+#[cfg(feature = "macros")]
+pub use ferrous_llm_macros::{ToolArgs, ToolSchema, tool};
+
+#[cfg(feature = "memory")]
+pub mod memory {
+    pub use ferrous_llm_memory::*;
+}